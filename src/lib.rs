@@ -17,6 +17,10 @@
 //! - [Merge](mesh/struct.Mesh.html#merge) used for merging of entire meshes (e.g. append one mesh to another or merge overlapping primitives in a mesh)
 //! - [Split](mesh/struct.Mesh.html#split) functionality (e.g. clone a subset of a mesh or split two meshes at their intersection)
 //! - [Export](mesh/struct.Mesh.html#export) functionality (methods for extracting raw float buffers which can be used for visualisation)
+//! - [Vertex attributes](mesh/struct.Mesh.html#vertex-attributes) for storing custom data on each vertex (e.g. texture coordinates or colors)
+//! - [Corner attributes](mesh/struct.Mesh.html#corner-attributes) for storing custom data on each corner of each face (e.g. texture coordinates with seams)
+//! - [Compact](mesh/struct.Mesh.html#compact) functionality for renumbering the primitives densely after editing
+//! - [Transactions](mesh/struct.Mesh.html#transactions) for recording changes to the mesh which can be rolled back or replayed (e.g. undo/redo)
 //! - And more..
 //!
 //! All functionality is implemented as methods on the [Mesh](crate::mesh::Mesh) struct, so take a look at that rather long list of methods for a complete overview.
@@ -29,4 +33,7 @@ pub mod mesh;
 pub mod mesh_builder;
 pub mod prelude;
 
+#[cfg(test)]
+mod test_utils;
+
 pub use crate::mesh_builder::MeshBuilder as MeshBuilder;
@@ -10,6 +10,8 @@ pub mod math {
     pub use cgmath;
     pub use cgmath::*;
 
+    /// Vector with two elements.
+    pub type Vec2 = Vector2<f64>;
     /// Vector with three elements.
     pub type Vec3 = Vector3<f64>;
     /// Vector with four elements.
@@ -37,8 +39,16 @@ pub mod intersection;
 pub mod merge;
 pub mod split;
 pub mod export;
+#[cfg(feature = "obj-io")]
+pub mod material;
+#[cfg(feature = "3d-io")]
+pub mod format_3d;
 pub mod connected_components;
+pub mod convex_hull;
 pub mod validity;
+pub mod attributes;
+pub mod compact;
+pub mod transaction;
 
 mod connectivity_info;
 
@@ -66,6 +76,16 @@ pub enum Error {
         /// Error reason.
         message: String
     },
+    /// Returned when trying to access an attribute which does not exist or is of another type than the requested type.
+    AttributeNotFound {
+        /// Error reason.
+        message: String
+    },
+    /// Returned when beginning, committing, rolling back or replaying a [transaction](crate::mesh::transaction::Transaction) is not possible.
+    TransactionError {
+        /// Error reason.
+        message: String
+    },
     /// Invalid 3d file format
     #[cfg(feature = "3d-io")]
     Bincode(bincode::Error),
@@ -108,9 +128,23 @@ impl From<std::io::Error> for Error {
 /// - [Split](#split)
 /// - [Export](#export)
 /// - [Connected components](#connected-components)
+/// - [Convex hull](#convex-hull)
 /// - [Validity](#validity)
+/// - [Vertex attributes](#vertex-attributes)
+/// - [Corner attributes](#corner-attributes)
+/// - [Compact](#compact)
+/// - [Transactions](#transactions)
+///
+/// All mutation goes through `&mut self`, so a mesh is `Send` and `Sync` whenever the face tag `T` is.
+/// This means that read-only queries, for example measures, intersections and export, can be run on the same mesh from several threads.
+///
+/// With the `serde` feature, a mesh implements `Serialize` and `Deserialize`. The half-edge structure, including all ids,
+/// the face tags and the exact positions, is stored, so a deserialized mesh is identical to the original and requires no reconstruction.
+/// The [vertex attributes](#vertex-attributes) and [corner attributes](#corner-attributes) are not serialized.
+/// Deserialization fails if the ids of the half-edge structure do not refer to existing vertices, half-edges and faces.
 ///
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mesh<T> {
     connectivity_info: ConnectivityInfo<T>
 }
@@ -146,23 +180,28 @@ impl<T: Clone> Mesh<T>
             
             // mark twin halfedges
 			let mut walker = mesh.walker_from_face(face);
-            for _ in 0..3 {
+            let edges: Vec<_> = (0..3).map(|_| {
                 let vertex_id = walker.vertex_id().unwrap();
                 walker.as_next();
-                let key = sort(vertex_id, walker.vertex_id().unwrap());
+                (walker.halfedge_id().unwrap(), sort(vertex_id, walker.vertex_id().unwrap()))
+            }).collect();
+            for (halfedge_id, key) in edges {
                 if let Some(twin) = twins.get(&key) {
-                    mesh.connectivity_info.set_halfedge_twin(walker.halfedge_id().unwrap(), *twin);
+                    mesh.connectivity_info.set_halfedge_twin(halfedge_id, *twin);
                 }
                 else {
-                    twins.insert(key, walker.halfedge_id().unwrap());
+                    twins.insert(key, halfedge_id);
                 }
             }
         }
-        for halfedge in mesh.connectivity_info.halfedge_iterator() {
-			if mesh.connectivity_info.halfedge(halfedge).unwrap().twin.is_none() {
-				let vertex = mesh.walker_from_halfedge(halfedge).as_previous().vertex_id().unwrap();
-				mesh.connectivity_info.set_halfedge_twin(halfedge, mesh.connectivity_info.new_halfedge(Some(vertex), None, None));
-			}
+        for index in 0..mesh.num_halfedge_slots() {
+            if let Some(halfedge) = mesh.halfedge_in_slot(index) {
+                if mesh.connectivity_info.halfedge(halfedge).unwrap().twin.is_none() {
+                    let vertex = mesh.walker_from_halfedge(halfedge).as_previous().vertex_id().unwrap();
+                    let boundary_halfedge = mesh.connectivity_info.new_halfedge(Some(vertex), None, None);
+                    mesh.connectivity_info.set_halfedge_twin(halfedge, boundary_halfedge);
+                }
+            }
         }
         
         mesh
@@ -215,14 +254,15 @@ impl<T: Clone> Mesh<T>
 
     fn create_boundary_edges(&mut self)
     {
-        let mut walker = self.walker();
-        for halfedge_id in self.halfedge_iter()
-        {
-            walker.as_halfedge_walker(halfedge_id);
-            if walker.twin_id().is_none()
-            {
-                let boundary_halfedge_id = self.connectivity_info.new_halfedge(walker.as_previous().vertex_id(), None, None);
-                self.connectivity_info.set_halfedge_twin(halfedge_id, boundary_halfedge_id);
+        for index in 0..self.num_halfedge_slots() {
+            if let Some(halfedge_id) = self.halfedge_in_slot(index) {
+                let mut walker = self.walker_from_halfedge(halfedge_id);
+                if walker.twin_id().is_none()
+                {
+                    let vertex_id = walker.as_previous().vertex_id();
+                    let boundary_halfedge_id = self.connectivity_info.new_halfedge(vertex_id, None, None);
+                    self.connectivity_info.set_halfedge_twin(halfedge_id, boundary_halfedge_id);
+                }
             }
         }
     }
@@ -230,16 +270,16 @@ impl<T: Clone> Mesh<T>
     /// to avoid a Schlemiel the painter's algorithm when appending meshes
     fn create_boundary_edges_for_faces(&mut self, faces: &[FaceID])
     {
-        let mut walker = self.walker();
         for halfedge_id in faces.iter().copied()
             .flat_map(|f| self.face_halfedge_iter(f))
             .collect::<HashSet<_>>()
             .into_iter()
         {
-            walker.as_halfedge_walker(halfedge_id);
+            let mut walker = self.walker_from_halfedge(halfedge_id);
             if walker.twin_id().is_none()
             {
-                let boundary_halfedge_id = self.connectivity_info.new_halfedge(walker.as_previous().vertex_id(), None, None);
+                let vertex_id = walker.as_previous().vertex_id();
+                let boundary_halfedge_id = self.connectivity_info.new_halfedge(vertex_id, None, None);
                 self.connectivity_info.set_halfedge_twin(halfedge_id, boundary_halfedge_id);
             }
         }
@@ -260,21 +300,63 @@ impl<T: std::fmt::Debug> std::fmt::Display for Mesh<T> {
     }
 }
 
-#[cfg(feature = "3d-io")]
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
-pub(crate) struct IOMesh {
-    pub magic_number: u8, // Always 61
-    pub version: u8,
-    pub indices: Vec<u32>,
-    pub positions: Vec<f32>,
-    pub normals: Vec<f32>
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::MeshBuilder;
 
+    #[test]
+    fn test_mesh_is_send_and_sync() {
+        fn assert_send_sync<M: Send + Sync>() {}
+        assert_send_sync::<Mesh<()>>();
+        assert_send_sync::<Mesh<u32>>();
+    }
+
+    #[test]
+    fn test_queries_from_several_threads() {
+        let mut mesh = MeshBuilder::<()>::new().cube().build().unwrap();
+        mesh.add_vertex_attribute("x", |values: &[(f64, f64)]| values[0].0);
+        let mesh = std::sync::Arc::new(mesh);
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let mesh = mesh.clone();
+            std::thread::spawn(move || (mesh.num_faces(), mesh.positions_buffer().len(), mesh.is_closed()))
+        }).collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), (12, 24, true));
+        }
+    }
+
+    #[cfg(feature = "3d-io")]
+    #[test]
+    fn test_serialize_round_trip() {
+        let mut mesh = MeshBuilder::<u32>::new().with_tags((0..20).collect()).icosahedron().build().unwrap();
+        let halfedge_id = mesh.halfedge_iter().nth(3).unwrap();
+        mesh.collapse_edge(halfedge_id);
+        mesh.move_vertex_by(mesh.vertex_iter().next().unwrap(), vec3(0.1, 1.0/3.0, -0.7));
+
+        let encoded = bincode::serialize(&mesh).unwrap();
+        let mut decoded: Mesh<u32> = bincode::deserialize(&encoded).unwrap();
+        decoded.is_valid().unwrap();
+
+        assert_eq!(mesh.vertex_iter().collect::<Vec<_>>(), decoded.vertex_iter().collect::<Vec<_>>());
+        assert_eq!(mesh.halfedge_iter().collect::<Vec<_>>(), decoded.halfedge_iter().collect::<Vec<_>>());
+        assert_eq!(mesh.face_iter().collect::<Vec<_>>(), decoded.face_iter().collect::<Vec<_>>());
+        for vertex_id in mesh.vertex_iter() {
+            assert_eq!(mesh.vertex_position(vertex_id), decoded.vertex_position(vertex_id));
+        }
+        for halfedge_id in mesh.halfedge_iter() {
+            assert_eq!(mesh.connectivity_info.halfedge(halfedge_id), decoded.connectivity_info.halfedge(halfedge_id));
+        }
+        for face_id in mesh.face_iter() {
+            assert_eq!(mesh.face_tag(face_id), decoded.face_tag(face_id));
+        }
+
+        // The free slots are preserved, so further editing gives the same ids
+        let face_id = mesh.face_iter().next().unwrap();
+        assert_eq!(mesh.split_face(face_id, vec3(0.0, 0.0, 0.0)), decoded.split_face(face_id, vec3(0.0, 0.0, 0.0)));
+    }
+
     #[test]
     fn test_one_face_connectivity() {
         let mesh = Mesh::new(vec![0, 1, 2], vec![()], vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
@@ -0,0 +1,681 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use crate::mesh::ids::*;
+
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+///
+/// # Vertex attributes
+///
+/// Methods for storing custom data (for example texture coordinates or colors) on each vertex.
+/// A vertex attribute is identified by a name and holds a value of any type `A: Clone` for each vertex.
+/// The values are kept in sync when vertices are created or removed by the [edit](#edit), [merge](#merge) and [split](#split) functionality,
+/// and the value of a new vertex is computed by the interpolation function given when the attribute is added.
+///
+/// # Examples
+///
+/// ```
+/// # use tri_mesh::prelude::*;
+/// # fn main() -> Result<(), tri_mesh::mesh::Error> {
+/// let mut mesh = MeshBuilder::<()>::new().square().build().unwrap();
+///
+/// // Add an attribute where the value of a new vertex is the weighted average of the values of the vertices it is created from
+/// mesh.add_vertex_attribute("temperature", |values: &[(f64, f64)]| values.iter().map(|(value, weight)| weight * value).sum());
+/// let temperatures: Vec<_> = mesh.vertex_iter().map(|vertex_id| (vertex_id, mesh.vertex_position(vertex_id).x)).collect();
+/// for (vertex_id, temperature) in temperatures {
+///     mesh.set_vertex_attribute("temperature", vertex_id, temperature)?;
+/// }
+///
+/// // Splitting an edge creates a new vertex with an interpolated temperature
+/// let halfedge_id = mesh.edge_iter().find(|halfedge_id| !mesh.is_edge_on_boundary(*halfedge_id)).unwrap();
+/// let (p0, p1) = mesh.edge_positions(halfedge_id);
+/// let (vertex_id, _) = mesh.split_edge(halfedge_id, 0.5 * (p0 + p1));
+/// assert_eq!(mesh.vertex_attribute::<f64>("temperature", vertex_id), Some(0.0));
+/// # Ok(())
+/// # }
+/// ```
+///
+impl<T: Clone> Mesh<T>
+{
+    ///
+    /// Adds a vertex attribute with the given name, replacing any existing attribute with that name.
+    /// Initially, no vertex has a value for the new attribute.
+    ///
+    /// The `interpolate` function computes the value of a vertex created by for example [split_edge](#method.split_edge),
+    /// [split_face](#method.split_face) or [collapse_edge](#method.collapse_edge) from the values of the vertices it is created from.
+    /// It is given the values together with weights which sum to one.
+    /// The value type and the function must be `Send + Sync`, so the mesh can still be shared between threads.
+    ///
+    pub fn add_vertex_attribute<A: Clone + Send + Sync + 'static>(&mut self, name: &str, interpolate: impl Fn(&[(A, f64)]) -> A + Send + Sync + 'static)
+    {
+        self.connectivity_info.vertex_attributes_mut().add(name, interpolate);
+    }
+
+    /// Removes the vertex attribute with the given name. Returns whether or not the attribute existed.
+    pub fn remove_vertex_attribute(&mut self, name: &str) -> bool
+    {
+        self.connectivity_info.vertex_attributes_mut().remove_attribute(name)
+    }
+
+    /// Returns whether or not a vertex attribute with the given name exists.
+    pub fn has_vertex_attribute(&self, name: &str) -> bool
+    {
+        self.connectivity_info.vertex_attributes().contains(name)
+    }
+
+    ///
+    /// Returns the value of the vertex attribute with the given name for the given vertex
+    /// or `None` if the attribute does not exist, is not of type `A` or the vertex has no value.
+    ///
+    pub fn vertex_attribute<A: Clone + 'static>(&self, name: &str, vertex_id: VertexID) -> Option<A>
+    {
+        self.connectivity_info.vertex_attributes().get(name, vertex_id)
+    }
+
+    ///
+    /// Sets the value of the vertex attribute with the given name for the given vertex.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the attribute does not exist or is not of type `A`.
+    ///
+    pub fn set_vertex_attribute<A: Clone + 'static>(&mut self, name: &str, vertex_id: VertexID, value: A) -> Result<(), Error>
+    {
+        self.connectivity_info.vertex_attributes_mut().set(name, vertex_id, value)
+    }
+
+    pub(super) fn interpolate_vertex_attributes(&mut self, vertex_id: VertexID, sources: &[(VertexID, f64)])
+    {
+        self.connectivity_info.vertex_attributes_mut().interpolate(&[(vertex_id, sources.to_vec())]);
+    }
+}
+
+///
+/// # Corner attributes
+///
+/// Methods for storing custom data on each corner of each face, i.e. data which can have different values for each of the faces around a vertex.
+/// This is for example needed for texture coordinates at a UV seam or for split normals at a sharp edge.
+/// A corner is identified by the half-edge which points to the vertex of the corner and is adjacent to the face of the corner.
+///
+/// The values are kept in sync by [flip_edge](#method.flip_edge), [split_edge](#method.split_edge), [split_face](#method.split_face)
+/// and [collapse_edge](#method.collapse_edge). The value of a new corner is interpolated from the corners of the original face,
+/// so seams are preserved. A collapsed edge keeps the values of the remaining corners.
+/// Use the [export](#export) functionality to extract the corner attributes as buffers.
+///
+/// # Examples
+///
+/// ```
+/// # use tri_mesh::prelude::*;
+/// # fn main() -> Result<(), tri_mesh::mesh::Error> {
+/// let mut mesh = MeshBuilder::<()>::new().square().build().unwrap();
+///
+/// // Give each face its own texture coordinates
+/// mesh.add_corner_attribute("uv", |values: &[((f64, f64), f64)]| values.iter()
+///     .fold((0.0, 0.0), |(u, v), ((value_u, value_v), weight)| (u + weight * value_u, v + weight * value_v)));
+/// let mut uvs = Vec::new();
+/// for (i, face_id) in mesh.face_iter().enumerate() {
+///     for halfedge_id in mesh.face_halfedge_iter(face_id) {
+///         let position = mesh.vertex_position(mesh.walker_from_halfedge(halfedge_id).vertex_id().unwrap());
+///         uvs.push((halfedge_id, (0.5 * position.x + i as f64, 0.5 * position.y)));
+///     }
+/// }
+/// for (halfedge_id, uv) in uvs {
+///     mesh.set_corner_attribute("uv", halfedge_id, uv)?;
+/// }
+///
+/// // The vertices on the edge between the two faces are split in the exported buffers
+/// let (indices, positions, uvs) = mesh.seam_split_buffers::<(f64, f64)>("uv");
+/// assert_eq!(indices.len(), 6);
+/// assert_eq!(positions.len(), 6 * 3);
+/// assert_eq!(uvs.len(), 6);
+/// # Ok(())
+/// # }
+/// ```
+///
+impl<T: Clone> Mesh<T>
+{
+    ///
+    /// Adds a corner attribute with the given name, replacing any existing attribute with that name.
+    /// Initially, no corner has a value for the new attribute.
+    ///
+    /// The `interpolate` function computes the value of a new corner, for example created by [split_edge](#method.split_edge)
+    /// or [split_face](#method.split_face), from the values of the corners of the original face.
+    /// It is given the values together with weights which sum to one.
+    ///
+    pub fn add_corner_attribute<A: Clone + Send + Sync + 'static>(&mut self, name: &str, interpolate: impl Fn(&[(A, f64)]) -> A + Send + Sync + 'static)
+    {
+        self.connectivity_info.corner_attributes_mut().add(name, interpolate);
+    }
+
+    /// Removes the corner attribute with the given name. Returns whether or not the attribute existed.
+    pub fn remove_corner_attribute(&mut self, name: &str) -> bool
+    {
+        self.connectivity_info.corner_attributes_mut().remove_attribute(name)
+    }
+
+    /// Returns whether or not a corner attribute with the given name exists.
+    pub fn has_corner_attribute(&self, name: &str) -> bool
+    {
+        self.connectivity_info.corner_attributes().contains(name)
+    }
+
+    ///
+    /// Returns the value of the corner attribute with the given name for the corner given by a half-edge
+    /// or `None` if the attribute does not exist, is not of type `A` or the corner has no value.
+    ///
+    pub fn corner_attribute<A: Clone + 'static>(&self, name: &str, halfedge_id: HalfEdgeID) -> Option<A>
+    {
+        self.connectivity_info.corner_attributes().get(name, halfedge_id)
+    }
+
+    ///
+    /// Sets the value of the corner attribute with the given name for the corner given by a half-edge.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the attribute does not exist, is not of type `A` or if the half-edge is not adjacent to a face.
+    ///
+    pub fn set_corner_attribute<A: Clone + 'static>(&mut self, name: &str, halfedge_id: HalfEdgeID, value: A) -> Result<(), Error>
+    {
+        if self.walker_from_halfedge(halfedge_id).face_id().is_none() {
+            return Err(Error::AttributeNotFound { message: format!("The half-edge {} is on the boundary and does not represent a corner", halfedge_id) });
+        }
+        self.connectivity_info.corner_attributes_mut().set(name, halfedge_id, value)
+    }
+
+    pub(super) fn interpolate_corner_attributes(&mut self, targets: &[(HalfEdgeID, Vec<(HalfEdgeID, f64)>)])
+    {
+        self.connectivity_info.corner_attributes_mut().interpolate(targets);
+    }
+}
+
+/// A set of named attributes which each store a value of some type for each ID.
+pub(crate) struct Attributes<K>
+{
+    channels: HashMap<String, Box<dyn Channel<K>>>,
+    journal: Option<Box<AttributesJournal<K>>>
+}
+
+/// Records the values of each ID before it is changed for the first time in a transaction.
+struct AttributesJournal<K>
+{
+    ids: HashSet<K>,
+    before: Attributes<K>
+}
+
+/// The values of the IDs which were changed in a transaction, either before or after the changes.
+#[derive(Clone, Debug)]
+pub(crate) struct AttributesState<K>
+{
+    ids: Vec<K>,
+    values: Attributes<K>
+}
+
+impl<K: ID + Send + Sync + 'static> Attributes<K>
+{
+    pub fn new() -> Self
+    {
+        Attributes { channels: HashMap::new(), journal: None }
+    }
+
+    pub fn add<A: Clone + Send + Sync + 'static>(&mut self, name: &str, interpolate: impl Fn(&[(A, f64)]) -> A + Send + Sync + 'static)
+    {
+        self.channels.insert(name.to_string(), Box::new(TypedChannel { values: HashMap::new(), interpolate: Arc::new(interpolate) }));
+    }
+
+    pub fn remove_attribute(&mut self, name: &str) -> bool
+    {
+        self.channels.remove(name).is_some()
+    }
+
+    pub fn contains(&self, name: &str) -> bool
+    {
+        self.channels.contains_key(name)
+    }
+
+    pub fn get<A: Clone + 'static>(&self, name: &str, id: K) -> Option<A>
+    {
+        self.channels.get(name)
+            .and_then(|channel| channel.as_any().downcast_ref::<TypedChannel<K, A>>())
+            .and_then(|channel| channel.values.get(&id).cloned())
+    }
+
+    pub fn set<A: Clone + 'static>(&mut self, name: &str, id: K, value: A) -> Result<(), Error>
+    {
+        self.record(id);
+        let channel = self.channels.get_mut(name)
+            .ok_or_else(|| Error::AttributeNotFound { message: format!("No attribute with the name '{}'", name) })?
+            .as_any_mut().downcast_mut::<TypedChannel<K, A>>()
+            .ok_or_else(|| Error::AttributeNotFound { message: format!("The attribute '{}' is not of type {}", name, std::any::type_name::<A>()) })?;
+        channel.values.insert(id, value);
+        Ok(())
+    }
+
+    /// Removes the values of the given ID from all attributes.
+    pub fn remove(&mut self, id: K)
+    {
+        self.record(id);
+        for channel in self.channels.values_mut() {
+            channel.remove(id);
+        }
+    }
+
+    /// Sets the value of each target ID to the interpolation of the values of the weighted source IDs.
+    /// All values are computed before any value is changed, so a target can also be used as a source.
+    pub fn interpolate(&mut self, targets: &[(K, Vec<(K, f64)>)])
+    {
+        for (target, _) in targets.iter() {
+            self.record(*target);
+        }
+        for channel in self.channels.values_mut() {
+            channel.interpolate(targets);
+        }
+    }
+
+    /// Copies the values of the `other` attributes, where `mapping` maps from an ID in `other` to an ID in these attributes.
+    /// Attributes which only exist in `other` are added.
+    pub fn copy_from(&mut self, other: &Attributes<K>, mapping: &HashMap<K, K>)
+    {
+        for id in mapping.values() {
+            self.record(*id);
+        }
+        copy_channels(&other.channels, &mut self.channels, mapping);
+    }
+
+    /// Changes the IDs of all values according to `mapping`. Values of IDs which are not in the mapping are removed.
+    pub fn remap(&mut self, mapping: &HashMap<K, K>)
+    {
+        // Remapping changes every ID and can therefore not be part of a transaction
+        self.journal = None;
+        for channel in self.channels.values_mut() {
+            channel.remap(mapping);
+        }
+    }
+}
+
+impl<K: ID + Send + Sync + 'static> Attributes<K>
+{
+    /// Starts recording the changes to the values.
+    pub fn begin_journal(&mut self)
+    {
+        self.journal = Some(Box::new(AttributesJournal { ids: HashSet::new(), before: Attributes::new() }));
+    }
+
+    /// Stops recording the changes and returns the values of the changed IDs before and after the changes.
+    pub fn end_journal(&mut self) -> Option<(AttributesState<K>, AttributesState<K>)>
+    {
+        let journal = self.journal.take()?;
+        let ids: Vec<K> = journal.ids.into_iter().collect();
+        let mut after = Attributes::new();
+        copy_channels(&self.channels, &mut after.channels, &ids.iter().map(|id| (*id, *id)).collect());
+        Some((AttributesState { ids: ids.clone(), values: journal.before }, AttributesState { ids, values: after }))
+    }
+
+    /// Sets the values of the changed IDs to the given state.
+    pub fn restore(&mut self, state: &AttributesState<K>)
+    {
+        for channel in self.channels.values_mut() {
+            for id in state.ids.iter() {
+                channel.remove(*id);
+            }
+        }
+        copy_channels(&state.values.channels, &mut self.channels, &state.ids.iter().map(|id| (*id, *id)).collect());
+    }
+
+    fn record(&mut self, id: K)
+    {
+        if let Some(ref mut journal) = self.journal {
+            if journal.ids.insert(id) {
+                copy_channels(&self.channels, &mut journal.before.channels, &std::iter::once((id, id)).collect());
+            }
+        }
+    }
+}
+
+/// Copies the values from one set of channels to another, where `mapping` maps from an ID in `from` to an ID in `to`.
+/// Channels which only exist in `from` are added to `to`.
+fn copy_channels<K>(from: &HashMap<String, Box<dyn Channel<K>>>, to: &mut HashMap<String, Box<dyn Channel<K>>>, mapping: &HashMap<K, K>)
+{
+    for (name, from_channel) in from.iter() {
+        let channel = to.entry(name.clone()).or_insert_with(|| from_channel.empty_clone());
+        channel.copy_from(from_channel.as_ref(), mapping);
+    }
+}
+
+impl<K: ID + Send + Sync + 'static> Default for Attributes<K>
+{
+    fn default() -> Self
+    {
+        Attributes::new()
+    }
+}
+
+impl<K: Clone> Clone for Attributes<K>
+{
+    fn clone(&self) -> Self
+    {
+        // The journal is cloned as well, so a transaction in progress continues in the clone like in the other maps of the mesh
+        let journal = self.journal.as_ref().map(|journal| Box::new(AttributesJournal { ids: journal.ids.clone(), before: journal.before.clone() }));
+        Attributes { channels: self.channels.iter().map(|(name, channel)| (name.clone(), channel.box_clone())).collect(), journal }
+    }
+}
+
+impl<K> std::fmt::Debug for Attributes<K>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_set().entries(self.channels.keys()).finish()
+    }
+}
+
+trait Channel<K>: Send + Sync
+{
+    fn remove(&mut self, id: K);
+    fn interpolate(&mut self, targets: &[(K, Vec<(K, f64)>)]);
+    fn copy_from(&mut self, other: &dyn Channel<K>, mapping: &HashMap<K, K>);
+    fn remap(&mut self, mapping: &HashMap<K, K>);
+    fn empty_clone(&self) -> Box<dyn Channel<K>>;
+    fn box_clone(&self) -> Box<dyn Channel<K>>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+type Interpolation<A> = Arc<dyn Fn(&[(A, f64)]) -> A + Send + Sync>;
+
+struct TypedChannel<K, A>
+{
+    values: HashMap<K, A>,
+    interpolate: Interpolation<A>
+}
+
+impl<K: ID + Send + Sync + 'static, A: Clone + Send + Sync + 'static> Channel<K> for TypedChannel<K, A>
+{
+    fn remove(&mut self, id: K)
+    {
+        self.values.remove(&id);
+    }
+
+    fn interpolate(&mut self, targets: &[(K, Vec<(K, f64)>)])
+    {
+        let new_values: Vec<(K, Option<A>)> = targets.iter().map(|(target, sources)| {
+            let mut values: Vec<(A, f64)> = sources.iter()
+                .filter_map(|(source, weight)| self.values.get(source).map(|value| (value.clone(), *weight)))
+                .collect();
+            let value = match values.len() {
+                0 => None,
+                1 => values.pop().map(|(value, _)| value),
+                _ => {
+                    // Renormalize in case some of the sources have no value
+                    let sum: f64 = values.iter().map(|(_, weight)| weight).sum();
+                    values.iter_mut().for_each(|(_, weight)| *weight /= sum);
+                    Some((self.interpolate)(&values))
+                }
+            };
+            (*target, value)
+        }).collect();
+
+        for (id, value) in new_values {
+            match value {
+                Some(value) => { self.values.insert(id, value); },
+                None => { self.values.remove(&id); }
+            }
+        }
+    }
+
+    fn copy_from(&mut self, other: &dyn Channel<K>, mapping: &HashMap<K, K>)
+    {
+        if let Some(other) = other.as_any().downcast_ref::<TypedChannel<K, A>>() {
+            for (other_id, id) in mapping.iter() {
+                if let Some(value) = other.values.get(other_id) {
+                    self.values.insert(*id, value.clone());
+                }
+            }
+        }
+    }
+
+    fn remap(&mut self, mapping: &HashMap<K, K>)
+    {
+        self.values = self.values.drain().filter_map(|(id, value)| mapping.get(&id).map(|new_id| (*new_id, value))).collect();
+    }
+
+    fn empty_clone(&self) -> Box<dyn Channel<K>>
+    {
+        Box::new(TypedChannel { values: HashMap::<K, A>::new(), interpolate: self.interpolate.clone() })
+    }
+
+    fn box_clone(&self) -> Box<dyn Channel<K>>
+    {
+        Box::new(TypedChannel { values: self.values.clone(), interpolate: self.interpolate.clone() })
+    }
+
+    fn as_any(&self) -> &dyn Any
+    {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any
+    {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MeshBuilder;
+    use crate::mesh::math::*;
+
+    fn add_x_attribute<T: Clone>(mesh: &mut Mesh<T>)
+    {
+        mesh.add_vertex_attribute("x", |values: &[(f64, f64)]| values.iter().map(|(value, weight)| weight * value).sum());
+        for vertex_id in mesh.vertex_iter().collect::<Vec<_>>() {
+            let x = mesh.vertex_position(vertex_id).x;
+            mesh.set_vertex_attribute("x", vertex_id, x).unwrap();
+        }
+    }
+
+    fn assert_x_attribute<T: Clone>(mesh: &Mesh<T>)
+    {
+        for vertex_id in mesh.vertex_iter() {
+            let x = mesh.vertex_attribute::<f64>("x", vertex_id).unwrap();
+            assert!((x - mesh.vertex_position(vertex_id).x).abs() < 0.00001);
+        }
+    }
+
+    #[test]
+    fn test_set_and_get_vertex_attribute()
+    {
+        let mut mesh = MeshBuilder::<()>::new().triangle().build().unwrap();
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        assert!(mesh.set_vertex_attribute("color", vertex_id, 1u8).is_err());
+
+        mesh.add_vertex_attribute("color", |values: &[(u8, f64)]| values[0].0);
+        assert!(mesh.has_vertex_attribute("color"));
+        assert_eq!(mesh.vertex_attribute::<u8>("color", vertex_id), None);
+
+        mesh.set_vertex_attribute("color", vertex_id, 3u8).unwrap();
+        assert_eq!(mesh.vertex_attribute::<u8>("color", vertex_id), Some(3));
+        assert_eq!(mesh.vertex_attribute::<f64>("color", vertex_id), None);
+        assert!(mesh.set_vertex_attribute("color", vertex_id, 1.0f64).is_err());
+
+        assert!(mesh.remove_vertex_attribute("color"));
+        assert!(!mesh.has_vertex_attribute("color"));
+    }
+
+    #[test]
+    fn test_vertex_attribute_split_edge()
+    {
+        let mut mesh = MeshBuilder::<()>::new().square().build().unwrap();
+        add_x_attribute(&mut mesh);
+
+        for halfedge_id in mesh.edge_iter().collect::<Vec<_>>() {
+            let (p0, p1) = mesh.edge_positions(halfedge_id);
+            mesh.split_edge(halfedge_id, 0.25 * p0 + 0.75 * p1);
+        }
+        assert_eq!(mesh.num_vertices(), 9);
+        assert_x_attribute(&mesh);
+    }
+
+    #[test]
+    fn test_vertex_attribute_split_face()
+    {
+        let mut mesh = MeshBuilder::<()>::new().triangle().build().unwrap();
+        add_x_attribute(&mut mesh);
+
+        let face_id = mesh.face_iter().next().unwrap();
+        let vertex_id = mesh.split_face(face_id, vec3(1.0, 0.0, 0.0));
+        assert_eq!(mesh.vertex_attribute::<f64>("x", vertex_id), Some(1.0));
+        assert_x_attribute(&mesh);
+    }
+
+    #[test]
+    fn test_vertex_attribute_collapse_edge()
+    {
+        let mut mesh = MeshBuilder::<()>::new().subdivided_triangle().build().unwrap();
+        add_x_attribute(&mut mesh);
+
+        let halfedge_id = mesh.halfedge_iter().find(|halfedge_id| !mesh.is_edge_on_boundary(*halfedge_id)).unwrap();
+        let vertex_id = mesh.collapse_edge(halfedge_id);
+        assert!(mesh.vertex_attribute::<f64>("x", vertex_id).is_some());
+        assert_x_attribute(&mesh);
+    }
+
+    #[test]
+    fn test_vertex_attribute_append_and_merge()
+    {
+        let mut mesh1 = MeshBuilder::<()>::new().with_indices(vec![0, 1, 2])
+            .with_positions(vec![-2.0, 0.0, -2.0, -2.0, 0.0, 2.0, 2.0, 0.0, 0.0]).build().unwrap();
+        add_x_attribute(&mut mesh1);
+        let mut mesh2 = MeshBuilder::<()>::new().with_indices(vec![0, 1, 2])
+            .with_positions(vec![-2.0, 0.0, 2.0, -2.0, 0.0, -2.0, -2.0, 0.5, 0.0]).build().unwrap();
+        add_x_attribute(&mut mesh2);
+
+        let mut appended = mesh1.clone();
+        appended.append(&mesh2);
+        assert_eq!(appended.num_vertices(), 6);
+        assert_x_attribute(&appended);
+
+        mesh1.merge_with(&mesh2).unwrap();
+        assert_eq!(mesh1.num_vertices(), 4);
+        assert_x_attribute(&mesh1);
+    }
+
+    fn add_corner_attribute<T: Clone>(mesh: &mut Mesh<T>)
+    {
+        mesh.add_corner_attribute("x", |values: &[(f64, f64)]| values.iter().map(|(value, weight)| weight * value).sum());
+        for halfedge_id in mesh.halfedge_iter().collect::<Vec<_>>() {
+            let walker = mesh.walker_from_halfedge(halfedge_id);
+            if walker.face_id().is_some() {
+                let x = mesh.vertex_position(walker.vertex_id().unwrap()).x;
+                mesh.set_corner_attribute("x", halfedge_id, x).unwrap();
+            }
+        }
+    }
+
+    fn assert_corner_attribute<T: Clone>(mesh: &Mesh<T>)
+    {
+        for halfedge_id in mesh.halfedge_iter() {
+            let walker = mesh.walker_from_halfedge(halfedge_id);
+            let x = mesh.corner_attribute::<f64>("x", halfedge_id);
+            if walker.face_id().is_some() {
+                assert!((x.unwrap() - mesh.vertex_position(walker.vertex_id().unwrap()).x).abs() < 0.00001);
+            }
+            else {
+                assert!(x.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_corner_attribute_on_boundary()
+    {
+        let mut mesh = MeshBuilder::<()>::new().triangle().build().unwrap();
+        mesh.add_corner_attribute("x", |values: &[(f64, f64)]| values[0].0);
+        let halfedge_id = mesh.halfedge_iter().find(|halfedge_id| mesh.walker_from_halfedge(*halfedge_id).face_id().is_none()).unwrap();
+        assert!(mesh.set_corner_attribute("x", halfedge_id, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_corner_attribute_flip_edge()
+    {
+        let mut mesh = MeshBuilder::<()>::new().square().build().unwrap();
+        add_corner_attribute(&mut mesh);
+
+        let halfedge_id = mesh.edge_iter().find(|halfedge_id| !mesh.is_edge_on_boundary(*halfedge_id)).unwrap();
+        mesh.flip_edge(halfedge_id).unwrap();
+        assert_corner_attribute(&mesh);
+    }
+
+    #[test]
+    fn test_corner_attribute_split_edge()
+    {
+        let mut mesh = MeshBuilder::<()>::new().square().build().unwrap();
+        add_corner_attribute(&mut mesh);
+
+        for halfedge_id in mesh.halfedge_iter().collect::<Vec<_>>() {
+            let (p0, p1) = mesh.edge_positions(halfedge_id);
+            mesh.split_edge(halfedge_id, 0.25 * p0 + 0.75 * p1);
+            assert_corner_attribute(&mesh);
+        }
+    }
+
+    #[test]
+    fn test_corner_attribute_split_face()
+    {
+        let mut mesh = MeshBuilder::<()>::new().subdivided_triangle().build().unwrap();
+        add_corner_attribute(&mut mesh);
+
+        for face_id in mesh.face_iter().collect::<Vec<_>>() {
+            let center = mesh.face_center(face_id);
+            mesh.split_face(face_id, center);
+        }
+        assert_eq!(mesh.num_faces(), 9);
+        assert_corner_attribute(&mesh);
+    }
+
+    #[test]
+    fn test_corner_attribute_collapse_edge()
+    {
+        let mut mesh = MeshBuilder::<()>::new().subdivided_triangle().build().unwrap();
+        mesh.add_corner_attribute("face", |values: &[(u32, f64)]| values[0].0);
+        for (i, face_id) in mesh.face_iter().collect::<Vec<_>>().into_iter().enumerate() {
+            for halfedge_id in mesh.face_halfedge_iter(face_id).collect::<Vec<_>>() {
+                mesh.set_corner_attribute("face", halfedge_id, i as u32).unwrap();
+            }
+        }
+
+        let halfedge_id = mesh.halfedge_iter().find(|halfedge_id| !mesh.is_edge_on_boundary(*halfedge_id)).unwrap();
+        mesh.collapse_edge(halfedge_id);
+        let face_id = mesh.face_iter().next().unwrap();
+        let values: Vec<_> = mesh.face_halfedge_iter(face_id).map(|halfedge_id| mesh.corner_attribute::<u32>("face", halfedge_id)).collect();
+        assert!(values[0].is_some());
+        assert_eq!(values[0], values[1]);
+        assert_eq!(values[0], values[2]);
+    }
+
+    #[test]
+    fn test_corner_attribute_append()
+    {
+        let mut mesh1 = MeshBuilder::<()>::new().square().build().unwrap();
+        add_corner_attribute(&mut mesh1);
+        let mut mesh2 = MeshBuilder::<()>::new().icosahedron().build().unwrap();
+        add_corner_attribute(&mut mesh2);
+
+        mesh1.append(&mesh2);
+        assert_corner_attribute(&mesh1);
+    }
+
+    #[test]
+    fn test_vertex_attribute_clone_subset()
+    {
+        let mut mesh = MeshBuilder::<()>::new().subdivided_triangle().build().unwrap();
+        add_x_attribute(&mut mesh);
+
+        let face_id = mesh.face_iter().next().unwrap();
+        let subset = mesh.clone_subset(&|_, f| f == face_id);
+        assert_eq!(subset.num_vertices(), 3);
+        assert_x_attribute(&subset);
+    }
+}
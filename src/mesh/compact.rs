@@ -0,0 +1,125 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+
+///
+/// Tables mapping the ids before a call to [compact](crate::mesh::Mesh::compact) to the ids after.
+/// Use these to update any data which is stored outside the mesh and keyed by ids.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Remapping {
+    /// Maps old vertex ids to new vertex ids
+    pub vertices: HashMap<VertexID, VertexID>,
+    /// Maps old half-edge ids to new half-edge ids
+    pub halfedges: HashMap<HalfEdgeID, HalfEdgeID>,
+    /// Maps old face ids to new face ids
+    pub faces: HashMap<FaceID, FaceID>
+}
+
+/// # Compact
+impl<T: Clone> Mesh<T>
+{
+    ///
+    /// Renumbers the vertices, half-edges and faces, such that the ids are dense, ie. without gaps left by removed primitives.
+    /// This is useful after a long editing session (e.g. after many calls to [collapse_edge](#method.collapse_edge)) to reduce memory
+    /// usage and to get a more coherent ordering of the exported buffers.
+    /// The vertex and corner attributes are updated accordingly.
+    ///
+    /// All ids obtained before the call are invalidated. Use the returned [remapping](crate::mesh::compact::Remapping) tables
+    /// to update any ids stored outside the mesh.
+    /// A [transaction](#transactions) in progress is ended without being committed and previously committed transactions can no longer be rolled back or replayed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// let mut mesh = MeshBuilder::<()>::new().subdivided_triangle().build().unwrap();
+    /// let vertex_id = mesh.vertex_iter().last().unwrap();
+    /// let position = mesh.vertex_position(vertex_id);
+    ///
+    /// // Remove a vertex in the middle
+    /// let halfedge_id = mesh.halfedge_iter().find(|halfedge_id| !mesh.is_edge_on_boundary(*halfedge_id)).unwrap();
+    /// mesh.collapse_edge(halfedge_id);
+    ///
+    /// let remapping = mesh.compact();
+    /// if let Some(new_vertex_id) = remapping.vertices.get(&vertex_id) {
+    ///     assert_eq!(mesh.vertex_position(*new_vertex_id), position);
+    /// }
+    /// ```
+    ///
+    pub fn compact(&mut self) -> Remapping
+    {
+        let (vertices, halfedges, faces) = self.connectivity_info.compact();
+        Remapping { vertices, halfedges, faces }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MeshBuilder;
+
+    #[test]
+    fn test_compact()
+    {
+        let mut mesh = MeshBuilder::<usize>::new().with_tags(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19])
+            .icosahedron().build().unwrap();
+        mesh.add_vertex_attribute("x", |values: &[(f64, f64)]| values.iter().map(|(value, weight)| weight * value).sum());
+        for index in 0..mesh.num_vertex_slots() {
+            if let Some(vertex_id) = mesh.vertex_in_slot(index) {
+                let x = mesh.vertex_position(vertex_id).x;
+                mesh.set_vertex_attribute("x", vertex_id, x).unwrap();
+            }
+        }
+        for _ in 0..3 {
+            let halfedge_id = mesh.halfedge_iter().nth(5).unwrap();
+            mesh.collapse_edge(halfedge_id);
+        }
+
+        let positions: Vec<_> = mesh.vertex_iter().map(|vertex_id| (vertex_id, mesh.vertex_position(vertex_id))).collect();
+        let tags: Vec<_> = mesh.face_iter().map(|face_id| (face_id, mesh.face_tag(face_id))).collect();
+        let (num_vertices, num_halfedges, num_faces) = (mesh.num_vertices(), mesh.num_halfedges(), mesh.num_faces());
+
+        let remapping = mesh.compact();
+        mesh.is_valid().unwrap();
+
+        assert_eq!((num_vertices, num_halfedges, num_faces), (mesh.num_vertices(), mesh.num_halfedges(), mesh.num_faces()));
+        assert_eq!(remapping.vertices.len(), num_vertices);
+        assert_eq!(remapping.halfedges.len(), num_halfedges);
+        assert_eq!(remapping.faces.len(), num_faces);
+        assert!(mesh.vertex_iter().all(|vertex_id| (vertex_id.deref() as usize) < num_vertices));
+        assert!(mesh.halfedge_iter().all(|halfedge_id| (halfedge_id.deref() as usize) < num_halfedges));
+        assert!(mesh.face_iter().all(|face_id| (face_id.deref() as usize) < num_faces));
+
+        for (old_vertex_id, position) in positions {
+            let vertex_id = remapping.vertices[&old_vertex_id];
+            assert_eq!(mesh.vertex_position(vertex_id), position);
+            assert_eq!(mesh.vertex_attribute::<f64>("x", vertex_id), Some(position.x));
+        }
+        for (old_face_id, tag) in tags {
+            assert_eq!(mesh.face_tag(remapping.faces[&old_face_id]), tag);
+        }
+    }
+
+    #[cfg(feature = "generational-ids")]
+    #[test]
+    fn test_old_ids_are_invalid_after_compact()
+    {
+        let mut mesh = MeshBuilder::<()>::new().subdivided_triangle().build().unwrap();
+        let halfedge_id = mesh.halfedge_iter().find(|halfedge_id| !mesh.is_edge_on_boundary(*halfedge_id)).unwrap();
+        mesh.collapse_edge(halfedge_id);
+        let old_halfedge_ids: Vec<_> = mesh.halfedge_iter().collect();
+
+        let remapping = mesh.compact();
+        for old_halfedge_id in old_halfedge_ids {
+            assert!(mesh.walker_from_halfedge(old_halfedge_id).vertex_id().is_none());
+            assert!(mesh.walker_from_halfedge(remapping.halfedges[&old_halfedge_id]).vertex_id().is_some());
+        }
+
+        // New primitives do not reuse the old ids either
+        let face_id = mesh.face_iter().next().unwrap();
+        let center = mesh.face_center(face_id);
+        mesh.split_face(face_id, center);
+        assert!(mesh.halfedge_iter().all(|halfedge_id| !remapping.halfedges.contains_key(&halfedge_id)));
+    }
+}
@@ -1,41 +1,134 @@
-use std::cell::{RefCell};
+use std::collections::HashMap;
+use std::marker::PhantomData;
 use crate::mesh::ids::*;
 use crate::mesh::math::Vec3;
+use crate::mesh::attributes::{Attributes, AttributesState};
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ConnectivityInfoData<T>"))]
 pub(crate) struct ConnectivityInfo<T> {
-    vertices: RefCell<IDMap<VertexID, Vertex>>,
-    halfedges: RefCell<IDMap<HalfEdgeID, HalfEdge>>,
-    faces: RefCell<IDMap<FaceID, Face<T>>>
+    vertices: IDMap<VertexID, Vertex>,
+    halfedges: IDMap<HalfEdgeID, HalfEdge>,
+    faces: IDMap<FaceID, Face<T>>,
+    // The attributes contain functions and are therefore not serialized
+    #[cfg_attr(feature = "serde", serde(skip))]
+    vertex_attributes: Attributes<VertexID>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    corner_attributes: Attributes<HalfEdgeID>
 }
 
 impl<T> ConnectivityInfo<T> {
     pub fn new(num_vertices: usize, num_faces: usize) -> ConnectivityInfo<T>
     {
         ConnectivityInfo {
-            vertices: RefCell::new(IDMap::with_capacity(num_vertices)),
-            halfedges: RefCell::new(IDMap::with_capacity(4 * num_faces)),
-            faces: RefCell::new(IDMap::with_capacity(num_faces))
+            vertices: IDMap::with_capacity(num_vertices),
+            halfedges: IDMap::with_capacity(4 * num_faces),
+            faces: IDMap::with_capacity(num_faces),
+            vertex_attributes: Attributes::new(),
+            corner_attributes: Attributes::new()
         }
     }
 
     pub fn num_vertices(&self) -> usize
     {
-        RefCell::borrow(&self.vertices).len()
+        self.vertices.len()
     }
 
     pub fn num_halfedges(&self) -> usize
     {
-        RefCell::borrow(&self.halfedges).len()
+        self.halfedges.len()
     }
 
     pub fn num_faces(&self) -> usize
     {
-        RefCell::borrow(&self.faces).len()
+        self.faces.len()
     }
 
+    pub fn vertex_iterator(&self) -> IDIter<'_, VertexID, Vertex>
+    {
+        self.vertices.iter()
+    }
+
+    pub fn halfedge_iterator(&self) -> IDIter<'_, HalfEdgeID, HalfEdge>
+    {
+        self.halfedges.iter()
+    }
+
+    pub fn face_iterator(&self) -> IDIter<'_, FaceID, Face<T>>
+    {
+        self.faces.iter()
+    }
+
+    pub fn num_vertex_slots(&self) -> u32
+    {
+        self.vertices.num_slots()
+    }
+
+    pub fn num_halfedge_slots(&self) -> u32
+    {
+        self.halfedges.num_slots()
+    }
+
+    pub fn num_face_slots(&self) -> u32
+    {
+        self.faces.num_slots()
+    }
+
+    pub fn vertex_in_slot(&self, index: u32) -> Option<VertexID>
+    {
+        self.vertices.id_in_slot(index)
+    }
+
+    pub fn halfedge_in_slot(&self, index: u32) -> Option<HalfEdgeID>
+    {
+        self.halfedges.id_in_slot(index)
+    }
+
+    pub fn face_in_slot(&self, index: u32) -> Option<FaceID>
+    {
+        self.faces.id_in_slot(index)
+    }
+
+    pub(crate) fn vertex_exists(&self, vertex_id: VertexID) -> bool
+    {
+        self.vertices.get(vertex_id).is_some()
+    }
+
+    pub fn vertex_halfedge(&self, vertex_id: VertexID) -> Option<HalfEdgeID>
+    {
+        self.vertices.get(vertex_id).unwrap().halfedge.clone()
+    }
+
+    pub fn halfedge(&self, halfedge_id: HalfEdgeID) -> Option<HalfEdge>
+    {
+        self.halfedges.get(halfedge_id).and_then(|halfedge| Some(halfedge.clone()))
+    }
+
+    pub fn face_halfedge(&self, face_id: FaceID) -> Option<HalfEdgeID>
+    {
+        self.faces.get(face_id).unwrap().halfedge.clone()
+    }
+
+    pub fn position(&self, vertex_id: VertexID) -> Vec3
+    {
+        self.vertices.get(vertex_id).unwrap().position
+    }
+
+    pub fn vertex_attributes(&self) -> &Attributes<VertexID>
+    {
+        &self.vertex_attributes
+    }
+
+    pub fn corner_attributes(&self) -> &Attributes<HalfEdgeID>
+    {
+        &self.corner_attributes
+    }
+}
+
+impl<T: Clone> ConnectivityInfo<T> {
     // Creates a face and the three internal half-edges and connects them to eachother and to the three given vertices
-    pub fn create_face(&self, vertex_id1: VertexID, vertex_id2: VertexID, vertex_id3: VertexID, tag: T) -> FaceID
+    pub fn create_face(&mut self, vertex_id1: VertexID, vertex_id2: VertexID, vertex_id3: VertexID, tag: T) -> FaceID
     {
         let id = self.new_face(tag);
 
@@ -55,7 +148,7 @@ impl<T> ConnectivityInfo<T> {
         id
     }
 
-    pub fn create_face_with_existing_halfedge(&self, vertex_id1: VertexID, vertex_id2: VertexID, vertex_id3: VertexID, halfedge_id: HalfEdgeID,
+    pub fn create_face_with_existing_halfedge(&mut self, vertex_id1: VertexID, vertex_id2: VertexID, vertex_id3: VertexID, halfedge_id: HalfEdgeID,
         tag: T) -> FaceID
     {
         let id = self.new_face(tag);
@@ -76,145 +169,204 @@ impl<T> ConnectivityInfo<T> {
         id
     }
 
-    pub fn new_vertex(&self, position: Vec3) -> VertexID
+    pub fn new_vertex(&mut self, position: Vec3) -> VertexID
     {
-        let vertices = &mut *RefCell::borrow_mut(&self.vertices);
-        vertices.insert_new(Vertex { halfedge: None, position }).unwrap()
+        self.vertices.insert_new(Vertex { halfedge: None, position }).unwrap()
     }
 
-    pub fn new_halfedge(&self, vertex: Option<VertexID>, next: Option<HalfEdgeID>, face: Option<FaceID>) -> HalfEdgeID
+    pub fn new_halfedge(&mut self, vertex: Option<VertexID>, next: Option<HalfEdgeID>, face: Option<FaceID>) -> HalfEdgeID
     {
-        let halfedges = &mut *RefCell::borrow_mut(&self.halfedges);
-        halfedges.insert_new(HalfEdge { vertex, twin: None, next, face }).unwrap()
+        self.halfedges.insert_new(HalfEdge { vertex, twin: None, next, face }).unwrap()
     }
 
-    fn new_face(&self, tag: T) -> FaceID
+    fn new_face(&mut self, tag: T) -> FaceID
     {
-        let faces = &mut *RefCell::borrow_mut(&self.faces);
-        faces.insert_new(Face { halfedge: None, tag }).unwrap()
+        self.faces.insert_new(Face { halfedge: None, tag }).unwrap()
     }
 
-    pub fn remove_vertex(&self, vertex_id: VertexID)
+    pub fn remove_vertex(&mut self, vertex_id: VertexID)
     {
-        let vertices = &mut *RefCell::borrow_mut(&self.vertices);
-        vertices.remove(vertex_id);
+        self.vertices.remove(vertex_id);
+        self.vertex_attributes.remove(vertex_id);
     }
 
-    pub fn remove_halfedge(&self, halfedge_id: HalfEdgeID)
+    pub fn remove_halfedge(&mut self, halfedge_id: HalfEdgeID)
     {
-        let halfedges = &mut *RefCell::borrow_mut(&self.halfedges);
+        let halfedges = &mut self.halfedges;
         let halfedge = halfedges.get(halfedge_id).unwrap();
         if let Some(twin_id) = halfedge.twin
         {
             halfedges.get_mut(twin_id).unwrap().twin = None;
         }
         halfedges.remove(halfedge_id);
+        self.corner_attributes.remove(halfedge_id);
     }
 
-    pub fn remove_face(&self, face_id: FaceID)
+    pub fn remove_face(&mut self, face_id: FaceID)
     {
-        let faces = &mut *RefCell::borrow_mut(&self.faces);
-        faces.remove(face_id);
+        self.faces.remove(face_id);
     }
 
-    pub fn face_tag(&self, face_id: FaceID) -> T where T: Clone {
-        RefCell::borrow(&self.faces).get(face_id).unwrap().tag.clone()
+    pub fn face_tag(&self, face_id: FaceID) -> T {
+        self.faces.get(face_id).unwrap().tag.clone()
     }
 
-    pub fn set_vertex_halfedge(&self, id: VertexID, val: Option<HalfEdgeID>)
+    pub fn set_vertex_halfedge(&mut self, id: VertexID, val: Option<HalfEdgeID>)
     {
-        RefCell::borrow_mut(&self.vertices).get_mut(id).unwrap().halfedge = val;
+        self.vertices.get_mut(id).unwrap().halfedge = val;
     }
 
-    pub fn set_halfedge_next(&self, id: HalfEdgeID, val: Option<HalfEdgeID>)
+    pub fn set_halfedge_next(&mut self, id: HalfEdgeID, val: Option<HalfEdgeID>)
     {
-        RefCell::borrow_mut(&self.halfedges).get_mut(id).unwrap().next = val;
+        self.halfedges.get_mut(id).unwrap().next = val;
     }
 
-    pub fn set_halfedge_twin(&self, id1: HalfEdgeID, id2: HalfEdgeID)
+    pub fn set_halfedge_twin(&mut self, id1: HalfEdgeID, id2: HalfEdgeID)
     {
-        let halfedges = &mut *RefCell::borrow_mut(&self.halfedges);
+        let halfedges = &mut self.halfedges;
         halfedges.get_mut(id1).unwrap().twin = Some(id2);
         halfedges.get_mut(id2).unwrap().twin = Some(id1);
     }
 
-    pub fn set_halfedge_vertex(&self, id: HalfEdgeID, val: VertexID)
+    pub fn set_halfedge_vertex(&mut self, id: HalfEdgeID, val: VertexID)
     {
-        RefCell::borrow_mut(&self.halfedges).get_mut(id).unwrap().vertex = Some(val);
+        self.halfedges.get_mut(id).unwrap().vertex = Some(val);
     }
 
-    pub fn set_halfedge_face(&self, id: HalfEdgeID, val: Option<FaceID>)
+    pub fn set_halfedge_face(&mut self, id: HalfEdgeID, val: Option<FaceID>)
     {
-        RefCell::borrow_mut(&self.halfedges).get_mut(id).unwrap().face = val;
+        self.halfedges.get_mut(id).unwrap().face = val;
+        if val.is_none() {
+            // A half-edge on the boundary does not represent a corner
+            self.corner_attributes.remove(id);
+        }
     }
 
-    pub fn set_face_halfedge(&self, id: FaceID, val: HalfEdgeID)
+    pub fn set_face_halfedge(&mut self, id: FaceID, val: HalfEdgeID)
     {
-        RefCell::borrow_mut(&self.faces).get_mut(id).unwrap().halfedge = Some(val);
+        self.faces.get_mut(id).unwrap().halfedge = Some(val);
     }
 
-    pub fn vertex_iterator(&self) -> Box<dyn Iterator<Item = VertexID>>
+    pub fn set_position(&mut self, vertex_id: VertexID, position: Vec3)
     {
-        RefCell::borrow(&self.vertices).iter()
+        self.vertices.get_mut(vertex_id).unwrap().position = position;
     }
 
-    pub fn halfedge_iterator(&self) -> Box<dyn Iterator<Item=HalfEdgeID>>
+    /// Renumbers the vertices, half-edges and faces such that there are no free slots and returns mappings from the old ids to the new ids.
+    pub fn compact(&mut self) -> (HashMap<VertexID, VertexID>, HashMap<HalfEdgeID, HalfEdgeID>, HashMap<FaceID, FaceID>)
     {
-        RefCell::borrow(&self.halfedges).iter()
-    }
+        let vertex_mapping = self.vertices.compact();
+        let halfedge_mapping = self.halfedges.compact();
+        let face_mapping = self.faces.compact();
 
-    pub fn face_iterator(&self) -> Box<dyn Iterator<Item = FaceID>>
-    {
-        RefCell::borrow(&self.faces).iter()
+        for vertex in self.vertices.values_mut() {
+            vertex.halfedge = vertex.halfedge.map(|id| halfedge_mapping[&id]);
+        }
+        for halfedge in self.halfedges.values_mut() {
+            halfedge.vertex = halfedge.vertex.map(|id| vertex_mapping[&id]);
+            halfedge.twin = halfedge.twin.map(|id| halfedge_mapping[&id]);
+            halfedge.next = halfedge.next.map(|id| halfedge_mapping[&id]);
+            halfedge.face = halfedge.face.map(|id| face_mapping[&id]);
+        }
+        for face in self.faces.values_mut() {
+            face.halfedge = face.halfedge.map(|id| halfedge_mapping[&id]);
+        }
+        self.vertex_attributes.remap(&vertex_mapping);
+        self.corner_attributes.remap(&halfedge_mapping);
+
+        (vertex_mapping, halfedge_mapping, face_mapping)
     }
 
-    pub(crate) fn vertex_exists(&self, vertex_id: VertexID) -> bool
+    /// Starts recording all changes, such that they can be rolled back or replayed.
+    pub fn begin_journal(&mut self)
     {
-        RefCell::borrow(&self.vertices).get(vertex_id).is_some()
+        self.vertices.begin_journal();
+        self.halfedges.begin_journal();
+        self.faces.begin_journal();
+        self.vertex_attributes.begin_journal();
+        self.corner_attributes.begin_journal();
     }
 
-    pub fn vertex_halfedge(&self, vertex_id: VertexID) -> Option<HalfEdgeID>
+    pub fn is_journaling(&self) -> bool
     {
-        RefCell::borrow(&self.vertices).get(vertex_id).unwrap().halfedge.clone()
+        self.vertices.is_journaling()
     }
 
-    pub fn halfedge(&self, halfedge_id: HalfEdgeID) -> Option<HalfEdge>
+    /// Stops recording the changes and returns the state of everything that changed before and after the changes.
+    pub fn end_journal(&mut self) -> Option<(Snapshot<T>, Snapshot<T>)>
+    {
+        let (vertices_before, vertices_after) = self.vertices.end_journal()?;
+        let (halfedges_before, halfedges_after) = self.halfedges.end_journal()?;
+        let (faces_before, faces_after) = self.faces.end_journal()?;
+        let (vertex_attributes_before, vertex_attributes_after) = self.vertex_attributes.end_journal()?;
+        let (corner_attributes_before, corner_attributes_after) = self.corner_attributes.end_journal()?;
+        Some((
+            Snapshot { vertices: vertices_before, halfedges: halfedges_before, faces: faces_before,
+                vertex_attributes: vertex_attributes_before, corner_attributes: corner_attributes_before },
+            Snapshot { vertices: vertices_after, halfedges: halfedges_after, faces: faces_after,
+                vertex_attributes: vertex_attributes_after, corner_attributes: corner_attributes_after }
+        ))
+    }
+
+    /// Sets everything recorded in the snapshot to the recorded state.
+    pub fn restore(&mut self, snapshot: &Snapshot<T>)
     {
-        RefCell::borrow(&self.halfedges).get(halfedge_id).and_then(|halfedge| Some(halfedge.clone()))
+        self.vertices.restore(&snapshot.vertices);
+        self.halfedges.restore(&snapshot.halfedges);
+        self.faces.restore(&snapshot.faces);
+        self.vertex_attributes.restore(&snapshot.vertex_attributes);
+        self.corner_attributes.restore(&snapshot.corner_attributes);
     }
 
-    pub fn face_halfedge(&self, face_id: FaceID) -> Option<HalfEdgeID>
+    /// Returns whether the vertices, half-edges and faces recorded in the snapshot are in the recorded state.
+    /// The face tags and the attribute values are not compared.
+    pub fn is_in_state(&self, snapshot: &Snapshot<T>) -> bool
     {
-        RefCell::borrow(&self.faces).get(face_id).unwrap().halfedge.clone()
+        self.vertices.is_in_state(&snapshot.vertices, |a, b| a.halfedge == b.halfedge && a.position == b.position)
+            && self.halfedges.is_in_state(&snapshot.halfedges, |a, b| a == b)
+            && self.faces.is_in_state(&snapshot.faces, |a, b| a.halfedge == b.halfedge)
     }
 
-    pub fn position(&self, vertex_id: VertexID) -> Vec3
+    pub fn vertex_attributes_mut(&mut self) -> &mut Attributes<VertexID>
     {
-        RefCell::borrow(&self.vertices).get(vertex_id).unwrap().position
+        &mut self.vertex_attributes
     }
 
-    pub fn set_position(&self, vertex_id: VertexID, position: Vec3)
+    pub fn corner_attributes_mut(&mut self) -> &mut Attributes<HalfEdgeID>
     {
-        RefCell::borrow_mut(&self.vertices).get_mut(vertex_id).unwrap().position = position;
+        &mut self.corner_attributes
     }
 }
 
+///
+/// The state of the primitives and attribute values which were changed in a transaction, either before or after the changes.
+///
+#[derive(Clone, Debug)]
+pub(crate) struct Snapshot<T>
+{
+    vertices: IDMapState<Vertex>,
+    halfedges: IDMapState<HalfEdge>,
+    faces: IDMapState<Face<T>>,
+    vertex_attributes: AttributesState<VertexID>,
+    corner_attributes: AttributesState<HalfEdgeID>
+}
+
 impl<T: std::fmt::Debug> std::fmt::Display for ConnectivityInfo<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         writeln!(f, "**** VERTICES: ****")?;
-        let vertices = RefCell::borrow(&self.vertices);
+        let vertices = &self.vertices;
         writeln!(f, "Count: {}", vertices.len())?;
         for id in vertices.iter() {
             writeln!(f, "{}: {:?}", id, vertices.get(id))?;
         }
         writeln!(f, "**** Halfedges: ****")?;
-        let halfedges = RefCell::borrow(&self.halfedges);
+        let halfedges = &self.halfedges;
         writeln!(f, "Count: {}", halfedges.len())?;
         for id in halfedges.iter() {
             writeln!(f, "{}: {:?}", id, halfedges.get(id))?;
         }
         writeln!(f, "**** Faces: ****")?;
-        let faces = RefCell::borrow(&self.faces);
+        let faces = &self.faces;
         writeln!(f, "Count: {}", faces.len())?;
         for id in faces.iter() {
             writeln!(f, "{}: {:?}", id, faces.get(id))?;
@@ -224,12 +376,70 @@ impl<T: std::fmt::Debug> std::fmt::Display for ConnectivityInfo<T> {
 }
 
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vertex {
     pub halfedge: Option<HalfEdgeID>,
+    #[cfg_attr(feature = "serde", serde(with = "serde_vec3"))]
     pub position: Vec3
 }
 
+/// The deserialized fields of a [ConnectivityInfo], which are validated before they are used.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ConnectivityInfoData<T> {
+    vertices: IDMap<VertexID, Vertex>,
+    halfedges: IDMap<HalfEdgeID, HalfEdge>,
+    faces: IDMap<FaceID, Face<T>>
+}
+
+#[cfg(feature = "serde")]
+impl<T> std::convert::TryFrom<ConnectivityInfoData<T>> for ConnectivityInfo<T> {
+    type Error = String;
+
+    /// Checks that all ids stored in the vertices, half-edges and faces refer to existing primitives.
+    fn try_from(data: ConnectivityInfoData<T>) -> Result<Self, String>
+    {
+        let ConnectivityInfoData { vertices, halfedges, faces } = data;
+        let vertex_exists = |vertex_id: Option<VertexID>| vertex_id.map(|vertex_id| vertices.get(vertex_id).is_some()).unwrap_or(true);
+        let halfedge_exists = |halfedge_id: Option<HalfEdgeID>| halfedge_id.map(|halfedge_id| halfedges.get(halfedge_id).is_some()).unwrap_or(true);
+        let face_exists = |face_id: Option<FaceID>| face_id.map(|face_id| faces.get(face_id).is_some()).unwrap_or(true);
+
+        if let Some(vertex_id) = vertices.iter().find(|vertex_id| !halfedge_exists(vertices.get(*vertex_id).unwrap().halfedge)) {
+            return Err(format!("Vertex {} refers to a half-edge which does not exist", vertex_id));
+        }
+        if let Some(halfedge_id) = halfedges.iter().find(|halfedge_id| {
+            let halfedge = halfedges.get(*halfedge_id).unwrap();
+            !vertex_exists(halfedge.vertex) || !halfedge_exists(halfedge.twin) || !halfedge_exists(halfedge.next) || !face_exists(halfedge.face)
+        }) {
+            return Err(format!("Half-edge {} refers to a vertex, half-edge or face which does not exist", halfedge_id));
+        }
+        if let Some(face_id) = faces.iter().find(|face_id| !halfedge_exists(faces.get(*face_id).unwrap().halfedge)) {
+            return Err(format!("Face {} refers to a half-edge which does not exist", face_id));
+        }
+        Ok(ConnectivityInfo { vertices, halfedges, faces, vertex_attributes: Attributes::new(), corner_attributes: Attributes::new() })
+    }
+}
+
+/// Serializes a [Vec3] as `[f64; 3]`.
+#[cfg(feature = "serde")]
+mod serde_vec3 {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use crate::mesh::math::{vec3, Vec3};
+
+    pub fn serialize<S: Serializer>(position: &Vec3, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        [position.x, position.y, position.z].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec3, D::Error>
+    {
+        let [x, y, z] = <[f64; 3]>::deserialize(deserializer)?;
+        Ok(vec3(x, y, z))
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HalfEdge {
     pub vertex: Option<VertexID>,
     pub twin: Option<HalfEdgeID>,
@@ -239,60 +449,400 @@ pub struct HalfEdge {
 
 /// Face is parameterized by the type of custom data to store
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Face<T> {
     pub halfedge: Option<HalfEdgeID>,
     /// Custom data to store in the face
     pub tag: T,
 }
 
+///
+/// Stores the values in slots indexed by the ids. A removed value leaves an empty slot (a tombstone) which is reused by the next insertion,
+/// so iteration is linear in the number of slots and does not allocate.
+///
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(serialize = "V: serde::Serialize", deserialize = "V: serde::Deserialize<'de>")))]
+#[cfg_attr(feature = "serde", serde(try_from = "IDMapData<K, V>"))]
 pub(crate) struct IDMap<K, V>
 {
-    values: Vec<V>,
-    free: Vec<K>
+    values: Vec<Option<V>>,
+    #[cfg(feature = "generational-ids")]
+    generations: Vec<u32>,
+    free: Vec<u32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    journal: Option<IDMapJournal<V>>,
+    id_type: PhantomData<K>
 }
 
-use std::collections::HashSet;
+/// The deserialized fields of an [IDMap], which are validated before they are used.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(bound(deserialize = "V: serde::Deserialize<'de>"))]
+struct IDMapData<K, V>
+{
+    values: Vec<Option<V>>,
+    #[cfg(feature = "generational-ids")]
+    generations: Vec<u32>,
+    free: Vec<u32>,
+    id_type: PhantomData<K>
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> std::convert::TryFrom<IDMapData<K, V>> for IDMap<K, V> {
+    type Error = String;
+
+    /// Checks that each slot has a generation and that the free slots are exactly the empty slots.
+    fn try_from(data: IDMapData<K, V>) -> Result<Self, String>
+    {
+        #[cfg(feature = "generational-ids")]
+        if data.generations.len() < data.values.len() {
+            return Err(format!("There are {} slots, but only {} generations", data.values.len(), data.generations.len()));
+        }
+        let mut is_free = vec![false; data.values.len()];
+        for index in data.free.iter() {
+            match data.values.get(*index as usize) {
+                Some(None) if !is_free[*index as usize] => is_free[*index as usize] = true,
+                _ => return Err(format!("The free slot {} is out of range, occupied or listed more than once", index))
+            }
+        }
+        if let Some(index) = data.values.iter().zip(is_free.iter()).position(|(value, is_free)| value.is_none() && !is_free) {
+            return Err(format!("The empty slot {} is not listed as free", index));
+        }
+        Ok(IDMap {
+            values: data.values,
+            #[cfg(feature = "generational-ids")]
+            generations: data.generations,
+            free: data.free,
+            journal: None,
+            id_type: PhantomData
+        })
+    }
+}
 
-impl<K: 'static, V> IDMap<K, V>
+///
+/// The state of the slots of an [IDMap] which were changed in a transaction, either before or after the changes.
+///
+#[derive(Debug, Clone)]
+pub(crate) struct IDMapState<V>
+{
+    len: usize,
+    generations_len: usize,
+    free: Vec<u32>,
+    slots: Vec<(u32, Option<V>, u32)>
+}
+
+/// Records the state of each slot before it is changed for the first time in a transaction.
+#[derive(Debug, Clone)]
+struct IDMapJournal<V>
+{
+    len: usize,
+    generations_len: usize,
+    free: Vec<u32>,
+    slots: HashMap<u32, (Option<V>, u32)>
+}
+
+impl<K, V> IDMap<K, V>
     where K: ID
 {
     pub fn with_capacity(capacity: usize) -> Self {
-        IDMap { values: Vec::with_capacity(capacity), free: Vec::new() }
+        IDMap {
+            values: Vec::with_capacity(capacity),
+            #[cfg(feature = "generational-ids")]
+            generations: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            journal: None,
+            id_type: PhantomData
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len() - self.free.len()
+    }
+
+    pub fn get(&self, id: K) -> Option<&V> {
+        if !self.is_current(id) { return None; }
+        self.values.get(id.deref() as usize).and_then(|value| value.as_ref())
+    }
+
+    pub fn iter(&self) -> IDIter<'_, K, V> {
+        IDIter { map: self, index: 0 }
+    }
+
+    /// The number of slots, including the empty slots.
+    pub fn num_slots(&self) -> u32 {
+        self.values.len() as u32
+    }
+
+    /// Returns the id of the value in the slot with the given index or `None` if the slot is empty.
+    pub fn id_in_slot(&self, index: u32) -> Option<K> {
+        match self.values.get(index as usize) {
+            Some(Some(_)) => Some(self.id(index)),
+            _ => None
+        }
+    }
+
+    #[cfg(feature = "generational-ids")]
+    fn id(&self, index: u32) -> K {
+        K::new_with_generation(index, self.generations[index as usize])
+    }
+
+    #[cfg(not(feature = "generational-ids"))]
+    fn id(&self, index: u32) -> K {
+        K::new(index)
+    }
+
+    #[cfg(feature = "generational-ids")]
+    fn is_current(&self, id: K) -> bool {
+        self.generations.get(id.deref() as usize) == Some(&id.generation())
+    }
+
+    #[cfg(not(feature = "generational-ids"))]
+    fn is_current(&self, _id: K) -> bool {
+        true
+    }
+
+    #[cfg(feature = "generational-ids")]
+    fn generations_len(&self) -> usize {
+        self.generations.len()
+    }
+
+    #[cfg(not(feature = "generational-ids"))]
+    fn generations_len(&self) -> usize {
+        0
     }
 
+    #[cfg(feature = "generational-ids")]
+    fn generation_of_slot(&self, index: u32) -> u32 {
+        self.generations.get(index as usize).copied().unwrap_or(0)
+    }
+
+    #[cfg(not(feature = "generational-ids"))]
+    fn generation_of_slot(&self, _index: u32) -> u32 {
+        0
+    }
+}
+
+impl<K, V: Clone> IDMap<K, V>
+    where K: ID
+{
     pub fn insert_new(&mut self, value: V) -> Option<K>  {
-        let id = if let Some(i) = self.free.pop() {
-            self.values[i.deref() as usize] = value;
+        let index = if let Some(i) = self.free.pop() {
+            self.record(i);
+            self.values[i as usize] = Some(value);
             i
         }
         else {
-            self.values.push(value);
-            K::new(self.values.len() as u32 - 1)
+            self.record(self.values.len() as u32);
+            self.values.push(Some(value));
+            // The generation of the slot is kept if the slot existed before the map was compacted
+            #[cfg(feature = "generational-ids")]
+            if self.generations.len() < self.values.len() {
+                self.generations.push(0);
+            }
+            self.values.len() as u32 - 1
         };
-        Some(id)
+        Some(self.id(index))
     }
 
     pub fn remove(&mut self, id: K) {
-        self.free.push(id);
+        if self.get(id).is_none() { return; }
+        self.record(id.deref());
+        // Increase the generation, such that the removed id is no longer valid
+        #[cfg(feature = "generational-ids")]
+        {
+            let generation = &mut self.generations[id.deref() as usize];
+            *generation = generation.wrapping_add(1);
+        }
+        self.values[id.deref() as usize] = None;
+        self.free.push(id.deref());
     }
 
-    pub fn len(&self) -> usize {
-        self.values.len() - self.free.len()
+    pub fn get_mut(&mut self, id: K) -> Option<&mut V> {
+        if !self.is_current(id) { return None; }
+        self.record(id.deref());
+        self.values.get_mut(id.deref() as usize).and_then(|value| value.as_mut())
     }
 
-    pub fn get(&self, id: K) -> Option<&V> {
-        self.values.get(id.deref() as usize)
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.values.iter_mut().flatten()
     }
 
-    pub fn get_mut(&mut self, id: K) -> Option<&mut V> {
-        self.values.get_mut(id.deref() as usize)
+    /// Moves all values to the front, removing all free slots, and returns a mapping from the old ids to the new ids.
+    pub fn compact(&mut self) -> HashMap<K, K> {
+        // Compaction changes every slot and can therefore not be part of a transaction
+        self.journal = None;
+        let old_ids: Vec<K> = self.iter().collect();
+        // Increase the generation of all slots, such that none of the old ids are valid
+        #[cfg(feature = "generational-ids")]
+        for generation in self.generations.iter_mut() {
+            *generation = generation.wrapping_add(1);
+        }
+        self.values = self.values.drain(..).flatten().map(Some).collect();
+        self.free.clear();
+        old_ids.into_iter().enumerate()
+            .map(|(index, old_id)| (old_id, self.id(index as u32)))
+            .collect()
+    }
+
+    /// Starts recording the changes to the slots.
+    pub fn begin_journal(&mut self) {
+        self.journal = Some(IDMapJournal { len: self.values.len(), generations_len: self.generations_len(), free: self.free.clone(), slots: HashMap::new() });
+    }
+
+    /// Stops recording the changes and returns the state of the changed slots before and after the changes.
+    pub fn end_journal(&mut self) -> Option<(IDMapState<V>, IDMapState<V>)> {
+        let journal = self.journal.take()?;
+        let mut indices: Vec<u32> = journal.slots.keys().copied().collect();
+        indices.sort_unstable();
+        let after = IDMapState {
+            len: self.values.len(),
+            generations_len: self.generations_len(),
+            free: self.free.clone(),
+            slots: indices.iter().map(|i| (*i, self.values.get(*i as usize).cloned().flatten(), self.generation_of_slot(*i))).collect()
+        };
+        let mut slots = journal.slots;
+        let before = IDMapState {
+            len: journal.len,
+            generations_len: journal.generations_len,
+            free: journal.free,
+            slots: indices.iter().map(|i| { let (value, generation) = slots.remove(i).unwrap(); (*i, value, generation) }).collect()
+        };
+        Some((before, after))
+    }
+
+    pub fn is_journaling(&self) -> bool {
+        self.journal.is_some()
+    }
+
+    /// Sets the changed slots to the given state.
+    pub fn restore(&mut self, state: &IDMapState<V>) {
+        self.values.truncate(state.len);
+        self.values.resize_with(state.len, || None);
+        #[cfg(feature = "generational-ids")]
+        self.generations.resize(state.generations_len, 0);
+        self.free = state.free.clone();
+        for (index, value, _generation) in state.slots.iter() {
+            if let Some(slot) = self.values.get_mut(*index as usize) {
+                *slot = value.clone();
+            }
+            #[cfg(feature = "generational-ids")]
+            {
+                if let Some(slot) = self.generations.get_mut(*index as usize) {
+                    *slot = *_generation;
+                }
+            }
+        }
+    }
+
+    /// Returns whether the number of slots, the free slots and the changed slots are in the given state,
+    /// where the values of the slots are compared with the given function.
+    pub fn is_in_state(&self, state: &IDMapState<V>, equal: impl Fn(&V, &V) -> bool) -> bool {
+        self.values.len() == state.len && self.generations_len() == state.generations_len && self.free == state.free
+            && state.slots.iter().all(|(index, value, generation)| {
+                self.generation_of_slot(*index) == *generation && match (self.values.get(*index as usize).and_then(|value| value.as_ref()), value) {
+                    (Some(a), Some(b)) => equal(a, b),
+                    (None, None) => true,
+                    _ => false
+                }
+            })
+    }
+
+    fn record(&mut self, index: u32) {
+        let generation = self.generation_of_slot(index);
+        if let Some(ref mut journal) = self.journal {
+            let values = &self.values;
+            journal.slots.entry(index).or_insert_with(|| (values.get(index as usize).cloned().flatten(), generation));
+        }
+    }
+}
+
+/// Iterates the ids of the occupied slots of an [IDMap].
+#[derive(Debug)]
+pub(crate) struct IDIter<'a, K, V>
+{
+    map: &'a IDMap<K, V>,
+    index: usize
+}
+
+impl<'a, K, V> Clone for IDIter<'a, K, V>
+{
+    fn clone(&self) -> Self
+    {
+        IDIter { map: self.map, index: self.index }
+    }
+}
+
+impl<'a, K: ID, V> Iterator for IDIter<'a, K, V>
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<K>
+    {
+        while self.index < self.map.values.len() {
+            let index = self.index;
+            self.index += 1;
+            if self.map.values[index].is_some() {
+                return Some(self.map.id(index as u32));
+            }
+        }
+        None
     }
 
-    pub fn iter(&self) -> Box<dyn Iterator<Item = K>> {
-		let free: HashSet<_> = self.free.iter().cloned().collect();
-		Box::new(   (0 .. self.values.len() as u32)
-					.map(|i| K::new(i))
-					.filter(move |i| !free.contains(&i))   )
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        (0, Some(self.map.values.len() - self.index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "generational-ids")]
+    #[test]
+    fn test_stale_id_is_not_found()
+    {
+        let mut map: IDMap<VertexID, u32> = IDMap::with_capacity(2);
+        let id0 = map.insert_new(0).unwrap();
+        let id1 = map.insert_new(1).unwrap();
+        map.remove(id0);
+        assert_eq!(map.get(id0), None);
+
+        let id2 = map.insert_new(2).unwrap();
+        assert_eq!(id2.deref(), id0.deref());
+        assert_ne!(id2, id0);
+        assert_eq!(map.get(id0), None);
+        assert_eq!(map.get(id2), Some(&2));
+        assert_eq!(map.get(id1), Some(&1));
+        assert_eq!(map.iter().collect::<Vec<_>>(), vec![id2, id1]);
+    }
+
+    #[cfg(feature = "3d-io")]
+    #[test]
+    fn test_deserialize_invalid()
+    {
+        let mut mesh = crate::MeshBuilder::<()>::new().cube().build().unwrap();
+        mesh.remove_face(mesh.face_iter().next().unwrap());
+        let connectivity_info = bincode::serialize(&mesh).unwrap();
+        let deserialize = |modify: &dyn Fn(&mut ConnectivityInfo<()>)| {
+            let mut connectivity_info: ConnectivityInfo<()> = bincode::deserialize(&connectivity_info).unwrap();
+            modify(&mut connectivity_info);
+            bincode::deserialize::<ConnectivityInfo<()>>(&bincode::serialize(&connectivity_info).unwrap())
+        };
+        assert!(deserialize(&|_| {}).is_ok());
+
+        #[cfg(feature = "generational-ids")]
+        assert!(deserialize(&|c| { c.vertices.generations.pop(); }).is_err());
+        assert!(deserialize(&|c| { c.faces.free.pop(); }).is_err());
+        assert!(deserialize(&|c| c.faces.free.push(0)).is_err());
+        assert!(deserialize(&|c| c.faces.free.push(100)).is_err());
+        assert!(deserialize(&|c| { let index = c.faces.free[0]; c.faces.free.push(index) }).is_err());
+        assert!(deserialize(&|c| c.halfedges.values[0].as_mut().unwrap().next = Some(HalfEdgeID::new(1000))).is_err());
+        assert!(deserialize(&|c| c.vertices.values[0].as_mut().unwrap().halfedge = Some(HalfEdgeID::new(1000))).is_err());
+        assert!(deserialize(&|c| {
+            let index = c.faces.free[0] as usize;
+            c.halfedges.values.iter_mut().flatten().find(|halfedge| halfedge.face.is_some()).unwrap().face = Some(FaceID::new(index as u32));
+        }).is_err());
     }
 }
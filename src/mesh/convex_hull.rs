@@ -0,0 +1,290 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use crate::mesh::math::*;
+use std::collections::{HashMap, HashSet};
+
+/// # Convex hull
+impl<T: Clone + Default> Mesh<T>
+{
+    ///
+    /// Returns the convex hull of the vertices of this mesh as a new closed mesh, where all faces have the default tag.
+    /// The hull only contains the vertices which are corners of the hull, vertices on the faces or edges of the hull are left out.
+    /// See also [MeshBuilder::convex_hull](crate::mesh_builder::MeshBuilder::convex_hull).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [ActionWillResultInInvalidMesh](crate::mesh::Error::ActionWillResultInInvalidMesh) error if all vertices are in the same plane,
+    /// since the hull is then flat and cannot be represented by a closed mesh.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// let mesh = MeshBuilder::<()>::new().torus(2.0, 0.5, 16, 8).build()?;
+    /// let hull = mesh.convex_hull().unwrap();
+    ///
+    /// assert!(hull.is_closed());
+    /// assert!(hull.num_vertices() < mesh.num_vertices());
+    /// #   hull.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn convex_hull(&self) -> Result<Mesh<T>, Error>
+    {
+        let points = self.vertex_iter().map(|vertex_id| self.vertex_position(vertex_id)).collect::<Vec<_>>();
+        let (positions, indices) = quickhull(&points).ok_or_else(|| Error::ActionWillResultInInvalidMesh {
+            message: format!("Cannot create a convex hull of {} vertices which are all in the same plane", points.len())
+        })?;
+        let tags = vec![T::default(); indices.len() / 3];
+        Ok(Mesh::new(indices, tags, positions.iter().flat_map(|p| vec![p.x, p.y, p.z]).collect()))
+    }
+}
+
+/// A face of the hull under construction.
+struct HullFace {
+    vertices: [usize; 3],
+    normal: Vec3,
+    offset: f64,
+    /// The points which are in front of this face and not yet assigned to another face.
+    outside: Vec<usize>,
+    alive: bool
+}
+
+impl HullFace {
+    fn new(points: &[Vec3], vertices: [usize; 3]) -> Self
+    {
+        let (a, b, c) = (points[vertices[0]], points[vertices[1]], points[vertices[2]]);
+        let normal = (b - a).cross(c - a);
+        let normal = if normal.magnitude2() > 0.0 { normal.normalize() } else { normal };
+        HullFace { vertices, normal, offset: normal.dot(a), outside: Vec::new(), alive: true }
+    }
+
+    /// The signed distance from the plane of the face to the point, which is positive in front of the face.
+    fn distance(&self, point: Vec3) -> f64
+    {
+        self.normal.dot(point) - self.offset
+    }
+
+    fn edges(&self) -> [(usize, usize); 3]
+    {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+///
+/// Computes the convex hull of the points with the quickhull algorithm and returns the positions of the corners of the hull
+/// and the indices of the outwards oriented triangles of the hull. Points which are within a tolerance, relative to the size of the point set,
+/// of the hull are considered to be on the hull and are not used as corners, which handles coplanar and duplicate points.
+/// Returns `None` if the points are all in the same plane (within the tolerance).
+///
+pub(crate) fn quickhull(points: &[Vec3]) -> Option<(Vec<Vec3>, Vec<u32>)>
+{
+    let points = points.iter().filter(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite()).cloned().collect::<Vec<_>>();
+    if points.len() < 4 {
+        return None;
+    }
+    let min = points.iter().fold(points[0], |min, p| vec3(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)));
+    let max = points.iter().fold(points[0], |max, p| vec3(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)));
+    let epsilon = 1.0e-10 * (max - min).magnitude().max(max.magnitude()).max(min.magnitude());
+
+    // The initial tetrahedron spanned by the two points furthest apart of the extreme points along the axes,
+    // the point furthest from the line through them and the point furthest from the plane through those three
+    let mut extremes = Vec::with_capacity(6);
+    for axis in 0..3 {
+        let by_axis = |a: &&Vec3, b: &&Vec3| a[axis].partial_cmp(&b[axis]).unwrap_or(std::cmp::Ordering::Equal);
+        extremes.push(points.iter().enumerate().min_by(|a, b| by_axis(&a.1, &b.1)).unwrap().0);
+        extremes.push(points.iter().enumerate().max_by(|a, b| by_axis(&a.1, &b.1)).unwrap().0);
+    }
+    let (mut i0, mut i1, mut max_distance) = (0, 0, 0.0);
+    for a in extremes.iter() {
+        for b in extremes.iter() {
+            let distance = (points[*a] - points[*b]).magnitude();
+            if distance > max_distance {
+                i0 = *a;
+                i1 = *b;
+                max_distance = distance;
+            }
+        }
+    }
+    if max_distance <= epsilon {
+        return None;
+    }
+    let direction = (points[i1] - points[i0]).normalize();
+    let line_distance = |p: Vec3| (p - points[i0]).cross(direction).magnitude();
+    let i2 = (0..points.len()).max_by(|a, b| line_distance(points[*a]).partial_cmp(&line_distance(points[*b])).unwrap()).unwrap();
+    if line_distance(points[i2]) <= epsilon {
+        return None;
+    }
+    let base = HullFace::new(&points, [i0, i1, i2]);
+    let i3 = (0..points.len()).max_by(|a, b| base.distance(points[*a]).abs().partial_cmp(&base.distance(points[*b]).abs()).unwrap()).unwrap();
+    if base.distance(points[i3]).abs() <= epsilon {
+        return None;
+    }
+
+    let mut faces = if base.distance(points[i3]) > 0.0 {
+        vec![[i0, i2, i1], [i0, i1, i3], [i1, i2, i3], [i2, i0, i3]]
+    } else {
+        vec![[i0, i1, i2], [i0, i3, i1], [i1, i3, i2], [i2, i3, i0]]
+    }.into_iter().map(|vertices| HullFace::new(&points, vertices)).collect::<Vec<_>>();
+
+    // The face on the other side of each directed edge
+    let mut edge_faces = HashMap::new();
+    for (face, hull_face) in faces.iter().enumerate() {
+        for edge in hull_face.edges().iter() {
+            edge_faces.insert(*edge, face);
+        }
+    }
+
+    let assign = |faces: &mut Vec<HullFace>, candidates: &[usize], point: usize| {
+        if let Some(face) = candidates.iter().find(|face| faces[**face].distance(points[point]) > epsilon) {
+            faces[*face].outside.push(point);
+        }
+    };
+    let initial = [0, 1, 2, 3];
+    for point in 0..points.len() {
+        assign(&mut faces, &initial, point);
+    }
+
+    while let Some(face) = (0..faces.len()).find(|face| faces[*face].alive && !faces[*face].outside.is_empty()) {
+        let eye = *faces[face].outside.iter()
+            .max_by(|a, b| faces[face].distance(points[**a]).partial_cmp(&faces[face].distance(points[**b])).unwrap()).unwrap();
+
+        // The faces visible from the eye point form a connected region, which is found from the face the point belongs to
+        let mut visible = vec![face];
+        let mut is_visible = HashSet::new();
+        is_visible.insert(face);
+        let mut i = 0;
+        while i < visible.len() {
+            for (a, b) in faces[visible[i]].edges().iter() {
+                let neighbour = edge_faces[&(*b, *a)];
+                if !is_visible.contains(&neighbour) && faces[neighbour].distance(points[eye]) > epsilon {
+                    is_visible.insert(neighbour);
+                    visible.push(neighbour);
+                }
+            }
+            i += 1;
+        }
+
+        // The horizon consists of the edges of the visible region which are shared with a face which is not visible
+        let mut horizon = Vec::new();
+        let mut orphans = Vec::new();
+        for visible_face in visible.iter() {
+            for (a, b) in faces[*visible_face].edges().iter() {
+                if !is_visible.contains(&edge_faces[&(*b, *a)]) {
+                    horizon.push((*a, *b));
+                }
+            }
+        }
+        for visible_face in visible.iter() {
+            for edge in faces[*visible_face].edges().iter() {
+                edge_faces.remove(edge);
+            }
+            faces[*visible_face].alive = false;
+            orphans.append(&mut faces[*visible_face].outside);
+        }
+
+        let mut new_faces = Vec::with_capacity(horizon.len());
+        for (a, b) in horizon {
+            let new_face = faces.len();
+            faces.push(HullFace::new(&points, [a, b, eye]));
+            for edge in faces[new_face].edges().iter() {
+                edge_faces.insert(*edge, new_face);
+            }
+            new_faces.push(new_face);
+        }
+        for point in orphans {
+            if point != eye {
+                assign(&mut faces, &new_faces, point);
+            }
+        }
+    }
+
+    // Only the points which are corners of the hull are kept
+    let mut vertices = HashMap::new();
+    let mut positions = Vec::new();
+    let mut indices = Vec::new();
+    for hull_face in faces.iter().filter(|hull_face| hull_face.alive) {
+        for vertex in hull_face.vertices.iter() {
+            indices.push(*vertices.entry(*vertex).or_insert_with(|| {
+                positions.push(points[*vertex]);
+                positions.len() as u32 - 1
+            }));
+        }
+    }
+    Some((positions, indices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MeshBuilder;
+
+    fn volume(mesh: &Mesh<()>) -> f64 {
+        mesh.face_iter().map(|face_id| {
+            let (p0, p1, p2) = mesh.face_positions(face_id);
+            p0.dot(p1.cross(p2)) / 6.0
+        }).sum::<f64>()
+    }
+
+    #[test]
+    fn test_convex_hull_of_grid() {
+        // A grid of points, with many coplanar points on each side, and duplicated points
+        let mut points = Vec::new();
+        for i in 0..5 {
+            for j in 0..5 {
+                for k in 0..5 {
+                    points.push(vec3(i as f64 * 0.5 - 1.0, j as f64 * 0.5 - 1.0, k as f64 * 0.5 - 1.0));
+                }
+            }
+        }
+        points.extend(points.clone());
+        let mesh = MeshBuilder::<()>::new().convex_hull(&points).unwrap().build().unwrap();
+
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        assert_eq!(mesh.num_vertices(), 8);
+        assert_eq!(mesh.num_faces(), 12);
+        assert!((volume(&mesh) - 8.0).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_convex_hull_of_sphere() {
+        // Points on a sphere, which are all corners of the hull, and points inside
+        let mut random = crate::test_utils::random(12345);
+        let mut points = Vec::new();
+        for _ in 0..200 {
+            let direction = vec3(random() - 0.5, random() - 0.5, random() - 0.5).normalize();
+            points.push(direction * 2.0);
+            points.push(direction * random());
+        }
+        let mesh = MeshBuilder::<()>::new().convex_hull(&points).unwrap().build().unwrap();
+
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        assert_eq!(mesh.num_vertices(), 200);
+        assert!(volume(&mesh) > 0.0);
+        for face_id in mesh.face_iter() {
+            let normal = mesh.face_normal(face_id);
+            let offset = normal.dot(mesh.face_positions(face_id).0);
+            assert!(points.iter().all(|p| normal.dot(*p) - offset < 0.000001));
+        }
+    }
+
+    #[test]
+    fn test_convex_hull_of_mesh() {
+        let mesh = MeshBuilder::<()>::new().torus(2.0, 0.5, 16, 8).build().unwrap();
+        let hull = mesh.convex_hull().unwrap();
+        hull.is_valid().unwrap();
+        assert!(hull.is_closed());
+        assert!(volume(&hull) > volume(&mesh));
+
+        let mesh = MeshBuilder::<()>::new().grid(3, 3).build().unwrap();
+        assert!(mesh.convex_hull().is_err());
+        let points = mesh.vertex_iter().map(|vertex_id| mesh.vertex_position(vertex_id)).collect::<Vec<_>>();
+        assert!(matches!(MeshBuilder::<()>::new().convex_hull(&points), Err(crate::mesh_builder::Error::InvalidPositions { .. })));
+    }
+}
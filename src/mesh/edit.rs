@@ -64,6 +64,9 @@ impl<T: Clone> Mesh<T>
         self.connectivity_info.set_halfedge_face(next_id, Some(twin_face_id));
         self.connectivity_info.set_halfedge_face(twin_next_id, Some(face_id));
 
+        // The flipped half-edges now represent the corners previously represented by the moved half-edges
+        self.interpolate_corner_attributes(&[(halfedge_id, vec![(next_id, 1.0)]), (twin_id, vec![(twin_next_id, 1.0)])]);
+
         Ok(())
     }
 
@@ -86,11 +89,18 @@ impl<T: Clone> Mesh<T>
         let twin_vertex_id = walker.vertex_id();
         let is_boundary = walker.face_id().is_none();
 
+        let (source_vertex_id, sink_vertex_id) = self.edge_vertices(split_halfedge_id);
+        let edge = self.edge_vector(split_halfedge_id);
+        let t = if edge.magnitude2() > 0.0 {
+            ((position - self.vertex_position(source_vertex_id)).dot(edge) / edge.magnitude2()).max(0.0).min(1.0)
+        } else { 0.5 };
+
         let new_vertex_id = self.create_vertex(position);
-        let mut farther_halfedge = self.split_one_face(split_halfedge_id, twin_halfedge_id, new_vertex_id);
+        self.interpolate_vertex_attributes(new_vertex_id, &[(source_vertex_id, 1.0 - t), (sink_vertex_id, t)]);
+        let mut farther_halfedge = self.split_one_face(split_halfedge_id, twin_halfedge_id, new_vertex_id, t);
 
         if !is_boundary {
-            self.split_one_face(twin_halfedge_id, split_halfedge_id, new_vertex_id);
+            self.split_one_face(twin_halfedge_id, split_halfedge_id, new_vertex_id, 1.0 - t);
         }
         else {
             let new_halfedge_id = self.connectivity_info.new_halfedge(twin_vertex_id, None, None);
@@ -124,6 +134,10 @@ impl<T: Clone> Mesh<T>
         let halfedge_id3 = walker.halfedge_id().unwrap();
         let vertex_id3 = walker.vertex_id().unwrap();
 
+        let (p1, p2, p3) = (self.vertex_position(vertex_id1), self.vertex_position(vertex_id2), self.vertex_position(vertex_id3));
+        let (w1, w2, w3) = crate::mesh::intersection::utility::barycentric(&position, &p1, &p2, &p3);
+        self.interpolate_vertex_attributes(new_vertex_id, &[(vertex_id1, w1), (vertex_id2, w2), (vertex_id3, w3)]);
+
         let tag = self.face_tag(face_id);
 
         let face_id1 = self.connectivity_info.create_face_with_existing_halfedge(vertex_id1, vertex_id2, new_vertex_id, halfedge_id2, tag.clone());
@@ -136,7 +150,7 @@ impl<T: Clone> Mesh<T>
 
         // Update twin information
         let mut new_halfedge_id = HalfEdgeID::new(0);
-        for halfedge_id in self.face_halfedge_iter(face_id1) {
+        for halfedge_id in self.face_halfedge_iter(face_id1).collect::<Vec<_>>() {
             let vid = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
             if vid == vertex_id1 {
                 self.connectivity_info.set_halfedge_twin(new_halfedge_id1, halfedge_id);
@@ -145,7 +159,7 @@ impl<T: Clone> Mesh<T>
                 new_halfedge_id = halfedge_id;
             }
         }
-        for halfedge_id in self.face_halfedge_iter(face_id2) {
+        for halfedge_id in self.face_halfedge_iter(face_id2).collect::<Vec<_>>() {
             let vid = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
             if vid == vertex_id2 {
                 self.connectivity_info.set_halfedge_twin(new_halfedge_id, halfedge_id);
@@ -154,10 +168,28 @@ impl<T: Clone> Mesh<T>
                 self.connectivity_info.set_halfedge_twin(new_halfedge_id2, halfedge_id);
             }
         }
+
+        // Update corner attributes
+        let old_corners = [(vertex_id1, halfedge_id1, w1), (vertex_id2, halfedge_id2, w2), (vertex_id3, halfedge_id3, w3)];
+        let targets: Vec<_> = [face_id, face_id1, face_id2].iter()
+            .flat_map(|face_id| self.face_halfedge_iter(*face_id))
+            .map(|halfedge_id| {
+                let vid = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                let sources = if vid == new_vertex_id {
+                    old_corners.iter().map(|(_, corner_id, weight)| (*corner_id, *weight)).collect()
+                } else {
+                    old_corners.iter().filter(|(vertex_id, ..)| *vertex_id == vid).map(|(_, corner_id, _)| (*corner_id, 1.0)).collect()
+                };
+                (halfedge_id, sources)
+            })
+            .collect();
+        self.interpolate_corner_attributes(&targets);
+
         new_vertex_id
     }
 
-    fn split_one_face(&mut self, halfedge_id: HalfEdgeID, twin_halfedge_id: HalfEdgeID, new_vertex_id: VertexID) -> HalfEdgeID
+    /// Splits the face adjacent to the given half-edge at the new vertex, which is positioned at the parameter `t` along the half-edge.
+    fn split_one_face(&mut self, halfedge_id: HalfEdgeID, twin_halfedge_id: HalfEdgeID, new_vertex_id: VertexID, t: f64) -> HalfEdgeID
     {
         let mut walker = self.walker_from_halfedge(halfedge_id);
         let vertex_id1 = walker.vertex_id().unwrap();
@@ -180,7 +212,7 @@ impl<T: Clone> Mesh<T>
         self.connectivity_info.set_face_halfedge(old_face_id, halfedge_id);
 
         // Update twin information
-        for halfedge_id in self.face_halfedge_iter(new_face_id) {
+        for halfedge_id in self.face_halfedge_iter(new_face_id).collect::<Vec<_>>() {
             let vid = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
             if vid == vertex_id1 {
                 self.connectivity_info.set_halfedge_twin(twin_halfedge_id, halfedge_id);
@@ -190,6 +222,20 @@ impl<T: Clone> Mesh<T>
             }
         }
 
+        // Update corner attributes
+        let new_corner_sources = vec![(halfedge_to_reuse_next, 1.0 - t), (halfedge_id, t)];
+        let mut targets = vec![(halfedge_id, new_corner_sources.clone()), (new_halfedge_id, vec![(halfedge_to_reuse, 1.0)])];
+        for corner_id in self.face_halfedge_iter(new_face_id) {
+            let vid = self.walker_from_halfedge(corner_id).vertex_id().unwrap();
+            if vid == vertex_id1 {
+                targets.push((corner_id, vec![(halfedge_id, 1.0)]));
+            }
+            else if vid == new_vertex_id {
+                targets.push((corner_id, new_corner_sources.clone()));
+            }
+        }
+        self.interpolate_corner_attributes(&targets);
+
         split_halfedge
     }
 
@@ -207,20 +253,21 @@ impl<T: Clone> Mesh<T>
         let surviving_vertex_id = walker.vertex_id().unwrap();
         walker.as_twin();
         let dying_vertex_id = walker.vertex_id().unwrap();
+        let mut he_id1 = walker.halfedge_id();
         let new_position = 0.5 * (self.vertex_position(surviving_vertex_id) + self.vertex_position(dying_vertex_id));
 
 
         // Update halfedges pointing to dying vertex
-        for halfedge_id in self.vertex_halfedge_iter(dying_vertex_id) {
-            self.connectivity_info.set_halfedge_vertex(self.walker_from_halfedge(halfedge_id).twin_id().unwrap(), surviving_vertex_id);
+        for halfedge_id in self.vertex_halfedge_iter(dying_vertex_id).collect::<Vec<_>>() {
+            let twin_id = self.walker_from_halfedge(halfedge_id).twin_id().unwrap();
+            self.connectivity_info.set_halfedge_vertex(twin_id, surviving_vertex_id);
         }
 
         // Remove first face + halfedges
-        let mut he_id1 = walker.halfedge_id();
+        let mut walker = self.walker_from_halfedge(he_id1.unwrap());
         if walker.face_id().is_some() {
-            walker.as_previous();
-            self.connectivity_info.set_vertex_halfedge( surviving_vertex_id, walker.twin_id());
-            walker.as_next();
+            let previous_twin_id = walker.as_previous().twin_id();
+            self.connectivity_info.set_vertex_halfedge( surviving_vertex_id, previous_twin_id);
         }
         else {
             self.connectivity_info.remove_halfedge(he_id1.unwrap());
@@ -228,11 +275,11 @@ impl<T: Clone> Mesh<T>
         }
 
         // Remove second face + halfedges
-        walker.as_twin();
-        let he_id2 = walker.halfedge_id().unwrap();
+        let he_id2 = halfedge_id;
+        let mut walker = self.walker_from_halfedge(he_id2);
         if walker.face_id().is_some() {
-            walker.as_previous();
-            self.connectivity_info.set_vertex_halfedge( surviving_vertex_id, walker.twin_id());
+            let previous_twin_id = walker.as_previous().twin_id();
+            self.connectivity_info.set_vertex_halfedge( surviving_vertex_id, previous_twin_id);
             self.remove_one_face(he_id2);
         }
         else {
@@ -244,6 +291,7 @@ impl<T: Clone> Mesh<T>
         }
 
         // Remove dying vertex
+        self.interpolate_vertex_attributes(surviving_vertex_id, &[(surviving_vertex_id, 0.5), (dying_vertex_id, 0.5)]);
         self.connectivity_info.remove_vertex(dying_vertex_id);
 
         self.move_vertex_to(surviving_vertex_id, new_position);
@@ -269,9 +317,6 @@ impl<T: Clone> Mesh<T>
         self.connectivity_info.remove_halfedge(halfedge_id2);
         self.connectivity_info.set_halfedge_twin(twin_id1, twin_id2);
         self.connectivity_info.set_vertex_halfedge(vertex_id, Some(twin_id1));
-
-        walker.as_twin();
-
     }
 
     /// Removes the given vertex and all edges and faces it's adjacent to,
@@ -376,27 +421,29 @@ mod tests {
         let mut no_flips = 0;
         let mut mesh = MeshBuilder::<()>::new().square().build().unwrap();
         let num_edges = mesh.num_halfedges();
-        for halfedge_id in mesh.halfedge_iter() {
-            let (v0, v1) = mesh.edge_vertices(halfedge_id);
-
-            if mesh.flip_edge(halfedge_id).is_ok()
-            {
-                mesh.is_valid().unwrap();
-
-                let (v2, v3) = mesh.edge_vertices(halfedge_id);
-                assert_ne!(v0, v2);
-                assert_ne!(v1, v2);
-                assert_ne!(v0, v3);
-                assert_ne!(v1, v3);
-
-                assert!(mesh.connecting_edge(v0, v1).is_none());
-                assert!(mesh.connecting_edge(v2, v3).is_some());
+        for index in 0..mesh.num_halfedge_slots() {
+            if let Some(halfedge_id) = mesh.halfedge_in_slot(index) {
+                let (v0, v1) = mesh.edge_vertices(halfedge_id);
 
-                let edge = mesh.connecting_edge(v2, v3).unwrap();
-                let twin = mesh.walker_from_halfedge(edge).twin_id().unwrap();
-                assert!(edge == halfedge_id || twin == halfedge_id,
-                        format!("Flipped edge {} or flipped edge twin {} should be equal to before flipped edge id {}", edge, twin, halfedge_id));
-                no_flips = no_flips + 1;
+                if mesh.flip_edge(halfedge_id).is_ok()
+                {
+                    mesh.is_valid().unwrap();
+
+                    let (v2, v3) = mesh.edge_vertices(halfedge_id);
+                    assert_ne!(v0, v2);
+                    assert_ne!(v1, v2);
+                    assert_ne!(v0, v3);
+                    assert_ne!(v1, v3);
+
+                    assert!(mesh.connecting_edge(v0, v1).is_none());
+                    assert!(mesh.connecting_edge(v2, v3).is_some());
+
+                    let edge = mesh.connecting_edge(v2, v3).unwrap();
+                    let twin = mesh.walker_from_halfedge(edge).twin_id().unwrap();
+                    assert!(edge == halfedge_id || twin == halfedge_id,
+                            format!("Flipped edge {} or flipped edge twin {} should be equal to before flipped edge id {}", edge, twin, halfedge_id));
+                    no_flips = no_flips + 1;
+                }
             }
         }
         assert_eq!(num_edges, mesh.num_halfedges());
@@ -409,27 +456,29 @@ mod tests {
         let mut no_flips = 0;
         let mut mesh = MeshBuilder::<()>::new().icosahedron().build().unwrap();
         let num_edges = mesh.num_halfedges();
-        for halfedge_id in mesh.halfedge_iter() {
-            let (v0, v1) = mesh.edge_vertices(halfedge_id);
-
-            if mesh.flip_edge(halfedge_id).is_ok()
-            {
-                mesh.is_valid().unwrap();
+        for index in 0..mesh.num_halfedge_slots() {
+            if let Some(halfedge_id) = mesh.halfedge_in_slot(index) {
+                let (v0, v1) = mesh.edge_vertices(halfedge_id);
 
-                let (v2, v3) = mesh.edge_vertices(halfedge_id);
-                assert_ne!(v0, v2);
-                assert_ne!(v1, v2);
-                assert_ne!(v0, v3);
-                assert_ne!(v1, v3);
-
-                assert!(mesh.connecting_edge(v0, v1).is_none());
-                assert!(mesh.connecting_edge(v2, v3).is_some());
-
-                let edge = mesh.connecting_edge(v2, v3).unwrap();
-                let twin = mesh.walker_from_halfedge(edge).twin_id().unwrap();
-                assert!(edge == halfedge_id || twin == halfedge_id,
-                        format!("Flipped edge {} or flipped edge twin {} should be equal to before flipped edge id {}", edge, twin, halfedge_id));
-                no_flips = no_flips + 1;
+                if mesh.flip_edge(halfedge_id).is_ok()
+                {
+                    mesh.is_valid().unwrap();
+
+                    let (v2, v3) = mesh.edge_vertices(halfedge_id);
+                    assert_ne!(v0, v2);
+                    assert_ne!(v1, v2);
+                    assert_ne!(v0, v3);
+                    assert_ne!(v1, v3);
+
+                    assert!(mesh.connecting_edge(v0, v1).is_none());
+                    assert!(mesh.connecting_edge(v2, v3).is_some());
+
+                    let edge = mesh.connecting_edge(v2, v3).unwrap();
+                    let twin = mesh.walker_from_halfedge(edge).twin_id().unwrap();
+                    assert!(edge == halfedge_id || twin == halfedge_id,
+                            format!("Flipped edge {} or flipped edge twin {} should be equal to before flipped edge id {}", edge, twin, halfedge_id));
+                    no_flips = no_flips + 1;
+                }
             }
         }
         assert_eq!(num_edges, mesh.num_halfedges());
@@ -640,6 +689,39 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "generational-ids")]
+    #[test]
+    fn test_stale_ids_after_collapse_edge()
+    {
+        let mut mesh = MeshBuilder::<()>::new().subdivided_triangle().build().unwrap();
+        let halfedge_id = mesh.halfedge_iter().find(|halfedge_id| !mesh.is_edge_on_boundary(*halfedge_id)).unwrap();
+        let face_ids: Vec<_> = mesh.face_iter().collect();
+        mesh.collapse_edge(halfedge_id);
+
+        // Split the remaining face to reuse the freed slots
+        let face_id = mesh.face_iter().next().unwrap();
+        let center = mesh.face_center(face_id);
+        mesh.split_face(face_id, center);
+        assert_eq!(mesh.num_faces(), 3);
+
+        let stale_face_ids: Vec<_> = face_ids.iter().filter(|face_id| !mesh.face_iter().any(|f| f == **face_id)).collect();
+        assert_eq!(stale_face_ids.len(), 2);
+        assert!(mesh.walker_from_halfedge(halfedge_id).vertex_id().is_none());
+        assert!(mesh.face_iter().all(|face_id| mesh.walker_from_face(face_id).face_id() == Some(face_id)));
+    }
+
+    #[cfg(feature = "generational-ids")]
+    #[test]
+    #[should_panic]
+    fn test_stale_face_id_panics()
+    {
+        let mut mesh = MeshBuilder::<()>::new().subdivided_triangle().build().unwrap();
+        let halfedge_id = mesh.halfedge_iter().find(|halfedge_id| !mesh.is_edge_on_boundary(*halfedge_id)).unwrap();
+        let face_id = mesh.walker_from_halfedge(halfedge_id).face_id().unwrap();
+        mesh.collapse_edge(halfedge_id);
+        mesh.face_area(face_id);
+    }
+
     #[test]
     fn test_recursive_collapse_edge()
     {
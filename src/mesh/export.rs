@@ -2,6 +2,8 @@
 
 use crate::mesh::Mesh;
 use crate::mesh::ids::*;
+#[cfg(feature = "obj-io")]
+use crate::mesh::material::Material;
 use crate::mesh::vec3;
 use crate::mesh::InnerSpace;
 
@@ -97,9 +99,8 @@ impl<T: Clone> Mesh<T>
         let mut indices = Vec::with_capacity(self.num_faces() * 3);
         for face_id in self.face_iter()
         {
-            for halfedge_id in self.face_halfedge_iter(face_id) {
-                let vertex_id = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
-                let index = index_map[&vertex_id];
+            for vertex_id in self.face_buffer_vertices(face_id).iter() {
+                let index = index_map[vertex_id];
                 indices.push(index as u32);
             }
         }
@@ -217,8 +218,62 @@ impl<T: Clone> Mesh<T>
         normals
     }
 
+    ///
+    /// Returns the values of the [corner attribute](#corner-attributes) with the given name for each face corner,
+    /// in the same order as the [non_indexed_positions_buffer](#method.non_indexed_positions_buffer) method.
+    /// The value is `None` if the corner has no value, the attribute does not exist or is not of type `A`.
+    ///
+    pub fn non_indexed_corner_attribute_buffer<A: Clone + 'static>(&self, name: &str) -> Vec<Option<A>>
+    {
+        let mut values = Vec::with_capacity(self.num_faces() * 3);
+        for face_id in self.face_iter()
+        {
+            let mut walker = self.walker_from_face(face_id);
+            for _ in 0..3 {
+                values.push(self.corner_attribute(name, walker.halfedge_id().unwrap()));
+                walker.as_next();
+            }
+        }
+        values
+    }
+
+    ///
+    /// Returns index based buffers where a vertex is split into several vertices if the value of the [corner attribute](#corner-attributes)
+    /// with the given name differs between the faces around the vertex, for example at a UV seam.
+    /// The returned buffers are the face indices, the positions of the split vertices and the attribute value of each split vertex.
+    /// Corners with no value are only merged with other corners with no value.
+    ///
+    pub fn seam_split_buffers<A: Clone + PartialEq + 'static>(&self, name: &str) -> (Vec<u32>, Vec<f64>, Vec<Option<A>>)
+    {
+        let mut split_vertices: HashMap<VertexID, Vec<(Option<A>, u32)>> = HashMap::new();
+        let mut indices = Vec::with_capacity(self.num_faces() * 3);
+        let mut positions = Vec::new();
+        let mut values = Vec::new();
+        for face_id in self.face_iter()
+        {
+            for halfedge_id in self.face_halfedge_iter(face_id) {
+                let vertex_id = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                let value = self.corner_attribute::<A>(name, halfedge_id);
+                let splits = split_vertices.entry(vertex_id).or_default();
+                let index = match splits.iter().find(|(v, _)| *v == value) {
+                    Some((_, index)) => *index,
+                    None => {
+                        let index = values.len() as u32;
+                        push_vec3(&mut positions, self.vertex_position(vertex_id));
+                        values.push(value.clone());
+                        splits.push((value, index));
+                        index
+                    }
+                };
+                indices.push(index);
+            }
+        }
+        (indices, positions, values)
+    }
+
     ///
     /// Parses the mesh into a text string that follows the .obj file format and which can then be saved into a file.
+    /// Use [write_obj](#method.write_obj) to write directly to a file without holding the entire text in memory.
     ///
     /// # Examples
     ///
@@ -236,35 +291,51 @@ impl<T: Clone> Mesh<T>
     #[cfg(feature = "obj-io")]
     pub fn parse_as_obj(&self) -> String
     {
-        let mut output = String::from("o object\n");
-
-        let positions = self.positions_buffer();
-        for i in 0..self.num_vertices()
-        {
-            output += &format!("v {} {} {}\n", positions[i*3], positions[i*3 + 1], positions[i*3 + 2]);
-        }
+        let mut output = Vec::new();
+        self.write_obj(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
 
-        let normals = self.normals_buffer();
-        for i in 0..self.num_vertices()
-        {
-            output += &format!("vn {} {} {}\n", normals[i*3], normals[i*3 + 1], normals[i*3 + 2]);
-        }
+    ///
+    /// Writes the mesh to the given writer following the .obj file format.
+    /// The vertices, normals and faces are written one at a time, so the file content is never held in memory.
+    /// Wrap the writer in a [BufWriter](std::io::BufWriter) when writing to a file to avoid many small writes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data directly to an .obj file
+    /// let mut writer = std::io::BufWriter::new(std::fs::File::create("foo.obj")?);
+    /// mesh.write_obj(&mut writer)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the writer.
+    ///
+    #[cfg(feature = "obj-io")]
+    pub fn write_obj<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()>
+    {
+        writeln!(writer, "o object")?;
+        self.write_obj_vertices(writer)?;
 
-        let indices = self.indices_buffer();
-        for i in 0..self.num_faces() {
-            let mut face = String::new();
-            for j in 0..3 {
-                let index = indices[i*3 + j] + 1;
-                face += &format!(" {}//{}", index, index);
-            }
-            output += &format!("f{}\n", face);
+        let index_map = self.vertex_index_map();
+        for face_id in self.face_iter() {
+            write_obj_face(writer, self.face_buffer_vertices(face_id), &index_map)?;
         }
-        output
+        Ok(())
     }
 
     ///
     /// Parses the mesh into a text string that follows the .obj file format and which can then be saved into a file.
     /// Includes materials. OBJ first, MTL second.
+    /// The materials are named `mat{N}`, where `N` is the number returned by `material_fn` for the tag of a face, and are given distinct diffuse colors.
+    /// Use [parse_as_obj_with_materials](#method.parse_as_obj_with_materials) to specify the materials
+    /// and [write_obj_multimaterial](#method.write_obj_multimaterial) to write directly to files without holding the entire text in memory.
     ///
     /// # Examples
     ///
@@ -281,76 +352,202 @@ impl<T: Clone> Mesh<T>
     /// # }
     /// ```
     #[cfg(feature = "obj-io")]
-    pub fn parse_as_obj_multimaterial(&self, mut material_fn: impl FnMut(T) -> usize) -> (String, String) where T: PartialEq
+    pub fn parse_as_obj_multimaterial(&self, material_fn: impl FnMut(T) -> usize) -> (String, String)
     {
-        let mut output = String::from("o object\n");
+        let mut obj = Vec::new();
+        let mut mtl = Vec::new();
+        self.write_obj_multimaterial(&mut obj, &mut mtl, material_fn).unwrap();
+        (String::from_utf8(obj).unwrap(), String::from_utf8(mtl).unwrap())
+    }
 
-        let positions = self.positions_buffer();
-        for i in 0..self.num_vertices()
-        {
-            output += &format!("v {} {} {}\n", positions[i*3], positions[i*3 + 1], positions[i*3 + 2]);
+    ///
+    /// Writes the mesh to the given writers following the .obj file format, including materials.
+    /// The .obj content is written to `obj_writer` and the .mtl content to `mtl_writer`.
+    /// The vertices, normals and faces are written one at a time, so the file content is never held in memory.
+    /// See [parse_as_obj_multimaterial](#method.parse_as_obj_multimaterial) for how the materials are defined.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data directly to an .obj and an .mtl file
+    /// let mut obj_writer = std::io::BufWriter::new(std::fs::File::create("foo.obj")?);
+    /// let mut mtl_writer = std::io::BufWriter::new(std::fs::File::create("foo.mtl")?);
+    /// mesh.write_obj_multimaterial(&mut obj_writer, &mut mtl_writer, |_| 0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the writers.
+    ///
+    #[cfg(feature = "obj-io")]
+    pub fn write_obj_multimaterial<W: std::io::Write, M: std::io::Write>(&self, obj_writer: &mut W, mtl_writer: &mut M,
+                                                                           mut material_fn: impl FnMut(T) -> usize) -> std::io::Result<()>
+    {
+        let face_materials = self.face_iter().map(|face_id| material_fn(self.face_tag(face_id))).collect::<Vec<_>>();
+        let mut numbers = face_materials.clone();
+        numbers.sort_unstable();
+        numbers.dedup();
+
+        let wheel = [vec3(1.0, 0.0, 0.0), vec3(1.0, 1.0, 0.0), vec3(0.0, 1.0, 0.0),
+            vec3(0.0, 1.0, 1.0), vec3(0.0, 0.0, 1.0), vec3(1.0, 0.0, 1.0), vec3(1.0, 0.0, 0.0)];
+        let materials = numbers.iter().enumerate().map(|(i, number)| {
+            let hue = i as f64 / numbers.len() as f64;
+            let index = (6.0 * hue).floor() as usize;
+            let frac = 6.0 * hue - index as f64;
+            Material { name: format!("mat{}", number), diffuse_color: Some(wheel[index].lerp(wheel[index + 1], frac)), ..Default::default() }
+        }).collect::<Vec<_>>();
+
+        let face_materials = face_materials.into_iter().map(|number| numbers.binary_search(&number).unwrap()).collect();
+        self.write_obj_with_material_indices(obj_writer, mtl_writer, None, &materials, face_materials)
+    }
+
+    ///
+    /// Parses the mesh into a text string that follows the .obj file format and a text string that follows the .mtl file format,
+    /// which can then be saved into two files. OBJ first, MTL second.
+    /// The `material_fn` function returns the [material](crate::mesh::material::Material) of a face given its tag,
+    /// where materials with the same name are considered the same material, and the first definition of a material is written to the .mtl file.
+    /// The .obj file refers to the .mtl file by the given file name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// let mesh = MeshBuilder::<u32>::new().with_tags((0..12).map(|i| i % 2).collect()).cube().build().unwrap();
+    ///
+    /// let (obj_source, mtl_source) = mesh.parse_as_obj_with_materials("foo.mtl", |tag| Material {
+    ///     name: if tag == 0 { "red".to_string() } else { "green".to_string() },
+    ///     diffuse_color: Some(if tag == 0 { vec3(1.0, 0.0, 0.0) } else { vec3(0.0, 1.0, 0.0) }),
+    ///     ..Default::default()
+    /// });
+    /// assert!(obj_source.starts_with("mtllib foo.mtl"));
+    /// assert!(mtl_source.contains("newmtl red\nKd 1 0 0\n"));
+    /// ```
+    #[cfg(feature = "obj-io")]
+    pub fn parse_as_obj_with_materials(&self, mtl_file_name: &str, material_fn: impl FnMut(T) -> Material) -> (String, String)
+    {
+        let mut obj = Vec::new();
+        let mut mtl = Vec::new();
+        self.write_obj_with_materials(&mut obj, &mut mtl, mtl_file_name, material_fn).unwrap();
+        (String::from_utf8(obj).unwrap(), String::from_utf8(mtl).unwrap())
+    }
+
+    ///
+    /// Writes the mesh to the given writers following the .obj and .mtl file formats, with the materials given by `material_fn`
+    /// as described in [parse_as_obj_with_materials](#method.parse_as_obj_with_materials).
+    /// The .obj content is written to `obj_writer` and the .mtl content to `mtl_writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data directly to an .obj and an .mtl file
+    /// let mut obj_writer = std::io::BufWriter::new(std::fs::File::create("foo.obj")?);
+    /// let mut mtl_writer = std::io::BufWriter::new(std::fs::File::create("foo.mtl")?);
+    /// mesh.write_obj_with_materials(&mut obj_writer, &mut mtl_writer, "foo.mtl",
+    ///     |_| Material { name: "brick".to_string(), diffuse_texture: Some("brick.png".to_string()), ..Default::default() })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the writers.
+    ///
+    #[cfg(feature = "obj-io")]
+    pub fn write_obj_with_materials<W: std::io::Write, M: std::io::Write>(&self, obj_writer: &mut W, mtl_writer: &mut M, mtl_file_name: &str,
+                                                                            mut material_fn: impl FnMut(T) -> Material) -> std::io::Result<()>
+    {
+        let mut materials: Vec<Material> = Vec::new();
+        let mut material_indices: HashMap<String, usize> = HashMap::new();
+        let mut face_materials = Vec::with_capacity(self.num_faces());
+        for face_id in self.face_iter() {
+            let material = material_fn(self.face_tag(face_id));
+            let index = match material_indices.get(&material.name) {
+                Some(index) => *index,
+                None => {
+                    material_indices.insert(material.name.clone(), materials.len());
+                    materials.push(material);
+                    materials.len() - 1
+                }
+            };
+            face_materials.push(index);
         }
+        self.write_obj_with_material_indices(obj_writer, mtl_writer, Some(mtl_file_name), &materials, face_materials)
+    }
 
-        let normals = self.normals_buffer();
-        for i in 0..self.num_vertices()
-        {
-            output += &format!("vn {} {} {}\n", normals[i*3], normals[i*3 + 1], normals[i*3 + 2]);
-        }
-
-        let faces = self.face_iter().collect::<Vec<_>>();
-        let buffer = self.indices_buffer();
-        let mut indices = buffer.chunks_exact(3).enumerate().collect::<Vec<_>>();
-        indices.sort_by_cached_key(|(i, _)| material_fn(self.face_tag(faces[*i])));
-        let indices = indices.into_iter().flat_map(|(_, i)| vec![i[0], i[1], i[2]]).collect::<Vec<_>>();
-
-        let mut prev = FaceID::new(0);
-        let mut materials = vec![];
-        for (i, face_id) in self.face_iter().enumerate() {
-            if i == 0 || self.face_tag(face_id) != self.face_tag(prev) {
-                let mat = material_fn(self.face_tag(face_id));
-                output += &format!("usemtl mat{}\n", mat);
-                materials.push(mat);
-            }
+    /// Writes the .obj and .mtl files, where `face_materials` contains the index into `materials` for each face in the order of the face iterator.
+    #[cfg(feature = "obj-io")]
+    fn write_obj_with_material_indices<W: std::io::Write, M: std::io::Write>(&self, obj_writer: &mut W, mtl_writer: &mut M, mtl_file_name: Option<&str>,
+                                                                               materials: &[Material], face_materials: Vec<usize>) -> std::io::Result<()>
+    {
+        if let Some(mtl_file_name) = mtl_file_name {
+            writeln!(obj_writer, "mtllib {}", mtl_file_name)?;
+        }
+        writeln!(obj_writer, "o object")?;
+        self.write_obj_vertices(obj_writer)?;
 
-            let mut face = String::new();
-            for j in 0..3 {
-                let index = indices[i*3 + j] + 1;
-                face += &format!(" {}//{}", index, index);
+        // Group the faces by material, such that each material is only used once
+        let index_map = self.vertex_index_map();
+        let mut faces = self.face_iter().zip(face_materials).collect::<Vec<_>>();
+        faces.sort_by_key(|(_, material)| *material);
+        let mut current_material = None;
+        for (face_id, material) in faces {
+            if current_material != Some(material) {
+                writeln!(obj_writer, "usemtl {}", materials[material].name)?;
+                current_material = Some(material);
             }
-            output += &format!("f{}\n", face);
-
-            prev = face_id;
+            write_obj_face(obj_writer, self.face_buffer_vertices(face_id), &index_map)?;
         }
 
-        let mut mtl = String::new();
+        for material in materials {
+            material.write_mtl(mtl_writer)?;
+        }
+        Ok(())
+    }
 
-        let wheel = vec![vec3(1.0, 0.0, 0.0), vec3(1.0, 1.0, 0.0), vec3(0.0, 1.0, 0.0),
-            vec3(0.0, 1.0, 1.0), vec3(0.0, 0.0, 1.0), vec3(1.0, 0.0, 1.0), vec3(1.0, 0.0, 0.0)];
-        
-        let len = materials.len();
-        for (i, mat) in materials.into_iter().enumerate() {
-            mtl += &format!("newmtl mat{}", mat);
-            
-            let hue = i as f64 / len as f64;
-            let index = (6.0 * hue).floor() as usize;
-            let frac = 6.0 * hue - index as f64;
-            let color = wheel[index].lerp(wheel[index + 1], frac);
-            mtl += &format!("Kd {} {} {}", color.x, color.y, color.z);
-            mtl += "\n";
+    /// Writes the positions and normals of all vertices in the .obj file format.
+    #[cfg(feature = "obj-io")]
+    fn write_obj_vertices<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()>
+    {
+        for vertex_id in self.vertex_iter() {
+            let position = self.vertex_position(vertex_id);
+            writeln!(writer, "v {} {} {}", position.x, position.y, position.z)?;
+        }
+        for vertex_id in self.vertex_iter() {
+            let normal = self.vertex_normal(vertex_id);
+            writeln!(writer, "vn {} {} {}", normal.x, normal.y, normal.z)?;
         }
+        Ok(())
+    }
+
+    /// Returns the index of each vertex in the buffers, which is the order of the [vertex iterator](#method.vertex_iter).
+    #[cfg(any(feature = "obj-io", feature = "ply-io", feature = "off-io", feature = "vtk-io"))]
+    fn vertex_index_map(&self) -> HashMap<VertexID, u32>
+    {
+        self.vertex_iter().enumerate().map(|(i, vertex_id)| (vertex_id, i as u32)).collect()
+    }
 
-        (output, mtl)
+    /// Returns the vertices of the face in the order used in the [indices buffer](#method.indices_buffer).
+    fn face_buffer_vertices(&self, face_id: FaceID) -> [VertexID; 3]
+    {
+        let mut vertices = [VertexID::new(0); 3];
+        for (vertex_id, halfedge_id) in vertices.iter_mut().zip(self.face_halfedge_iter(face_id)) {
+            *vertex_id = self.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+        }
+        vertices
     }
 
     ///
     /// Parses the mesh into a byte array that follows a custom file format (.3d) and which can then be saved into a file.
     /// The .3d file format is optimized for loading and saving using Rust (using [serde](https://docs.rs/serde/) and [bindcode](https://docs.rs/bincode/)).
-    /// The layout is:
-    /// magic_number: u8, // Always 61
-    //  version: u8,
-    //  indices: Vec<u32>,
-    //  positions: Vec<f32>,
-    //  normals: Vec<f32>
+    /// The mesh is written in version 2 of the format with the [default options](crate::mesh::format_3d::Options3d),
+    /// see [format_3d](crate::mesh::format_3d) for a description of the format.
     ///
     /// # Examples
     ///
@@ -366,83 +563,990 @@ impl<T: Clone> Mesh<T>
     /// # }
     /// ```
     #[cfg(feature = "3d-io")]
-    pub fn parse_as_3d(&self) -> Result<Vec<u8>, bincode::Error>
+    pub fn parse_as_3d(&self) -> Result<Vec<u8>, crate::mesh::Error>
     {
-        let export_mesh = crate::mesh::IOMesh {magic_number: 61, version: 1, indices: self.indices_buffer(),
-            positions: self.positions_buffer_f32(), normals: self.normals_buffer_f32()};
-        bincode::serialize(&export_mesh)
+        self.parse_as_3d_with_options(&crate::mesh::format_3d::Options3d::default())
     }
-}
 
-fn push_vec3(vec: &mut Vec<f64>, vec3: crate::mesh::math::Vec3)
-{
-    for i in 0..3 {
-        vec.push(vec3[i]);
+    ///
+    /// Parses the mesh into a byte array that follows version 2 of the custom .3d file format using the given options,
+    /// which specify the precision and compression of the positions and the vertex attributes to store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut mesh = MeshBuilder::<()>::new().icosahedron().build().unwrap();
+    /// mesh.add_vertex_attribute("height", |values: &[(f64, f64)]| values.iter().map(|(value, weight)| weight * value).sum());
+    /// let heights: Vec<_> = mesh.vertex_iter().map(|vertex_id| (vertex_id, mesh.vertex_position(vertex_id).y)).collect();
+    /// for (vertex_id, height) in heights {
+    ///     mesh.set_vertex_attribute("height", vertex_id, height).unwrap();
+    /// }
+    ///
+    /// let options = Options3d { compression: Compression::Quantized { bits: 16 }, vertex_attributes: vec!["height".to_string()], ..Default::default() };
+    /// let bytes = mesh.parse_as_3d_with_options(&options).unwrap();
+    ///
+    /// let decoded = MeshBuilder::<()>::new().with_3d(&bytes).unwrap().build().unwrap();
+    /// let vertex_id = decoded.vertex_iter().next().unwrap();
+    /// assert_eq!(decoded.vertex_attribute::<f64>("height", vertex_id), Some(mesh.vertex_position(mesh.vertex_iter().next().unwrap()).y));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [AttributeNotFound](crate::mesh::Error::AttributeNotFound) error if one of the vertex attributes does not exist
+    /// or is not of a supported type.
+    ///
+    #[cfg(feature = "3d-io")]
+    pub fn parse_as_3d_with_options(&self, options: &crate::mesh::format_3d::Options3d) -> Result<Vec<u8>, crate::mesh::Error>
+    {
+        crate::mesh::format_3d::encode(self, options, None)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::MeshBuilder;
-    use crate::mesh::math::*;
-
-    #[test]
-    fn test_parse_as_3d() {
-        let mesh = MeshBuilder::<()>::new().cylinder(3, 16).build().unwrap();
-        let encoded: Vec<u8> = mesh.parse_as_3d().unwrap();
-        let decoded = MeshBuilder::<()>::new().with_3d(&encoded).unwrap().build().unwrap();
 
-        assert_eq!(mesh.num_vertices(), decoded.num_vertices());
-        assert_eq!(mesh.num_faces(), decoded.num_faces());
+    ///
+    /// Same as [parse_as_3d_with_options](#method.parse_as_3d_with_options), except that the face tags are serialized into the file as well.
+    /// Use [with_tagged_3d](crate::mesh_builder::MeshBuilder::with_tagged_3d) to read the tags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// let mesh = MeshBuilder::<u32>::new().with_tags((0..12).collect()).cube().build().unwrap();
+    ///
+    /// let bytes = mesh.parse_as_tagged_3d(&Options3d::default()).unwrap();
+    ///
+    /// let decoded = MeshBuilder::<u32>::new().with_tagged_3d(&bytes).unwrap().build().unwrap();
+    /// assert_eq!(decoded.face_iter().map(|face_id| decoded.face_tag(face_id)).collect::<Vec<_>>(), (0..12).collect::<Vec<_>>());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [AttributeNotFound](crate::mesh::Error::AttributeNotFound) error if one of the vertex attributes does not exist
+    /// or is not of a supported type and a [Bincode](crate::mesh::Error::Bincode) error if the tags cannot be serialized.
+    ///
+    #[cfg(feature = "3d-io")]
+    pub fn parse_as_tagged_3d(&self, options: &crate::mesh::format_3d::Options3d) -> Result<Vec<u8>, crate::mesh::Error>
+        where T: serde::Serialize
+    {
+        let tags = self.face_iter().map(|face_id| self.face_tag(face_id)).collect::<Vec<_>>();
+        crate::mesh::format_3d::encode(self, options, Some(bincode::serialize(&tags)?))
     }
 
-    #[test]
-    fn test_indexed_export() {
-        let mesh = MeshBuilder::<()>::new().cylinder(3, 16).build().unwrap();
-        let indices = mesh.indices_buffer();
-        let positions = mesh.positions_buffer();
-        let normals = mesh.normals_buffer();
-
-        assert_eq!(indices.len(), mesh.num_faces() * 3);
-        assert_eq!(positions.len(), mesh.num_vertices() * 3);
-        assert_eq!(normals.len(), mesh.num_vertices() * 3);
-
-        for face in 0..positions.len()/9 {
-            let vertices = (indices[3*face] as usize, indices[3*face + 1] as usize, indices[3*face + 2] as usize);
-            let p0 = vec3(positions[3*vertices.0], positions[3*vertices.0+1], positions[3*vertices.0+2]);
-            let p1 = vec3(positions[3*vertices.1], positions[3*vertices.1+1], positions[3*vertices.1+2]);
-            let p2 = vec3(positions[3*vertices.2], positions[3*vertices.2+1], positions[3*vertices.2+2]);
-            let center = (p0 + p1 + p2) / 3.0;
-            let face_id = mesh.face_iter().find(|face_id| (mesh.face_center(*face_id) - center).magnitude() < 0.00001);
-            assert!(face_id.is_some());
-
-            let n0 = vec3(normals[3*vertices.0], normals[3*vertices.0+1], normals[3*vertices.0+2]);
-            let n1 = vec3(normals[3*vertices.1], normals[3*vertices.1+1], normals[3*vertices.1+2]);
-            let n2 = vec3(normals[3*vertices.2], normals[3*vertices.2+1], normals[3*vertices.2+2]);
-
-            let (v0, v1, v2) = mesh.face_vertices(face_id.unwrap());
+    ///
+    /// Parses the mesh into a byte array that follows the binary .stl file format and which can then be saved into a file.
+    /// Each face is written as a separate triangle with the face normal, so the vertex connectivity and the face tags are not stored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data to a byte array
+    /// let bytes = mesh.parse_as_stl_binary();
+    ///
+    /// // Write the byte array to an .stl file
+    /// std::fs::write("foo.stl", bytes)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "stl-io")]
+    pub fn parse_as_stl_binary(&self) -> Vec<u8>
+    {
+        let mut output = Vec::with_capacity(84 + 50 * self.num_faces());
+        let mut header = b"binary stl exported by tri-mesh".to_vec();
+        header.resize(80, 0);
+        output.extend_from_slice(&header);
+        output.extend_from_slice(&(self.num_faces() as u32).to_le_bytes());
 
-            assert!(n0 == mesh.vertex_normal(v0) || n1 == mesh.vertex_normal(v0) || n2 == mesh.vertex_normal(v0));
-            assert!(n0 == mesh.vertex_normal(v1) || n1 == mesh.vertex_normal(v1) || n2 == mesh.vertex_normal(v1));
-            assert!(n0 == mesh.vertex_normal(v2) || n1 == mesh.vertex_normal(v2) || n2 == mesh.vertex_normal(v2));
+        for face_id in self.face_iter() {
+            let (p0, p1, p2) = self.face_positions(face_id);
+            for vector in [self.face_normal(face_id), p0, p1, p2].iter() {
+                for i in 0..3 {
+                    output.extend_from_slice(&(vector[i] as f32).to_le_bytes());
+                }
+            }
+            // Attribute byte count
+            output.extend_from_slice(&[0, 0]);
         }
+        output
     }
 
-    #[test]
-    fn test_non_indexed_export() {
-        let mesh = MeshBuilder::<()>::new().cylinder(3, 16).build().unwrap();
-        let positions = mesh.non_indexed_positions_buffer();
-        let normals = mesh.non_indexed_normals_buffer();
-
-        assert_eq!(positions.len(), mesh.num_faces() * 3 * 3);
-        assert_eq!(normals.len(), mesh.num_faces() * 3 * 3);
+    ///
+    /// Parses the mesh into a text string that follows the ASCII .stl file format and which can then be saved into a file.
+    /// Each face is written as a separate triangle with the face normal, so the vertex connectivity and the face tags are not stored.
+    /// Use [write_stl_ascii](#method.write_stl_ascii) to write directly to a file without holding the entire text in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data to a string
+    /// let stl_source = mesh.parse_as_stl_ascii();
+    ///
+    /// // Write the string to an .stl file
+    /// std::fs::write("foo.stl", stl_source)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "stl-io")]
+    pub fn parse_as_stl_ascii(&self) -> String
+    {
+        let mut output = Vec::new();
+        self.write_stl_ascii(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
 
-        for face in 0..positions.len()/9 {
-            let vertices = (9*face, 9*face+3, 9*face+6);
-            let p0 = vec3(positions[vertices.0], positions[vertices.0+1], positions[vertices.0+2]);
-            let p1 = vec3(positions[vertices.1], positions[vertices.1+1], positions[vertices.1+2]);
-            let p2 = vec3(positions[vertices.2], positions[vertices.2+1], positions[vertices.2+2]);
-            let center = (p0 + p1 + p2) / 3.0;
+    ///
+    /// Writes the mesh to the given writer following the ASCII .stl file format, see [parse_as_stl_ascii](#method.parse_as_stl_ascii).
+    /// The faces are written one at a time, so the file content is never held in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data directly to an .stl file
+    /// let mut writer = std::io::BufWriter::new(std::fs::File::create("foo.stl")?);
+    /// mesh.write_stl_ascii(&mut writer)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the writer.
+    ///
+    #[cfg(feature = "stl-io")]
+    pub fn write_stl_ascii<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()>
+    {
+        writeln!(writer, "solid object")?;
+        for face_id in self.face_iter() {
+            let normal = self.face_normal(face_id);
+            let (p0, p1, p2) = self.face_positions(face_id);
+            writeln!(writer, "facet normal {} {} {}", normal.x, normal.y, normal.z)?;
+            writeln!(writer, "outer loop")?;
+            for position in [p0, p1, p2].iter() {
+                writeln!(writer, "vertex {} {} {}", position.x, position.y, position.z)?;
+            }
+            writeln!(writer, "endloop\nendfacet")?;
+        }
+        writeln!(writer, "endsolid object")
+    }
+
+    ///
+    /// Parses the mesh into a text string that follows the ASCII .ply file format and which can then be saved into a file.
+    /// The positions and normals of the vertices are written together with the colors if all vertices have a value in the `"color"`
+    /// [vertex attribute](#vertex-attributes) of type [Vec4](crate::mesh::math::Vec4).
+    /// Use [write_ply](#method.write_ply) to write directly to a file without holding the entire text in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data to a string
+    /// let ply_source = mesh.parse_as_ply();
+    ///
+    /// // Write the string to a .ply file
+    /// std::fs::write("foo.ply", ply_source)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "ply-io")]
+    pub fn parse_as_ply(&self) -> String
+    {
+        let mut output = Vec::new();
+        self.write_ply(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    ///
+    /// Writes the mesh to the given writer following the ASCII .ply file format, see [parse_as_ply](#method.parse_as_ply).
+    /// The vertices and faces are written one at a time, so the file content is never held in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data directly to a .ply file
+    /// let mut writer = std::io::BufWriter::new(std::fs::File::create("foo.ply")?);
+    /// mesh.write_ply(&mut writer)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the writer.
+    ///
+    #[cfg(feature = "ply-io")]
+    pub fn write_ply<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()>
+    {
+        self.write_ply_internal(writer, None::<(&str, fn(T) -> i32)>)
+    }
+
+    ///
+    /// Parses the mesh into a text string that follows the ASCII .ply file format like [parse_as_ply](#method.parse_as_ply)
+    /// and in addition writes the integer returned by `value_fn` for the tag of each face as a face property with the given name.
+    /// Use [write_ply_with_face_property](#method.write_ply_with_face_property) to write directly to a file without holding the entire text in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<u32>::new().cube().build().unwrap();
+    /// // Write the mesh data to a string with the tags as face labels
+    /// let ply_source = mesh.parse_as_ply_with_face_property("label", |tag| tag as i32);
+    ///
+    /// // Write the string to a .ply file
+    /// std::fs::write("foo.ply", ply_source)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "ply-io")]
+    pub fn parse_as_ply_with_face_property(&self, property: &str, value_fn: impl FnMut(T) -> i32) -> String
+    {
+        let mut output = Vec::new();
+        self.write_ply_with_face_property(&mut output, property, value_fn).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    ///
+    /// Writes the mesh to the given writer following the ASCII .ply file format with a face property,
+    /// see [parse_as_ply_with_face_property](#method.parse_as_ply_with_face_property).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<u32>::new().cube().build().unwrap();
+    /// // Write the mesh data directly to a .ply file with the tags as face labels
+    /// let mut writer = std::io::BufWriter::new(std::fs::File::create("foo.ply")?);
+    /// mesh.write_ply_with_face_property(&mut writer, "label", |tag| tag as i32)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the writer.
+    ///
+    #[cfg(feature = "ply-io")]
+    pub fn write_ply_with_face_property<W: std::io::Write>(&self, writer: &mut W, property: &str, value_fn: impl FnMut(T) -> i32) -> std::io::Result<()>
+    {
+        self.write_ply_internal(writer, Some((property, value_fn)))
+    }
+
+    #[cfg(feature = "ply-io")]
+    fn write_ply_internal<W: std::io::Write>(&self, writer: &mut W, face_property: Option<(&str, impl FnMut(T) -> i32)>) -> std::io::Result<()>
+    {
+        let has_colors = self.vertex_iter().all(|vertex_id| self.vertex_attribute::<crate::mesh::math::Vec4>("color", vertex_id).is_some());
+
+        writeln!(writer, "ply\nformat ascii 1.0")?;
+        writeln!(writer, "element vertex {}", self.num_vertices())?;
+        writeln!(writer, "property double x\nproperty double y\nproperty double z")?;
+        writeln!(writer, "property double nx\nproperty double ny\nproperty double nz")?;
+        if has_colors {
+            writeln!(writer, "property uchar red\nproperty uchar green\nproperty uchar blue\nproperty uchar alpha")?;
+        }
+        writeln!(writer, "element face {}", self.num_faces())?;
+        writeln!(writer, "property list uchar int vertex_indices")?;
+        if let Some((name, _)) = face_property.as_ref() {
+            writeln!(writer, "property int {}", name)?;
+        }
+        writeln!(writer, "end_header")?;
+
+        for vertex_id in self.vertex_iter() {
+            let position = self.vertex_position(vertex_id);
+            let normal = self.vertex_normal(vertex_id);
+            write!(writer, "{} {} {} {} {} {}", position.x, position.y, position.z, normal.x, normal.y, normal.z)?;
+            if has_colors {
+                let color = self.vertex_attribute::<crate::mesh::math::Vec4>("color", vertex_id).unwrap()
+                    .map(|c| (c.max(0.0).min(1.0) * 255.0).round() as u8);
+                write!(writer, " {} {} {} {}", color.x, color.y, color.z, color.w)?;
+            }
+            writeln!(writer)?;
+        }
+
+        let index_map = self.vertex_index_map();
+        let mut face_property = face_property;
+        for face_id in self.face_iter() {
+            let vertices = self.face_buffer_vertices(face_id);
+            write!(writer, "3 {} {} {}", index_map[&vertices[0]], index_map[&vertices[1]], index_map[&vertices[2]])?;
+            if let Some((_, value_fn)) = face_property.as_mut() {
+                write!(writer, " {}", value_fn(self.face_tag(face_id)))?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Parses the mesh into a text string that follows the .gltf file format (glTF 2.0) and which can then be saved into a file.
+    /// The binary data is embedded in the file as a base64 encoded buffer.
+    /// The faces are grouped into one primitive for each distinct face tag, in the order in which the tags first appear,
+    /// and all primitives share the positions and normals of the vertices.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data to a string
+    /// let gltf_source = mesh.parse_as_gltf();
+    ///
+    /// // Write the string to a .gltf file
+    /// std::fs::write("foo.gltf", gltf_source)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "gltf-io")]
+    pub fn parse_as_gltf(&self) -> String where T: PartialEq
+    {
+        let (mut document, buffer) = self.gltf_document();
+        document["buffers"][0]["uri"] = format!("data:application/octet-stream;base64,{}", base64::encode(&buffer)).into();
+        serde_json::to_string_pretty(&document).unwrap()
+    }
+
+    ///
+    /// Parses the mesh into a byte array that follows the binary .glb file format (glTF 2.0) and which can then be saved into a file.
+    /// The content is the same as for [parse_as_gltf](#method.parse_as_gltf), except that the buffer is stored as binary data.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data to a byte array
+    /// let bytes = mesh.parse_as_glb();
+    ///
+    /// // Write the byte array to a .glb file
+    /// std::fs::write("foo.glb", bytes)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "gltf-io")]
+    pub fn parse_as_glb(&self) -> Vec<u8> where T: PartialEq
+    {
+        let (document, mut buffer) = self.gltf_document();
+        let mut json = serde_json::to_vec(&document).unwrap();
+        // Chunks must be aligned to four bytes, padded with spaces and zeros respectively
+        json.resize(json.len() + (4 - json.len() % 4) % 4, b' ');
+        buffer.resize(buffer.len() + (4 - buffer.len() % 4) % 4, 0);
+
+        let length = 12 + 8 + json.len() + 8 + buffer.len();
+        let mut output = Vec::with_capacity(length);
+        output.extend_from_slice(b"glTF");
+        output.extend_from_slice(&2u32.to_le_bytes());
+        output.extend_from_slice(&(length as u32).to_le_bytes());
+        output.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        output.extend_from_slice(b"JSON");
+        output.extend_from_slice(&json);
+        output.extend_from_slice(&(buffer.len() as u32).to_le_bytes());
+        output.extend_from_slice(b"BIN\0");
+        output.extend_from_slice(&buffer);
+        output
+    }
+
+    /// Returns the glTF json document, without the uri of the buffer, together with the content of the buffer.
+    #[cfg(feature = "gltf-io")]
+    fn gltf_document(&self) -> (serde_json::Value, Vec<u8>) where T: PartialEq
+    {
+        use serde_json::json;
+        const ARRAY_BUFFER: u32 = 34962;
+        const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+        const FLOAT: u32 = 5126;
+        const UNSIGNED_INT: u32 = 5125;
+
+        let positions = self.positions_buffer_f32();
+        let normals = self.normals_buffer_f32();
+        let indices = self.indices_buffer();
+
+        let mut groups: Vec<(T, Vec<u32>)> = Vec::new();
+        for (face_id, face_indices) in self.face_iter().zip(indices.chunks_exact(3)) {
+            let tag = self.face_tag(face_id);
+            match groups.iter_mut().find(|(t, _)| *t == tag) {
+                Some((_, group)) => group.extend_from_slice(face_indices),
+                None => groups.push((tag, face_indices.to_vec()))
+            }
+        }
+
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for position in positions.chunks_exact(3) {
+            for i in 0..3 {
+                min[i] = min[i].min(position[i]);
+                max[i] = max[i].max(position[i]);
+            }
+        }
+
+        let mut buffer = Vec::with_capacity(4 * (positions.len() + normals.len() + indices.len()));
+        buffer.extend(positions.iter().flat_map(|value| value.to_le_bytes().to_vec()));
+        buffer.extend(normals.iter().flat_map(|value| value.to_le_bytes().to_vec()));
+        let mut buffer_views = vec![
+            json!({"buffer": 0, "byteOffset": 0, "byteLength": 4 * positions.len(), "target": ARRAY_BUFFER}),
+            json!({"buffer": 0, "byteOffset": 4 * positions.len(), "byteLength": 4 * normals.len(), "target": ARRAY_BUFFER})
+        ];
+        let mut accessors = vec![
+            json!({"bufferView": 0, "componentType": FLOAT, "count": self.num_vertices(), "type": "VEC3", "min": min, "max": max}),
+            json!({"bufferView": 1, "componentType": FLOAT, "count": self.num_vertices(), "type": "VEC3"})
+        ];
+        let mut primitives = Vec::with_capacity(groups.len());
+        for (_, group) in groups.iter() {
+            buffer_views.push(json!({"buffer": 0, "byteOffset": buffer.len(), "byteLength": 4 * group.len(), "target": ELEMENT_ARRAY_BUFFER}));
+            accessors.push(json!({"bufferView": buffer_views.len() - 1, "componentType": UNSIGNED_INT, "count": group.len(), "type": "SCALAR"}));
+            primitives.push(json!({"attributes": {"POSITION": 0, "NORMAL": 1}, "indices": accessors.len() - 1, "mode": 4}));
+            buffer.extend(group.iter().flat_map(|index| index.to_le_bytes().to_vec()));
+        }
+
+        let document = json!({
+            "asset": {"version": "2.0", "generator": "tri-mesh"},
+            "scene": 0,
+            "scenes": [{"nodes": [0]}],
+            "nodes": [{"mesh": 0}],
+            "meshes": [{"primitives": primitives}],
+            "buffers": [{"byteLength": buffer.len()}],
+            "bufferViews": buffer_views,
+            "accessors": accessors
+        });
+        (document, buffer)
+    }
+
+    ///
+    /// Parses the mesh into a text string that follows the .off file format and which can then be saved into a file.
+    /// Use [write_off](#method.write_off) to write directly to a file without holding the entire text in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data to a string
+    /// let off_source = mesh.parse_as_off();
+    ///
+    /// // Write the string to an .off file
+    /// std::fs::write("foo.off", off_source)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "off-io")]
+    pub fn parse_as_off(&self) -> String
+    {
+        let mut output = Vec::new();
+        self.write_off(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    ///
+    /// Writes the mesh to the given writer following the .off file format.
+    /// The vertices and faces are written one at a time, so the file content is never held in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data directly to an .off file
+    /// let mut writer = std::io::BufWriter::new(std::fs::File::create("foo.off")?);
+    /// mesh.write_off(&mut writer)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the writer.
+    ///
+    #[cfg(feature = "off-io")]
+    pub fn write_off<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()>
+    {
+        writeln!(writer, "OFF\n{} {} {}", self.num_vertices(), self.num_faces(), self.num_edges())?;
+        for vertex_id in self.vertex_iter() {
+            let position = self.vertex_position(vertex_id);
+            writeln!(writer, "{} {} {}", position.x, position.y, position.z)?;
+        }
+        self.write_indexed_faces(writer)
+    }
+
+    ///
+    /// Parses the mesh into a text string that follows the ASCII legacy .vtk file format (polydata) and which can then be saved into a file.
+    /// Use [write_vtk](#method.write_vtk) to write directly to a file without holding the entire text in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data to a string
+    /// let vtk_source = mesh.parse_as_vtk();
+    ///
+    /// // Write the string to a .vtk file
+    /// std::fs::write("foo.vtk", vtk_source)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "vtk-io")]
+    pub fn parse_as_vtk(&self) -> String
+    {
+        self.parse_as_vtk_with_scalars(&[], &[])
+    }
+
+    ///
+    /// Writes the mesh to the given writer following the ASCII legacy .vtk file format (polydata).
+    /// The vertices and faces are written one at a time, so the file content is never held in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data directly to a .vtk file
+    /// let mut writer = std::io::BufWriter::new(std::fs::File::create("foo.vtk")?);
+    /// mesh.write_vtk(&mut writer)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the writer.
+    ///
+    #[cfg(feature = "vtk-io")]
+    pub fn write_vtk<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()>
+    {
+        self.write_vtk_with_scalars(writer, &[], &[])
+    }
+
+    ///
+    /// Parses the mesh into a text string that follows the ASCII legacy .vtk file format (polydata) like [parse_as_vtk](#method.parse_as_vtk)
+    /// and in addition writes the given scalar fields on the vertices and faces, for example to inspect them in ParaView.
+    /// Each field is given by a name and a function which computes the value of a vertex or face.
+    /// Whitespace in the names is replaced by underscores, since it is not allowed in the file format.
+    /// Use [write_vtk_with_scalars](#method.write_vtk_with_scalars) to write directly to a file without holding the entire text in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<u32>::new().cube().build().unwrap();
+    /// // Write the mesh data together with the height of each vertex and the tag and area of each face
+    /// let vtk_source = mesh.parse_as_vtk_with_scalars(
+    ///     &[("height", &|vertex_id| mesh.vertex_position(vertex_id).y)],
+    ///     &[("tag", &|face_id| mesh.face_tag(face_id) as f64), ("area", &|face_id| mesh.face_area(face_id))]);
+    ///
+    /// // Write the string to a .vtk file
+    /// std::fs::write("foo.vtk", vtk_source)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "vtk-io")]
+    pub fn parse_as_vtk_with_scalars(&self, vertex_scalars: &[(&str, &dyn Fn(VertexID) -> f64)], face_scalars: &[(&str, &dyn Fn(FaceID) -> f64)]) -> String
+    {
+        let mut output = Vec::new();
+        self.write_vtk_with_scalars(&mut output, vertex_scalars, face_scalars).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    ///
+    /// Writes the mesh to the given writer following the ASCII legacy .vtk file format (polydata) together with the given scalar fields,
+    /// see [parse_as_vtk_with_scalars](#method.parse_as_vtk_with_scalars).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mesh = tri_mesh::MeshBuilder::<()>::new().cube().build().unwrap();
+    /// // Write the mesh data directly to a .vtk file together with the area of each face
+    /// let mut writer = std::io::BufWriter::new(std::fs::File::create("foo.vtk")?);
+    /// mesh.write_vtk_with_scalars(&mut writer, &[], &[("area", &|face_id| mesh.face_area(face_id))])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error returned by the writer.
+    ///
+    #[cfg(feature = "vtk-io")]
+    pub fn write_vtk_with_scalars<W: std::io::Write>(&self, writer: &mut W, vertex_scalars: &[(&str, &dyn Fn(VertexID) -> f64)],
+                                                      face_scalars: &[(&str, &dyn Fn(FaceID) -> f64)]) -> std::io::Result<()>
+    {
+        writeln!(writer, "# vtk DataFile Version 3.0\nmesh exported by tri-mesh\nASCII\nDATASET POLYDATA")?;
+        writeln!(writer, "POINTS {} double", self.num_vertices())?;
+        for vertex_id in self.vertex_iter() {
+            let position = self.vertex_position(vertex_id);
+            writeln!(writer, "{} {} {}", position.x, position.y, position.z)?;
+        }
+        writeln!(writer, "POLYGONS {} {}", self.num_faces(), 4 * self.num_faces())?;
+        self.write_indexed_faces(writer)?;
+
+        let write_scalars = |writer: &mut W, name: &str, values: &mut dyn Iterator<Item = f64>| -> std::io::Result<()> {
+            writeln!(writer, "SCALARS {} double 1\nLOOKUP_TABLE default", name.split_whitespace().collect::<Vec<_>>().join("_"))?;
+            for value in values {
+                writeln!(writer, "{}", value)?;
+            }
+            Ok(())
+        };
+        if !vertex_scalars.is_empty() {
+            writeln!(writer, "POINT_DATA {}", self.num_vertices())?;
+            for (name, scalar_fn) in vertex_scalars {
+                write_scalars(writer, name, &mut self.vertex_iter().map(scalar_fn))?;
+            }
+        }
+        if !face_scalars.is_empty() {
+            writeln!(writer, "CELL_DATA {}", self.num_faces())?;
+            for (name, scalar_fn) in face_scalars {
+                write_scalars(writer, name, &mut self.face_iter().map(scalar_fn))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the indices of each face prefixed by the number of vertices, one face per line, as used by the .off and .vtk formats.
+    #[cfg(any(feature = "off-io", feature = "vtk-io"))]
+    fn write_indexed_faces<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()>
+    {
+        let index_map = self.vertex_index_map();
+        for face_id in self.face_iter() {
+            let vertices = self.face_buffer_vertices(face_id);
+            writeln!(writer, "3 {} {} {}", index_map[&vertices[0]], index_map[&vertices[1]], index_map[&vertices[2]])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "obj-io")]
+fn write_obj_face<W: std::io::Write>(writer: &mut W, vertices: [VertexID; 3], index_map: &HashMap<VertexID, u32>) -> std::io::Result<()>
+{
+    // Indices in .obj files start at one and the position and normal of a vertex have the same index
+    let (i0, i1, i2) = (index_map[&vertices[0]] + 1, index_map[&vertices[1]] + 1, index_map[&vertices[2]] + 1);
+    writeln!(writer, "f {}//{} {}//{} {}//{}", i0, i0, i1, i1, i2, i2)
+}
+
+fn push_vec3(vec: &mut Vec<f64>, vec3: crate::mesh::math::Vec3)
+{
+    for i in 0..3 {
+        vec.push(vec3[i]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MeshBuilder;
+    use crate::mesh::math::*;
+
+    #[test]
+    fn test_parse_as_3d() {
+        let mesh = MeshBuilder::<()>::new().cylinder(3, 16).build().unwrap();
+        let encoded: Vec<u8> = mesh.parse_as_3d().unwrap();
+        let decoded = MeshBuilder::<()>::new().with_3d(&encoded).unwrap().build().unwrap();
+
+        assert_eq!(mesh.num_vertices(), decoded.num_vertices());
+        assert_eq!(mesh.num_faces(), decoded.num_faces());
+    }
+
+    #[cfg(feature = "3d-io")]
+    #[test]
+    fn test_parse_as_3d_with_options() {
+        use crate::mesh::format_3d::*;
+        let mut mesh = MeshBuilder::<u32>::new().with_tags((0..20).collect()).icosahedron().build().unwrap();
+        mesh.add_vertex_attribute("color", |values: &[(Vec3, f64)]| values[0].0);
+        let vertex_ids = mesh.vertex_iter().collect::<Vec<_>>();
+        for vertex_id in vertex_ids.iter().skip(1) {
+            mesh.set_vertex_attribute("color", *vertex_id, mesh.vertex_position(*vertex_id)).unwrap();
+        }
+
+        let options = Options3d { precision: Precision::F64, vertex_attributes: vec!["color".to_string()], ..Default::default() };
+        let decoded = MeshBuilder::<u32>::new().with_tagged_3d(&mesh.parse_as_tagged_3d(&options).unwrap()).unwrap().build().unwrap();
+        decoded.is_valid().unwrap();
+        for (vertex_id, decoded_vertex_id) in mesh.vertex_iter().zip(decoded.vertex_iter()) {
+            assert_eq!(mesh.vertex_position(vertex_id), decoded.vertex_position(decoded_vertex_id));
+            assert_eq!(mesh.vertex_attribute::<Vec3>("color", vertex_id), decoded.vertex_attribute::<Vec3>("color", decoded_vertex_id));
+        }
+        assert_eq!(decoded.vertex_attribute::<Vec3>("color", decoded.vertex_iter().next().unwrap()), None);
+        for (face_id, decoded_face_id) in mesh.face_iter().zip(decoded.face_iter()) {
+            assert_eq!(mesh.face_tag(face_id), decoded.face_tag(decoded_face_id));
+        }
+
+        let options = Options3d { compression: Compression::Quantized { bits: 12 }, ..Default::default() };
+        let compressed = mesh.parse_as_3d_with_options(&options).unwrap();
+        assert!(compressed.len() < mesh.parse_as_3d().unwrap().len());
+        let decoded = MeshBuilder::<u32>::new().with_tagged_3d(&compressed).unwrap().build().unwrap();
+        decoded.is_valid().unwrap();
+        assert!(decoded.face_iter().all(|face_id| decoded.face_tag(face_id) == 0));
+        for (vertex_id, decoded_vertex_id) in mesh.vertex_iter().zip(decoded.vertex_iter()) {
+            assert!((mesh.vertex_position(vertex_id) - decoded.vertex_position(decoded_vertex_id)).magnitude() < 0.001);
+        }
+
+        let options = Options3d { vertex_attributes: vec!["missing".to_string()], ..Default::default() };
+        assert!(mesh.parse_as_3d_with_options(&options).is_err());
+    }
+
+    #[cfg(feature = "3d-io")]
+    #[test]
+    fn test_read_3d_version_1() {
+        let mesh = MeshBuilder::<()>::new().cube().build().unwrap();
+        let version_1 = crate::mesh::format_3d::IOMesh {magic_number: 61, version: 1, indices: mesh.indices_buffer(),
+            positions: mesh.positions_buffer_f32(), normals: mesh.normals_buffer_f32()};
+        let mut bytes = bincode::serialize(&version_1).unwrap();
+        let decoded = MeshBuilder::<()>::new().with_3d(&bytes).unwrap().build().unwrap();
+        assert_eq!(mesh.num_vertices(), decoded.num_vertices());
+        assert_eq!(mesh.num_faces(), decoded.num_faces());
+
+        bytes[1] = 3;
+        assert!(MeshBuilder::<()>::new().with_3d(&bytes).is_err());
+    }
+
+    #[cfg(feature = "stl-io")]
+    #[test]
+    fn test_parse_as_stl_binary() {
+        let mesh = MeshBuilder::<()>::new().cylinder(3, 16).build().unwrap();
+        let bytes = mesh.parse_as_stl_binary();
+        assert_eq!(bytes.len(), 84 + 50 * mesh.num_faces());
+
+        let decoded = MeshBuilder::<()>::new().with_stl(&bytes).unwrap().build().unwrap();
+        assert_eq!(mesh.num_vertices(), decoded.num_vertices());
+        assert_eq!(mesh.num_faces(), decoded.num_faces());
+        decoded.is_valid().unwrap();
+    }
+
+    #[cfg(feature = "stl-io")]
+    #[test]
+    fn test_parse_as_stl_ascii() {
+        let mesh = MeshBuilder::<()>::new().icosahedron().build().unwrap();
+        let source = mesh.parse_as_stl_ascii();
+
+        let decoded = MeshBuilder::<()>::new().with_stl(source.as_bytes()).unwrap().build().unwrap();
+        assert_eq!(mesh.num_vertices(), decoded.num_vertices());
+        assert_eq!(mesh.num_faces(), decoded.num_faces());
+        decoded.is_valid().unwrap();
+        for decoded_vertex_id in decoded.vertex_iter() {
+            let position = decoded.vertex_position(decoded_vertex_id);
+            assert!(mesh.vertex_iter().any(|vertex_id| (mesh.vertex_position(vertex_id) - position).magnitude() < 0.00001));
+        }
+    }
+
+    #[cfg(feature = "ply-io")]
+    #[test]
+    fn test_parse_as_ply() {
+        let mut mesh = MeshBuilder::<u32>::new().with_tags((0..20).collect()).icosahedron().build().unwrap();
+        mesh.add_vertex_attribute("color", |values: &[(Vec4, f64)]| values[0].0);
+        for vertex_id in mesh.vertex_iter().collect::<Vec<_>>() {
+            mesh.set_vertex_attribute("color", vertex_id, vec4(1.0, 0.0, 0.2, 1.0)).unwrap();
+        }
+        let source = mesh.parse_as_ply_with_face_property("label", |tag| tag as i32);
+
+        let decoded = MeshBuilder::<u32>::new().with_ply_face_property(source.as_bytes(), "label", |label| label as u32).unwrap().build().unwrap();
+        assert_eq!(mesh.num_vertices(), decoded.num_vertices());
+        assert_eq!(mesh.num_faces(), decoded.num_faces());
+        decoded.is_valid().unwrap();
+        for (face_id, decoded_face_id) in mesh.face_iter().zip(decoded.face_iter()) {
+            assert_eq!(mesh.face_tag(face_id), decoded.face_tag(decoded_face_id));
+        }
+        for (vertex_id, decoded_vertex_id) in mesh.vertex_iter().zip(decoded.vertex_iter()) {
+            assert!((mesh.vertex_position(vertex_id) - decoded.vertex_position(decoded_vertex_id)).magnitude() < 0.00001);
+            assert!((mesh.vertex_normal(vertex_id) - decoded.vertex_attribute::<Vec3>("normal", decoded_vertex_id).unwrap()).magnitude() < 0.00001);
+            assert!((vec4(1.0, 0.0, 0.2, 1.0) - decoded.vertex_attribute::<Vec4>("color", decoded_vertex_id).unwrap()).magnitude() < 0.00001);
+        }
+    }
+
+    #[cfg(feature = "gltf-io")]
+    #[test]
+    fn test_parse_as_gltf() {
+        let mesh = MeshBuilder::<u32>::new().with_tags((0..20).map(|i| i % 3).collect()).icosahedron().build().unwrap();
+        let path = std::env::temp_dir().join("tri_mesh_test_parse_as_gltf.gltf");
+        std::fs::write(&path, mesh.parse_as_gltf()).unwrap();
+
+        let decoded = MeshBuilder::<u32>::new().with_gltf_multiprimitive(&path, |primitive| primitive as u32).unwrap().build().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(mesh.num_vertices(), decoded.num_vertices());
+        assert_eq!(mesh.num_faces(), decoded.num_faces());
+        decoded.is_valid().unwrap();
+        for tag in 0..3 {
+            assert_eq!(mesh.face_iter().filter(|face_id| mesh.face_tag(*face_id) == tag).count(),
+                       decoded.face_iter().filter(|face_id| decoded.face_tag(*face_id) == tag).count());
+        }
+        for (vertex_id, decoded_vertex_id) in mesh.vertex_iter().zip(decoded.vertex_iter()) {
+            assert!((mesh.vertex_position(vertex_id) - decoded.vertex_position(decoded_vertex_id)).magnitude() < 0.00001);
+        }
+    }
+
+    #[cfg(feature = "gltf-io")]
+    #[test]
+    fn test_parse_as_glb() {
+        let mesh = MeshBuilder::<()>::new().cylinder(3, 16).build().unwrap();
+        let bytes = mesh.parse_as_glb();
+        assert_eq!(&bytes[0..4], b"glTF");
+        assert_eq!(bytes.len() % 4, 0);
+        let path = std::env::temp_dir().join("tri_mesh_test_parse_as_glb.glb");
+        std::fs::write(&path, bytes).unwrap();
+
+        let decoded = MeshBuilder::<()>::new().with_gltf(&path).unwrap().build().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(mesh.num_vertices(), decoded.num_vertices());
+        assert_eq!(mesh.num_faces(), decoded.num_faces());
+        decoded.is_valid().unwrap();
+    }
+
+    #[cfg(feature = "off-io")]
+    #[test]
+    fn test_parse_as_off() {
+        let mesh = MeshBuilder::<()>::new().cylinder(3, 16).build().unwrap();
+        let source = mesh.parse_as_off();
+
+        let decoded = MeshBuilder::<()>::new().with_off(&source).unwrap().build().unwrap();
+        assert_eq!(mesh.num_vertices(), decoded.num_vertices());
+        assert_eq!(mesh.num_faces(), decoded.num_faces());
+        decoded.is_valid().unwrap();
+        for (vertex_id, decoded_vertex_id) in mesh.vertex_iter().zip(decoded.vertex_iter()) {
+            assert!((mesh.vertex_position(vertex_id) - decoded.vertex_position(decoded_vertex_id)).magnitude() < 0.00001);
+        }
+    }
+
+    #[cfg(feature = "vtk-io")]
+    #[test]
+    fn test_parse_as_vtk() {
+        let mesh = MeshBuilder::<u32>::new().with_tags((0..20).collect()).icosahedron().build().unwrap();
+        let source = mesh.parse_as_vtk_with_scalars(&[("vertex height", &|vertex_id| mesh.vertex_position(vertex_id).y)],
+                                                    &[("tag", &|face_id| mesh.face_tag(face_id) as f64)]);
+        assert!(source.contains("SCALARS vertex_height double 1"));
+        assert!(source.contains("CELL_DATA 20\nSCALARS tag double 1"));
+
+        let mut output = Vec::new();
+        mesh.write_vtk(&mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), mesh.parse_as_vtk());
+
+        let decoded = MeshBuilder::<()>::new().with_vtk(&source).unwrap().build().unwrap();
+        assert_eq!(mesh.num_vertices(), decoded.num_vertices());
+        assert_eq!(mesh.num_faces(), decoded.num_faces());
+        decoded.is_valid().unwrap();
+        for (vertex_id, decoded_vertex_id) in mesh.vertex_iter().zip(decoded.vertex_iter()) {
+            let position = decoded.vertex_position(decoded_vertex_id);
+            assert!((mesh.vertex_position(vertex_id) - position).magnitude() < 0.00001);
+            assert_eq!(decoded.vertex_attribute::<f64>("vertex_height", decoded_vertex_id), Some(position.y));
+        }
+    }
+
+    #[cfg(feature = "obj-io")]
+    #[test]
+    fn test_write_obj() {
+        let mesh = MeshBuilder::<()>::new().cylinder(3, 16).build().unwrap();
+        let mut output = Vec::new();
+        mesh.write_obj(&mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), mesh.parse_as_obj());
+
+        let decoded = MeshBuilder::<()>::new().with_obj(mesh.parse_as_obj()).build().unwrap();
+        assert_eq!(mesh.num_vertices(), decoded.num_vertices());
+        assert_eq!(mesh.num_faces(), decoded.num_faces());
+        decoded.is_valid().unwrap();
+    }
+
+    #[cfg(feature = "obj-io")]
+    #[test]
+    fn test_write_obj_returns_writer_error() {
+        struct FailingWriter;
+        impl std::io::Write for FailingWriter {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk is full"))
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mesh = MeshBuilder::<()>::new().cube().build().unwrap();
+        assert!(mesh.write_obj(&mut FailingWriter).is_err());
+        assert!(mesh.write_obj_multimaterial(&mut FailingWriter, &mut Vec::new(), |_| 0).is_err());
+    }
+
+    #[cfg(feature = "obj-io")]
+    #[test]
+    fn test_obj_with_materials_round_trip() {
+        use crate::mesh::material::Material;
+        let material = |tag: u32| Material {
+            name: format!("material{}", tag),
+            diffuse_color: Some(vec3(tag as f64 / 3.0, 0.5, 0.0)),
+            specular_exponent: Some(20.0),
+            diffuse_texture: Some(format!("texture{}.png", tag)),
+            ..Default::default()
+        };
+        let mesh = MeshBuilder::<u32>::new().with_tags((0..12).map(|i| i % 3).collect()).cube().build().unwrap();
+        let (obj_source, mtl_source) = mesh.parse_as_obj_with_materials("cube.mtl", material);
+        assert!(obj_source.starts_with("mtllib cube.mtl\n"));
+        assert_eq!(obj_source.matches("usemtl").count(), 3);
+        assert_eq!(mtl_source.matches("newmtl").count(), 3);
+
+        let decoded = MeshBuilder::<u32>::new()
+            .with_obj_and_mtl(obj_source, &mtl_source, |m| {
+                let m = m.unwrap();
+                assert_eq!(m.diffuse_texture.as_deref(), Some(format!("texture{}.png", m.name[8..].parse::<u32>().unwrap()).as_str()));
+                m.name[8..].parse().unwrap()
+            }).unwrap()
+            .build().unwrap();
+        assert_eq!(mesh.num_faces(), decoded.num_faces());
+        for tag in 0..3 {
+            assert_eq!(mesh.face_iter().filter(|f| mesh.face_tag(*f) == tag).count(),
+                       decoded.face_iter().filter(|f| decoded.face_tag(*f) == tag).count());
+        }
+
+        let (obj_source, mtl_source) = mesh.parse_as_obj_multimaterial(|tag| tag as usize);
+        assert_eq!(obj_source.matches("usemtl").count(), 3);
+        assert!(mtl_source.starts_with("newmtl mat0\nKd 1 0 0\n"));
+    }
+
+    #[test]
+    fn test_indexed_export() {
+        let mesh = MeshBuilder::<()>::new().cylinder(3, 16).build().unwrap();
+        let indices = mesh.indices_buffer();
+        let positions = mesh.positions_buffer();
+        let normals = mesh.normals_buffer();
+
+        assert_eq!(indices.len(), mesh.num_faces() * 3);
+        assert_eq!(positions.len(), mesh.num_vertices() * 3);
+        assert_eq!(normals.len(), mesh.num_vertices() * 3);
+
+        for face in 0..positions.len()/9 {
+            let vertices = (indices[3*face] as usize, indices[3*face + 1] as usize, indices[3*face + 2] as usize);
+            let p0 = vec3(positions[3*vertices.0], positions[3*vertices.0+1], positions[3*vertices.0+2]);
+            let p1 = vec3(positions[3*vertices.1], positions[3*vertices.1+1], positions[3*vertices.1+2]);
+            let p2 = vec3(positions[3*vertices.2], positions[3*vertices.2+1], positions[3*vertices.2+2]);
+            let center = (p0 + p1 + p2) / 3.0;
+            let face_id = mesh.face_iter().find(|face_id| (mesh.face_center(*face_id) - center).magnitude() < 0.00001);
+            assert!(face_id.is_some());
+
+            let n0 = vec3(normals[3*vertices.0], normals[3*vertices.0+1], normals[3*vertices.0+2]);
+            let n1 = vec3(normals[3*vertices.1], normals[3*vertices.1+1], normals[3*vertices.1+2]);
+            let n2 = vec3(normals[3*vertices.2], normals[3*vertices.2+1], normals[3*vertices.2+2]);
+
+            let (v0, v1, v2) = mesh.face_vertices(face_id.unwrap());
+
+            assert!(n0 == mesh.vertex_normal(v0) || n1 == mesh.vertex_normal(v0) || n2 == mesh.vertex_normal(v0));
+            assert!(n0 == mesh.vertex_normal(v1) || n1 == mesh.vertex_normal(v1) || n2 == mesh.vertex_normal(v1));
+            assert!(n0 == mesh.vertex_normal(v2) || n1 == mesh.vertex_normal(v2) || n2 == mesh.vertex_normal(v2));
+        }
+    }
+
+    #[test]
+    fn test_non_indexed_export() {
+        let mesh = MeshBuilder::<()>::new().cylinder(3, 16).build().unwrap();
+        let positions = mesh.non_indexed_positions_buffer();
+        let normals = mesh.non_indexed_normals_buffer();
+
+        assert_eq!(positions.len(), mesh.num_faces() * 3 * 3);
+        assert_eq!(normals.len(), mesh.num_faces() * 3 * 3);
+
+        for face in 0..positions.len()/9 {
+            let vertices = (9*face, 9*face+3, 9*face+6);
+            let p0 = vec3(positions[vertices.0], positions[vertices.0+1], positions[vertices.0+2]);
+            let p1 = vec3(positions[vertices.1], positions[vertices.1+1], positions[vertices.1+2]);
+            let p2 = vec3(positions[vertices.2], positions[vertices.2+1], positions[vertices.2+2]);
+            let center = (p0 + p1 + p2) / 3.0;
 
             let face_id = mesh.face_iter().find(|face_id| (mesh.face_center(*face_id) - center).magnitude() < 0.00001);
             assert!(face_id.is_some());
@@ -458,4 +1562,51 @@ mod tests {
             assert!(n0 == mesh.vertex_normal(v2) || n1 == mesh.vertex_normal(v2) || n2 == mesh.vertex_normal(v2));
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_non_indexed_corner_attribute_export() {
+        let mut mesh = MeshBuilder::<()>::new().cylinder(3, 16).build().unwrap();
+        mesh.add_corner_attribute("x", |values: &[(f64, f64)]| values[0].0);
+        for halfedge_id in mesh.halfedge_iter().collect::<Vec<_>>() {
+            let walker = mesh.walker_from_halfedge(halfedge_id);
+            if walker.face_id().is_some() {
+                mesh.set_corner_attribute("x", halfedge_id, mesh.vertex_position(walker.vertex_id().unwrap()).x).unwrap();
+            }
+        }
+        let positions = mesh.non_indexed_positions_buffer();
+        let values = mesh.non_indexed_corner_attribute_buffer::<f64>("x");
+
+        assert_eq!(values.len(), mesh.num_faces() * 3);
+        for i in 0..values.len() {
+            assert_eq!(values[i], Some(positions[3*i]));
+        }
+        assert!(mesh.non_indexed_corner_attribute_buffer::<u32>("x").iter().all(|value| value.is_none()));
+    }
+
+    #[test]
+    fn test_seam_split_export() {
+        let mut mesh = MeshBuilder::<()>::new().square().build().unwrap();
+        mesh.add_corner_attribute("face", |values: &[(u32, f64)]| values[0].0);
+
+        let (indices, positions, values) = mesh.seam_split_buffers::<u32>("face");
+        assert_eq!(indices.len(), 6);
+        assert_eq!(positions.len(), 4 * 3);
+        assert_eq!(values.len(), 4);
+
+        for (i, face_id) in mesh.face_iter().collect::<Vec<_>>().into_iter().enumerate() {
+            for halfedge_id in mesh.face_halfedge_iter(face_id).collect::<Vec<_>>() {
+                mesh.set_corner_attribute("face", halfedge_id, i as u32).unwrap();
+            }
+        }
+
+        let (indices, positions, values) = mesh.seam_split_buffers::<u32>("face");
+        assert_eq!(indices.len(), 6);
+        assert_eq!(positions.len(), 6 * 3);
+        assert_eq!(values.len(), 6);
+        for face in 0..2 {
+            for corner in 0..3 {
+                assert_eq!(values[indices[3*face + corner] as usize], Some(face as u32));
+            }
+        }
+    }
+}
@@ -0,0 +1,357 @@
+//!
+//! Module containing the options for the custom .3d file format, see [parse_as_3d_with_options](crate::mesh::Mesh::parse_as_3d_with_options)
+//! and [with_3d](crate::mesh_builder::MeshBuilder::with_3d).
+//!
+//! A .3d file is serialized using [bincode](https://docs.rs/bincode/) and starts with the magic number 61 followed by the version number.
+//! Version 1 stores the indices, positions and normals. Version 2 stores the indices, the positions in 32 or 64 bit precision
+//! or quantized, the serialized face tags and vertex attributes.
+//!
+
+use crate::mesh::Mesh;
+use crate::mesh::Error;
+use crate::mesh::math::*;
+use crate::mesh_builder;
+use std::convert::TryFrom;
+
+const MAGIC_NUMBER: u8 = 61;
+const VERSION: u8 = 2;
+
+///
+/// The precision of the positions in a .3d file.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Precision {
+    /// The positions are stored as 32 bit floats.
+    #[default]
+    F32,
+    /// The positions are stored as 64 bit floats.
+    F64
+}
+
+///
+/// The compression of the positions and indices in a .3d file.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Compression {
+    /// The positions and indices are stored as they are.
+    #[default]
+    None,
+    /// Each coordinate of the positions is quantized to the given number of bits (between 1 and 32) relative to the bounding box of the mesh.
+    /// The quantized positions and the indices are delta coded and stored with a variable number of bytes,
+    /// so small differences between consecutive values take up less space.
+    /// The [precision](crate::mesh::format_3d::Precision) is ignored.
+    Quantized {
+        /// The number of bits per coordinate.
+        bits: u8
+    }
+}
+
+///
+/// Options for writing a .3d file, see [parse_as_3d_with_options](crate::mesh::Mesh::parse_as_3d_with_options).
+///
+/// # Examples
+///
+/// ```
+/// # use tri_mesh::prelude::*;
+/// let options = Options3d {
+///     compression: Compression::Quantized { bits: 16 },
+///     vertex_attributes: vec!["temperature".to_string()],
+///     ..Default::default()
+/// };
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options3d {
+    /// The precision of the positions.
+    pub precision: Precision,
+    /// The compression of the positions and indices.
+    pub compression: Compression,
+    /// The names of the [vertex attributes](crate::mesh::Mesh#vertex-attributes) to store.
+    /// Only attributes of type `f64`, [Vec2], [Vec3] and [Vec4] are supported.
+    pub vertex_attributes: Vec<String>
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub(crate) struct IOMesh {
+    pub magic_number: u8, // Always 61
+    pub version: u8,
+    pub indices: Vec<u32>,
+    pub positions: Vec<f32>,
+    pub normals: Vec<f32>
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+struct IOMeshV2 {
+    magic_number: u8, // Always 61
+    version: u8, // Always 2
+    indices: IOIndices,
+    positions: IOPositions,
+    /// The bincode serialized face tags, if any.
+    tags: Option<Vec<u8>>,
+    vertex_attributes: Vec<IOAttribute>
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+enum IOIndices {
+    Plain(Vec<u32>),
+    /// The number of indices followed by the zigzag and variable length encoded differences between consecutive indices.
+    Delta(u32, Vec<u8>)
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+enum IOPositions {
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    Quantized {
+        min: [f64; 3],
+        step: [f64; 3],
+        num_vertices: u32,
+        /// The zigzag and variable length encoded differences between the quantized coordinates of consecutive vertices.
+        deltas: Vec<u8>
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+struct IOAttribute {
+    name: String,
+    components: u8,
+    /// Whether each vertex has a value.
+    defined: Vec<bool>,
+    /// The components of the values of the vertices which have a value.
+    values: Vec<f64>
+}
+
+/// A vertex attribute read from a .3d file.
+pub(crate) struct DecodedAttribute {
+    pub name: String,
+    pub components: u8,
+    pub values: Vec<Option<Vec<f64>>>
+}
+
+/// The content of a .3d file of any version.
+pub(crate) struct Decoded3d {
+    pub positions: Vec<f64>,
+    pub indices: Vec<u32>,
+    pub tags: Option<Vec<u8>>,
+    pub vertex_attributes: Vec<DecodedAttribute>
+}
+
+/// Encodes the mesh in version 2 of the .3d file format with the given bincode serialized tags.
+pub(crate) fn encode<T: Clone>(mesh: &Mesh<T>, options: &Options3d, tags: Option<Vec<u8>>) -> Result<Vec<u8>, Error>
+{
+    let vertex_attributes = options.vertex_attributes.iter()
+        .map(|name| encode_attribute(mesh, name))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let indices = mesh.indices_buffer();
+    let (indices, positions) = match options.compression {
+        Compression::None => (IOIndices::Plain(indices), match options.precision {
+            Precision::F32 => IOPositions::F32(mesh.positions_buffer_f32()),
+            Precision::F64 => IOPositions::F64(mesh.positions_buffer())
+        }),
+        Compression::Quantized { bits } => {
+            let mut index_deltas = Vec::new();
+            let mut previous = 0;
+            for index in indices.iter() {
+                write_varint(&mut index_deltas, *index as i64 - previous);
+                previous = *index as i64;
+            }
+            (IOIndices::Delta(indices.len() as u32, index_deltas), quantize(&mesh.positions_buffer(), bits.max(1).min(32)))
+        }
+    };
+
+    let io_mesh = IOMeshV2 { magic_number: MAGIC_NUMBER, version: VERSION, indices, positions, tags, vertex_attributes };
+    Ok(bincode::serialize(&io_mesh)?)
+}
+
+fn encode_attribute<T: Clone>(mesh: &Mesh<T>, name: &str) -> Result<IOAttribute, Error>
+{
+    let values = mesh.vertex_iter().map(|vertex_id| {
+        mesh.vertex_attribute::<f64>(name, vertex_id).map(|v| vec![v])
+            .or_else(|| mesh.vertex_attribute::<Vec2>(name, vertex_id).map(|v| vec![v.x, v.y]))
+            .or_else(|| mesh.vertex_attribute::<Vec3>(name, vertex_id).map(|v| vec![v.x, v.y, v.z]))
+            .or_else(|| mesh.vertex_attribute::<Vec4>(name, vertex_id).map(|v| vec![v.x, v.y, v.z, v.w]))
+    }).collect::<Vec<_>>();
+
+    let components = values.iter().flatten().next().map(|value| value.len())
+        .ok_or_else(|| Error::AttributeNotFound {
+            message: format!("The vertex attribute '{}' does not exist or has no values of type f64, Vec2, Vec3 or Vec4", name)
+        })?;
+    Ok(IOAttribute {
+        name: name.to_string(),
+        components: components as u8,
+        defined: values.iter().map(|value| value.is_some()).collect(),
+        values: values.into_iter().flatten().flatten().collect()
+    })
+}
+
+fn quantize(positions: &[f64], bits: u8) -> IOPositions
+{
+    let levels = ((1u64 << bits) - 1) as f64;
+    let mut min = [f64::MAX; 3];
+    let mut max = [f64::MIN; 3];
+    for position in positions.chunks(3) {
+        for i in 0..3 {
+            min[i] = min[i].min(position[i]);
+            max[i] = max[i].max(position[i]);
+        }
+    }
+    let mut step = [0.0; 3];
+    for i in 0..3 {
+        if max[i] > min[i] {
+            step[i] = (max[i] - min[i]) / levels;
+        }
+    }
+
+    let mut deltas = Vec::new();
+    let mut previous = [0i64; 3];
+    for position in positions.chunks(3) {
+        for i in 0..3 {
+            let value = if step[i] > 0.0 { ((position[i] - min[i]) / step[i]).round() as i64 } else { 0 };
+            write_varint(&mut deltas, value - previous[i]);
+            previous[i] = value;
+        }
+    }
+    IOPositions::Quantized { min, step, num_vertices: (positions.len() / 3) as u32, deltas }
+}
+
+/// Decodes a .3d file of version 1 or 2.
+pub(crate) fn decode(bytes: &[u8]) -> Result<Decoded3d, mesh_builder::Error>
+{
+    let invalid = |message: &str| mesh_builder::Error::InvalidFile { message: message.to_string() };
+    if bytes.first() != Some(&MAGIC_NUMBER) {
+        return Err(invalid("Invalid 3d file!"));
+    }
+
+    let decoded = match bytes.get(1) {
+        Some(1) => {
+            let io_mesh: IOMesh = bincode::deserialize(bytes)?;
+            Decoded3d { positions: io_mesh.positions.iter().map(|x| *x as f64).collect(), indices: io_mesh.indices,
+                tags: None, vertex_attributes: Vec::new() }
+        },
+        Some(2) => {
+            let io_mesh: IOMeshV2 = bincode::deserialize(bytes)?;
+            let indices = match io_mesh.indices {
+                IOIndices::Plain(indices) => indices,
+                IOIndices::Delta(count, deltas) => {
+                    let mut reader = deltas.iter();
+                    let mut previous = 0;
+                    // Each index takes at least one byte, so the count from the file is not trusted beyond the number of bytes
+                    let mut indices = Vec::with_capacity((count as usize).min(deltas.len()));
+                    for _ in 0..count {
+                        previous = read_varint(&mut reader).and_then(|delta| delta.checked_add(previous))
+                            .ok_or_else(|| invalid("Unexpected end of indices in 3d file"))?;
+                        indices.push(u32::try_from(previous).map_err(|_| invalid("Invalid index in 3d file"))?);
+                    }
+                    indices
+                }
+            };
+            let positions = match io_mesh.positions {
+                IOPositions::F32(positions) => positions.iter().map(|x| *x as f64).collect(),
+                IOPositions::F64(positions) => positions,
+                IOPositions::Quantized { min, step, num_vertices, deltas } => {
+                    let mut reader = deltas.iter();
+                    let mut previous = [0i64; 3];
+                    // Each coordinate takes at least one byte, so the number of vertices from the file is not trusted beyond the number of bytes
+                    let mut positions = Vec::with_capacity((3 * num_vertices as usize).min(deltas.len()));
+                    for _ in 0..num_vertices {
+                        for i in 0..3 {
+                            previous[i] = read_varint(&mut reader).and_then(|delta| delta.checked_add(previous[i]))
+                                .ok_or_else(|| invalid("Unexpected end of positions in 3d file"))?;
+                            positions.push(min[i] + previous[i] as f64 * step[i]);
+                        }
+                    }
+                    positions
+                }
+            };
+
+            let num_vertices = positions.len() / 3;
+            let mut vertex_attributes = Vec::new();
+            for attribute in io_mesh.vertex_attributes {
+                let components = attribute.components as usize;
+                if attribute.defined.len() != num_vertices || components == 0 || components > 4
+                    || attribute.values.len() != components * attribute.defined.iter().filter(|defined| **defined).count() {
+                    return Err(invalid(&format!("Invalid vertex attribute '{}' in 3d file", attribute.name)));
+                }
+                let mut values = attribute.values.chunks(components);
+                let values = attribute.defined.iter().map(|defined| if *defined { values.next().map(|v| v.to_vec()) } else { None }).collect();
+                vertex_attributes.push(DecodedAttribute { name: attribute.name, components: attribute.components, values });
+            }
+            Decoded3d { positions, indices, tags: io_mesh.tags, vertex_attributes }
+        },
+        _ => return Err(invalid("Unsupported version of 3d file"))
+    };
+
+    if decoded.positions.len() % 3 != 0 || decoded.indices.len() % 3 != 0 {
+        return Err(invalid("Invalid number of positions or indices in 3d file"));
+    }
+    if let Some(index) = decoded.indices.iter().find(|index| **index as usize >= decoded.positions.len() / 3) {
+        return Err(invalid(&format!("The vertex index {} is out of range", index)));
+    }
+    Ok(decoded)
+}
+
+/// Writes the value zigzag encoded with seven bits per byte, where the highest bit of a byte signals that more bytes follow.
+fn write_varint(bytes: &mut Vec<u8>, value: i64)
+{
+    let mut value = ((value << 1) ^ (value >> 63)) as u64;
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn read_varint<'a>(bytes: &mut impl Iterator<Item = &'a u8>) -> Option<i64>
+{
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes.next()?;
+        if shift >= 64 {
+            return None;
+        }
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(((value >> 1) as i64) ^ -((value & 1) as i64));
+        }
+        shift += 7;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varint() {
+        let values = [0, 1, -1, 63, -64, 64, 1000, -100000, i64::MAX, i64::MIN];
+        let mut bytes = Vec::new();
+        for value in values.iter() {
+            write_varint(&mut bytes, *value);
+        }
+        assert_eq!(bytes[..3], [0, 2, 1]);
+
+        let mut reader = bytes.iter();
+        for value in values.iter() {
+            assert_eq!(read_varint(&mut reader), Some(*value));
+        }
+        assert_eq!(read_varint(&mut reader), None);
+    }
+
+    #[test]
+    fn test_decode_invalid_counts() {
+        let mesh = crate::MeshBuilder::<()>::new().cube().build().unwrap();
+        let bytes = encode(&mesh, &Options3d { compression: Compression::Quantized { bits: 16 }, ..Default::default() }, None).unwrap();
+        assert!(decode(&bytes).is_ok());
+
+        let modified = |modify: &dyn Fn(&mut IOMeshV2)| {
+            let mut io_mesh: IOMeshV2 = bincode::deserialize(&bytes).unwrap();
+            modify(&mut io_mesh);
+            decode(&bincode::serialize(&io_mesh).unwrap())
+        };
+        assert!(modified(&|io_mesh| if let IOIndices::Delta(count, _) = &mut io_mesh.indices { *count = u32::MAX }).is_err());
+        assert!(modified(&|io_mesh| if let IOPositions::Quantized { num_vertices, .. } = &mut io_mesh.positions { *num_vertices = u32::MAX }).is_err());
+        assert!(modified(&|io_mesh| io_mesh.indices = IOIndices::Delta(2, vec![0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x02])).is_err());
+    }
+}
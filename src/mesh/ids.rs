@@ -1,4 +1,9 @@
 //! Defines unique id's for a vertex, half-edge and face.
+//!
+//! With the `generational-ids` feature (enabled by default), each id also carries the generation of the slot it refers to.
+//! The generation is increased every time a primitive is removed, so an id which is used after the primitive it refers to is removed
+//! does not silently refer to a new primitive which has reused the slot. Instead the lookup fails.
+//! Disable the feature to get ids which only contain an index.
 
 use std::fmt;
 use std::hash::Hash;
@@ -11,22 +16,46 @@ pub trait Deref {
 
 pub(crate) trait ID: Clone + Eq + Copy + Ord + Hash + Debug + Deref {
     fn new(val: u32) -> Self;
+    fn new_with_generation(val: u32, generation: u32) -> Self;
+    #[cfg(feature = "generational-ids")]
+    fn generation(&self) -> u32;
 }
 
 ///
 /// An unique ID for a vertex
 ///
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexID
 {
-    val: u32
+    val: u32,
+    #[cfg(feature = "generational-ids")]
+    generation: u32
 }
 
 impl ID for VertexID {
     fn new(val: u32) -> VertexID
+    {
+        VertexID::new_with_generation(val, 0)
+    }
+
+    #[cfg(feature = "generational-ids")]
+    fn new_with_generation(val: u32, generation: u32) -> VertexID
+    {
+        VertexID { val, generation }
+    }
+
+    #[cfg(not(feature = "generational-ids"))]
+    fn new_with_generation(val: u32, _generation: u32) -> VertexID
     {
         VertexID { val }
     }
+
+    #[cfg(feature = "generational-ids")]
+    fn generation(&self) -> u32
+    {
+        self.generation
+    }
 }
 
 impl Deref for VertexID {
@@ -46,15 +75,36 @@ impl fmt::Display for VertexID {
 /// An unique ID for a halfedge
 ///
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HalfEdgeID
 {
-    val: u32
+    val: u32,
+    #[cfg(feature = "generational-ids")]
+    generation: u32
 }
 
 impl ID for HalfEdgeID {
     fn new(val: u32) -> HalfEdgeID
     {
-        HalfEdgeID {val}
+        HalfEdgeID::new_with_generation(val, 0)
+    }
+
+    #[cfg(feature = "generational-ids")]
+    fn new_with_generation(val: u32, generation: u32) -> HalfEdgeID
+    {
+        HalfEdgeID { val, generation }
+    }
+
+    #[cfg(not(feature = "generational-ids"))]
+    fn new_with_generation(val: u32, _generation: u32) -> HalfEdgeID
+    {
+        HalfEdgeID { val }
+    }
+
+    #[cfg(feature = "generational-ids")]
+    fn generation(&self) -> u32
+    {
+        self.generation
     }
 }
 
@@ -75,15 +125,36 @@ impl fmt::Display for HalfEdgeID {
 /// An unique ID for a face
 ///
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FaceID
 {
-    val: u32
+    val: u32,
+    #[cfg(feature = "generational-ids")]
+    generation: u32
 }
 
 impl ID for FaceID {
     fn new(val: u32) -> FaceID
     {
-        FaceID {val}
+        FaceID::new_with_generation(val, 0)
+    }
+
+    #[cfg(feature = "generational-ids")]
+    fn new_with_generation(val: u32, generation: u32) -> FaceID
+    {
+        FaceID { val, generation }
+    }
+
+    #[cfg(not(feature = "generational-ids"))]
+    fn new_with_generation(val: u32, _generation: u32) -> FaceID
+    {
+        FaceID { val }
+    }
+
+    #[cfg(feature = "generational-ids")]
+    fn generation(&self) -> u32
+    {
+        self.generation
     }
 }
 
@@ -114,4 +185,15 @@ mod tests {
         assert!(v0 != v1);
         assert!(v1 == v1_);
     }
+
+    #[cfg(feature = "generational-ids")]
+    #[test]
+    fn test_equality_with_generation()
+    {
+        let v1 = VertexID::new(1);
+        let v1_ = VertexID::new_with_generation(1, 1);
+
+        assert!(v1 != v1_);
+        assert_eq!(v1.deref(), v1_.deref());
+    }
 }
\ No newline at end of file
@@ -368,7 +368,7 @@ mod tests {
     }
 }
 
-mod utility {
+pub(crate) mod utility {
     use crate::prelude::*;
 
     pub const MARGIN: f64 = 0.0000001;
@@ -3,16 +3,73 @@
 use crate::mesh::Mesh;
 use crate::mesh::ids::*;
 use crate::mesh::traversal::Walker;
-use crate::mesh::connectivity_info::ConnectivityInfo;
+use crate::mesh::connectivity_info::{ConnectivityInfo, IDIter, Vertex, HalfEdge, Face};
 
 /// An iterator over the vertices. See [here](../struct.Mesh.html#method.vertex_iter) for more information.
-pub type VertexIter = Box<dyn Iterator<Item=VertexID>>;
+/// It borrows the mesh, see [here](../struct.Mesh.html#iterators) for how to change the mesh while iterating.
+#[derive(Clone, Debug)]
+pub struct VertexIter<'a>
+{
+    iter: IDIter<'a, VertexID, Vertex>
+}
+
+impl<'a> Iterator for VertexIter<'a> {
+    type Item = VertexID;
+
+    fn next(&mut self) -> Option<VertexID>
+    {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.iter.size_hint()
+    }
+}
 
 /// An iterator over the half-edges. See [here](../struct.Mesh.html#method.halfedge_iter) for more information.
-pub type HalfEdgeIter = Box<dyn Iterator<Item = HalfEdgeID>>;
+/// It borrows the mesh, see [here](../struct.Mesh.html#iterators) for how to change the mesh while iterating.
+#[derive(Clone, Debug)]
+pub struct HalfEdgeIter<'a>
+{
+    iter: IDIter<'a, HalfEdgeID, HalfEdge>
+}
+
+impl<'a> Iterator for HalfEdgeIter<'a> {
+    type Item = HalfEdgeID;
+
+    fn next(&mut self) -> Option<HalfEdgeID>
+    {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.iter.size_hint()
+    }
+}
 
 /// An iterator over the faces. See [here](../struct.Mesh.html#method.face_iter) for more information.
-pub type FaceIter = Box<dyn Iterator<Item = FaceID>>;
+/// It borrows the mesh, see [here](../struct.Mesh.html#iterators) for how to change the mesh while iterating.
+#[derive(Clone, Debug)]
+pub struct FaceIter<'a, T>
+{
+    iter: IDIter<'a, FaceID, Face<T>>
+}
+
+impl<'a, T> Iterator for FaceIter<'a, T> {
+    type Item = FaceID;
+
+    fn next(&mut self) -> Option<FaceID>
+    {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        self.iter.size_hint()
+    }
+}
 
 /// An iterator over the half-edges starting in a given vertex. See [here](../struct.Mesh.html#method.vertex_halfedge_iter) for more information.
 pub struct VertexHalfedgeIter<'a, T>
@@ -86,13 +143,13 @@ impl<'a, T> Iterator for FaceHalfedgeIter<'a, T> {
 pub struct EdgeIter<'a, T>
 {
     walker: Walker<'a, T>,
-    iter: HalfEdgeIter
+    iter: HalfEdgeIter<'a>
 }
 
 impl<'a, T> EdgeIter<'a, T> {
     pub(crate) fn new(connectivity_info: &'a ConnectivityInfo<T>) -> EdgeIter<'a, T>
     {
-        EdgeIter { walker: Walker::new(connectivity_info), iter: connectivity_info.halfedge_iterator() }
+        EdgeIter { walker: Walker::new(connectivity_info), iter: HalfEdgeIter { iter: connectivity_info.halfedge_iterator() } }
     }
 }
 
@@ -101,22 +158,25 @@ impl<'a, T> Iterator for EdgeIter<'a, T> {
 
     fn next(&mut self) -> Option<HalfEdgeID>
     {
-        if let Some(next_id) = self.iter.next() {
-            if self.walker.as_halfedge_walker(next_id).twin_id().unwrap() < next_id
-            {
-                self.next()
-            }
-            else {
-                Some(next_id)
-            }
-        }
-        else {
-            None
-        }
+        let walker = &mut self.walker;
+        self.iter.find(|halfedge_id| walker.as_halfedge_walker(*halfedge_id).twin_id().unwrap() > *halfedge_id)
     }
 }
 
 /// # Iterators
+///
+/// **Note:** The vertex, half-edge and face iterators borrow the mesh and do not allocate (in version 0.5 and earlier they returned a copy of the ids).
+/// The mesh can therefore not be changed while iterating, so collect the ids first if the mesh is changed in the loop:
+///
+/// ```
+/// # use tri_mesh::prelude::*;
+/// let mut mesh = MeshBuilder::<()>::new().cube().build().unwrap();
+/// for vertex_id in mesh.vertex_iter().collect::<Vec<_>>() {
+///     let p = mesh.vertex_position(vertex_id);
+///     mesh.move_vertex_to(vertex_id, 2.0 * p);
+/// }
+/// ```
+///
 impl<T: Clone> Mesh<T>
 {
     ///
@@ -133,9 +193,9 @@ impl<T: Clone> Mesh<T>
     /// }
     /// ```
     ///
-    pub fn vertex_iter(&self) -> VertexIter
+    pub fn vertex_iter(&self) -> VertexIter<'_>
     {
-        self.connectivity_info.vertex_iterator()
+        VertexIter { iter: self.connectivity_info.vertex_iterator() }
     }
 
     ///
@@ -158,9 +218,9 @@ impl<T: Clone> Mesh<T>
     /// # assert_eq!(i, 36);
     /// ```
     ///
-    pub fn halfedge_iter(&self) -> HalfEdgeIter
+    pub fn halfedge_iter(&self) -> HalfEdgeIter<'_>
     {
-        self.connectivity_info.halfedge_iterator()
+        HalfEdgeIter { iter: self.connectivity_info.halfedge_iterator() }
     }
 
     ///
@@ -202,9 +262,9 @@ impl<T: Clone> Mesh<T>
     /// }
     /// ```
     ///
-    pub fn face_iter(&self) -> FaceIter
+    pub fn face_iter(&self) -> FaceIter<'_, T>
     {
-        self.connectivity_info.face_iterator()
+        FaceIter { iter: self.connectivity_info.face_iterator() }
     }
 
     ///
@@ -255,6 +315,45 @@ impl<T: Clone> Mesh<T>
     {
         FaceHalfedgeIter::new(face_id, &self.connectivity_info)
     }
+
+    ///
+    /// The number of vertex slots, including the slots of removed vertices. Visiting the slots `0..num_vertex_slots()`
+    /// with [vertex_in_slot](Self::vertex_in_slot) visits all vertices without borrowing the mesh between the visits.
+    ///
+    pub(crate) fn num_vertex_slots(&self) -> u32
+    {
+        self.connectivity_info.num_vertex_slots()
+    }
+
+    /// The number of half-edge slots. See [num_vertex_slots](Self::num_vertex_slots).
+    pub(crate) fn num_halfedge_slots(&self) -> u32
+    {
+        self.connectivity_info.num_halfedge_slots()
+    }
+
+    /// The number of face slots. See [num_vertex_slots](Self::num_vertex_slots).
+    pub(crate) fn num_face_slots(&self) -> u32
+    {
+        self.connectivity_info.num_face_slots()
+    }
+
+    /// Returns the id of the vertex in the slot with the given index or `None` if the vertex is removed.
+    pub(crate) fn vertex_in_slot(&self, index: u32) -> Option<VertexID>
+    {
+        self.connectivity_info.vertex_in_slot(index)
+    }
+
+    /// Returns the id of the half-edge in the slot with the given index or `None` if the half-edge is removed.
+    pub(crate) fn halfedge_in_slot(&self, index: u32) -> Option<HalfEdgeID>
+    {
+        self.connectivity_info.halfedge_in_slot(index)
+    }
+
+    /// Returns the id of the face in the slot with the given index or `None` if the face is removed.
+    pub(crate) fn face_in_slot(&self, index: u32) -> Option<FaceID>
+    {
+        self.connectivity_info.face_in_slot(index)
+    }
 }
 
 #[cfg(test)]
@@ -345,6 +444,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_iterators_skip_removed_primitives() {
+        let mut mesh = MeshBuilder::<()>::new().subdivided_triangle().build().unwrap();
+        let halfedge_id = mesh.halfedge_iter().find(|halfedge_id| !mesh.is_edge_on_boundary(*halfedge_id)).unwrap();
+        mesh.collapse_edge(halfedge_id);
+
+        assert_eq!(mesh.vertex_iter().count(), 3);
+        assert_eq!(mesh.halfedge_iter().count(), 6);
+        assert_eq!(mesh.edge_iter().count(), 3);
+        assert_eq!(mesh.face_iter().count(), 1);
+        assert!(mesh.halfedge_iter().all(|halfedge_id| mesh.walker_from_halfedge(halfedge_id).vertex_id().is_some()));
+    }
+
     #[test]
     fn test_vertex_halfedge_iterator() {
         let mesh = MeshBuilder::<()>::new().subdivided_triangle().build().unwrap();
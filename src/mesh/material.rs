@@ -0,0 +1,223 @@
+//!
+//! Module containing [Material](crate::mesh::material::Material) which describes the appearance of the faces in an .obj file
+//! and which is read from and written to .mtl files.
+//!
+
+use crate::mesh::math::{Vec3, vec3};
+
+///
+/// A material as defined in an .mtl file, see [write_obj_with_materials](crate::mesh::Mesh::write_obj_with_materials)
+/// and [with_obj_and_mtl](crate::mesh_builder::MeshBuilder::with_obj_and_mtl).
+/// Only the name is required, all other properties are left out of the .mtl file if they are not specified.
+///
+/// # Examples
+///
+/// ```
+/// # use tri_mesh::prelude::*;
+/// let material = Material {
+///     name: "brick".to_string(),
+///     diffuse_color: Some(vec3(0.8, 0.3, 0.2)),
+///     diffuse_texture: Some("brick.png".to_string()),
+///     ..Default::default()
+/// };
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Material {
+    /// The name of the material (`newmtl`).
+    pub name: String,
+    /// The ambient color (`Ka`).
+    pub ambient_color: Option<Vec3>,
+    /// The diffuse color (`Kd`).
+    pub diffuse_color: Option<Vec3>,
+    /// The specular color (`Ks`).
+    pub specular_color: Option<Vec3>,
+    /// The emissive color (`Ke`).
+    pub emissive_color: Option<Vec3>,
+    /// The specular exponent (`Ns`).
+    pub specular_exponent: Option<f64>,
+    /// The opacity, where 1 is opaque (`d`).
+    pub dissolve: Option<f64>,
+    /// The index of refraction (`Ni`).
+    pub optical_density: Option<f64>,
+    /// The illumination model (`illum`).
+    pub illumination_model: Option<u32>,
+    /// The file name of the ambient texture (`map_Ka`).
+    pub ambient_texture: Option<String>,
+    /// The file name of the diffuse texture (`map_Kd`).
+    pub diffuse_texture: Option<String>,
+    /// The file name of the specular texture (`map_Ks`).
+    pub specular_texture: Option<String>,
+    /// The file name of the bump map (`map_Bump` or `bump`).
+    pub bump_texture: Option<String>,
+    /// The file name of the opacity texture (`map_d`).
+    pub dissolve_texture: Option<String>,
+}
+
+impl Material {
+    /// Writes the material in the .mtl file format.
+    pub(crate) fn write_mtl<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()>
+    {
+        writeln!(writer, "newmtl {}", self.name)?;
+        let colors = [("Ka", &self.ambient_color), ("Kd", &self.diffuse_color), ("Ks", &self.specular_color), ("Ke", &self.emissive_color)];
+        for (keyword, color) in colors.iter() {
+            if let Some(color) = color {
+                writeln!(writer, "{} {} {} {}", keyword, color.x, color.y, color.z)?;
+            }
+        }
+        let values = [("Ns", &self.specular_exponent), ("d", &self.dissolve), ("Ni", &self.optical_density)];
+        for (keyword, value) in values.iter() {
+            if let Some(value) = value {
+                writeln!(writer, "{} {}", keyword, value)?;
+            }
+        }
+        if let Some(illumination_model) = self.illumination_model {
+            writeln!(writer, "illum {}", illumination_model)?;
+        }
+        let textures = [("map_Ka", &self.ambient_texture), ("map_Kd", &self.diffuse_texture), ("map_Ks", &self.specular_texture),
+            ("map_Bump", &self.bump_texture), ("map_d", &self.dissolve_texture)];
+        for (keyword, texture) in textures.iter() {
+            if let Some(texture) = texture {
+                writeln!(writer, "{} {}", keyword, texture)?;
+            }
+        }
+        writeln!(writer)
+    }
+}
+
+///
+/// Parses the source of an .mtl file into a list of materials. Statements which are not supported are ignored.
+/// Returns an error message if the source is invalid.
+///
+pub(crate) fn parse_mtl(source: &str) -> Result<Vec<Material>, String>
+{
+    let mut materials: Vec<Material> = Vec::new();
+    for (line_number, line) in source.lines().enumerate() {
+        let line = line.split('#').next().unwrap().trim();
+        let (keyword, rest) = match line.find(char::is_whitespace) {
+            Some(i) => (&line[..i], line[i..].trim()),
+            None => (line, "")
+        };
+        if keyword.is_empty() {
+            continue;
+        }
+        if keyword == "newmtl" {
+            materials.push(Material { name: rest.to_string(), ..Default::default() });
+            continue;
+        }
+
+        let error = |message: &str| format!("Invalid mtl file at line {}: {}", line_number + 1, message);
+        let material = materials.last_mut().ok_or_else(|| error("expected 'newmtl' before any other statement"))?;
+        let numbers = || rest.split_whitespace().map(|word| word.parse::<f64>()).collect::<Result<Vec<_>, _>>()
+            .map_err(|_| error(&format!("expected numbers after '{}'", keyword)));
+        let color = || numbers().and_then(|values| match values.as_slice() {
+            [r, g, b] => Ok(vec3(*r, *g, *b)),
+            // A single value means a gray color
+            [v] => Ok(vec3(*v, *v, *v)),
+            _ => Err(error(&format!("expected a color after '{}'", keyword)))
+        });
+        let number = || numbers().and_then(|values| values.first().cloned().ok_or_else(|| error(&format!("expected a number after '{}'", keyword))));
+
+        match keyword {
+            "Ka" => material.ambient_color = Some(color()?),
+            "Kd" => material.diffuse_color = Some(color()?),
+            "Ks" => material.specular_color = Some(color()?),
+            "Ke" => material.emissive_color = Some(color()?),
+            "Ns" => material.specular_exponent = Some(number()?),
+            "d" => material.dissolve = Some(number()?),
+            "Tr" => material.dissolve = Some(1.0 - number()?),
+            "Ni" => material.optical_density = Some(number()?),
+            "illum" => material.illumination_model = Some(rest.parse::<u32>().map_err(|_| error("expected a non-negative integer after 'illum'"))?),
+            "map_Ka" => material.ambient_texture = texture_file_name(rest),
+            "map_Kd" => material.diffuse_texture = texture_file_name(rest),
+            "map_Ks" => material.specular_texture = texture_file_name(rest),
+            "map_Bump" | "map_bump" | "bump" => material.bump_texture = texture_file_name(rest),
+            "map_d" => material.dissolve_texture = texture_file_name(rest),
+            _ => {}
+        }
+    }
+    Ok(materials)
+}
+
+///
+/// Returns the file name of a texture statement, which can have options in front of the file name, for example '-s 2 2 1 texture.png'.
+/// The file name is the rest of the statement after the options, so it can contain spaces.
+///
+fn texture_file_name(statement: &str) -> Option<String>
+{
+    fn split_word(text: &str) -> (&str, &str) {
+        match text.find(char::is_whitespace) {
+            Some(i) => (&text[..i], text[i..].trim_start()),
+            None => (text, "")
+        }
+    }
+
+    let mut rest = statement.trim();
+    while rest.starts_with('-') {
+        let (option, after) = split_word(rest);
+        rest = after;
+        // The minimum and maximum number of arguments of the option
+        let (min, max) = match option {
+            "-o" | "-s" | "-t" => (1, 3),
+            "-mm" => (2, 2),
+            _ => (1, 1)
+        };
+        for i in 0..max {
+            let (argument, after) = split_word(rest);
+            if i >= min && argument.parse::<f64>().is_err() {
+                break;
+            }
+            rest = after;
+        }
+    }
+    if rest.is_empty() { None } else { Some(rest.to_string()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_and_parse_mtl() {
+        let materials = vec![
+            Material { name: "brick".to_string(), diffuse_color: Some(vec3(0.8, 0.3, 0.2)), specular_exponent: Some(10.0),
+                diffuse_texture: Some("brick.png".to_string()), illumination_model: Some(2), ..Default::default() },
+            Material { name: "glass".to_string(), specular_color: Some(vec3(1.0, 1.0, 1.0)), dissolve: Some(0.25), ..Default::default() }
+        ];
+        let mut source = Vec::new();
+        for material in materials.iter() {
+            material.write_mtl(&mut source).unwrap();
+        }
+
+        assert_eq!(parse_mtl(std::str::from_utf8(&source).unwrap()).unwrap(), materials);
+    }
+
+    #[test]
+    fn test_parse_mtl() {
+        let source = "# A comment
+        newmtl stone
+        Ka 0.1
+        Kd 0.5 0.5 0.5
+        Tr 0.25
+        map_Kd -s 2 2 1 stone.png
+        unknown statement
+        ";
+        let materials = parse_mtl(source).unwrap();
+
+        assert_eq!(materials.len(), 1);
+        assert_eq!(materials[0].name, "stone");
+        assert_eq!(materials[0].ambient_color, Some(vec3(0.1, 0.1, 0.1)));
+        assert_eq!(materials[0].diffuse_color, Some(vec3(0.5, 0.5, 0.5)));
+        assert_eq!(materials[0].dissolve, Some(0.75));
+        assert_eq!(materials[0].diffuse_texture, Some("stone.png".to_string()));
+
+        let materials = parse_mtl("newmtl wall\nmap_Kd -clamp on -o 0.5 0.5 my wall.png\nbump -bm 0.2 wall bump.png\nillum 2").unwrap();
+        assert_eq!(materials[0].diffuse_texture, Some("my wall.png".to_string()));
+        assert_eq!(materials[0].bump_texture, Some("wall bump.png".to_string()));
+        assert_eq!(materials[0].illumination_model, Some(2));
+
+        assert!(parse_mtl("Kd 1 0 0").is_err());
+        assert!(parse_mtl("newmtl red\nillum -1").is_err());
+        assert!(parse_mtl("newmtl red\nKd 1 zero 0").is_err());
+    }
+}
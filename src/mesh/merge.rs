@@ -39,6 +39,7 @@ impl<T: Clone> Mesh<T>
         };
 
         let mut face_mapping: HashMap<FaceID, FaceID> = HashMap::new();
+        let mut corner_mapping: HashMap<HalfEdgeID, HalfEdgeID> = HashMap::new();
         for other_face_id in other.face_iter() {
             let vertex_ids = other.face_vertices(other_face_id);
 
@@ -48,22 +49,32 @@ impl<T: Clone> Mesh<T>
             let tag = other.face_tag(other_face_id);
             let new_face_id = self.connectivity_info.create_face(vertex_id0, vertex_id1, vertex_id2, tag);
 
+            for other_halfedge_id in other.face_halfedge_iter(other_face_id) {
+                let other_vertex_id = other.walker_from_halfedge(other_halfedge_id).vertex_id().unwrap();
+                let vertex_id = if other_vertex_id == vertex_ids.0 { vertex_id0 } else if other_vertex_id == vertex_ids.1 { vertex_id1 } else { vertex_id2 };
+                let halfedge_id = self.face_halfedge_iter(new_face_id)
+                    .find(|halfedge_id| self.walker_from_halfedge(*halfedge_id).vertex_id() == Some(vertex_id)).unwrap();
+                corner_mapping.insert(other_halfedge_id, halfedge_id);
+            }
+
             for halfedge_id in other.face_halfedge_iter(other_face_id) {
                 if let Some(fid) = other.walker_from_halfedge(halfedge_id).as_twin().face_id()
                 {
                     if let Some(self_face_id) = face_mapping.get(&fid)
                     {
-                        for halfedge_id1 in self.face_halfedge_iter(*self_face_id)
+                        for halfedge_id1 in self.face_halfedge_iter(*self_face_id).collect::<Vec<_>>()
                         {
                             let mut walker1 = self.walker_from_halfedge(halfedge_id1);
                             let source_vertex_id = walker1.vertex_id().unwrap();
                             let sink_vertex_id = walker1.as_next().vertex_id().unwrap();
+                            let twin_id1 = walker1.halfedge_id().unwrap();
 
-                            for halfedge_id2 in self.face_halfedge_iter(new_face_id)
+                            for halfedge_id2 in self.face_halfedge_iter(new_face_id).collect::<Vec<_>>()
                             {
                                 let mut walker2 = self.walker_from_halfedge(halfedge_id2);
                                 if sink_vertex_id == walker2.vertex_id().unwrap() && source_vertex_id == walker2.as_next().vertex_id().unwrap() {
-                                    self.connectivity_info.set_halfedge_twin(walker1.halfedge_id().unwrap(), walker2.halfedge_id().unwrap());
+                                    let twin_id2 = walker2.halfedge_id().unwrap();
+                                    self.connectivity_info.set_halfedge_twin(twin_id1, twin_id2);
                                 }
                             }
                         }
@@ -75,6 +86,8 @@ impl<T: Clone> Mesh<T>
         }
 
         self.create_boundary_edges_for_faces(&face_mapping.values().copied().collect::<Vec<_>>());
+        self.connectivity_info.vertex_attributes_mut().copy_from(other.connectivity_info.vertex_attributes(), &mapping);
+        self.connectivity_info.corner_attributes_mut().copy_from(other.connectivity_info.corner_attributes(), &corner_mapping);
     }
 
     ///
@@ -205,10 +218,12 @@ impl<T: Clone> Mesh<T>
 
     fn merge_vertices(&mut self, vertex_id1: VertexID, vertex_id2: VertexID) -> Result<VertexID, Error>
     {
-        for halfedge_id in self.halfedge_iter() {
-            let walker = self.walker_from_halfedge(halfedge_id);
-            if walker.vertex_id().unwrap() == vertex_id2 {
-                self.connectivity_info.set_halfedge_vertex(walker.halfedge_id().unwrap(), vertex_id1);
+        for index in 0..self.num_halfedge_slots() {
+            if let Some(halfedge_id) = self.halfedge_in_slot(index) {
+                let walker = self.walker_from_halfedge(halfedge_id);
+                if walker.vertex_id().unwrap() == vertex_id2 {
+                    self.connectivity_info.set_halfedge_vertex(walker.halfedge_id().unwrap(), vertex_id1);
+                }
             }
         }
         self.connectivity_info.remove_vertex(vertex_id2);
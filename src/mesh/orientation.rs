@@ -9,8 +9,10 @@ impl<T: Clone> Mesh<T>
     /// Flip the orientation of all faces in the mesh, ie. such that the normal points in the opposite direction.
     pub fn flip_orientation(&mut self)
     {
-        for face_id in self.face_iter() {
-            self.flip_orientation_of_face(face_id);
+        for index in 0..self.num_face_slots() {
+            if let Some(face_id) = self.face_in_slot(index) {
+                self.flip_orientation_of_face(face_id);
+            }
         }
     }
 
@@ -33,19 +35,22 @@ impl<T: Clone> Mesh<T>
         let mut update_list = [(None, None, None); 3];
 
         let mut i = 0;
-        for halfedge_id in self.face_halfedge_iter(face_id) {
+        for halfedge_id in self.face_halfedge_iter(face_id).collect::<Vec<_>>() {
             let mut walker = self.walker_from_halfedge(halfedge_id);
             let vertex_id = walker.vertex_id();
+            let twin_id = walker.twin_id().unwrap();
             walker.as_previous();
-            update_list[i] = (Some(halfedge_id), walker.vertex_id(), walker.halfedge_id());
+            let (previous_vertex_id, previous_id) = (walker.vertex_id(), walker.halfedge_id());
+            update_list[i] = (Some(halfedge_id), previous_vertex_id, previous_id);
             i += 1;
 
-            self.connectivity_info.set_vertex_halfedge(walker.vertex_id().unwrap(), walker.halfedge_id());
+            self.connectivity_info.set_vertex_halfedge(previous_vertex_id.unwrap(), previous_id);
 
-            walker.as_next().as_twin();
-            if walker.face_id().is_none() {
-                self.connectivity_info.set_vertex_halfedge(walker.vertex_id().unwrap(), walker.halfedge_id());
-                self.connectivity_info.set_halfedge_vertex(walker.halfedge_id().unwrap(), vertex_id.unwrap());
+            let twin_walker = self.walker_from_halfedge(twin_id);
+            if twin_walker.face_id().is_none() {
+                let twin_vertex_id = twin_walker.vertex_id().unwrap();
+                self.connectivity_info.set_vertex_halfedge(twin_vertex_id, Some(twin_id));
+                self.connectivity_info.set_halfedge_vertex(twin_id, vertex_id.unwrap());
             }
         }
 
@@ -81,7 +86,8 @@ mod tests {
     {
         let indices: Vec<u32> = vec![0, 1, 2,  1, 2, 3];
         let positions: Vec<f64> = vec![0.0, 0.0, 0.0,  0.0, 0.0, 1.0,  1.0, 0.0, 0.5,  1.0, 0.0, 1.5];
-        let mut mesh = crate::MeshBuilder::<()>::new().with_indices(indices).with_positions(positions).build().unwrap();
+        // The faces have opposite orientations, which the mesh builder rejects, so the mesh is constructed directly
+        let mut mesh = crate::mesh::Mesh::new(indices, vec![(); 2], positions);
 
         mesh.flip_orientation_of_face(mesh.face_iter().next().unwrap());
         mesh.is_valid().unwrap();
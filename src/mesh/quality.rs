@@ -26,8 +26,10 @@ impl<T: Clone> Mesh<T>
             map.insert(vertex_id, p + factor * (avg_pos - p));
         }
 
-        for vertex_id in self.vertex_iter() {
-            self.move_vertex_to(vertex_id, *map.get(&vertex_id).unwrap());
+        for index in 0..self.num_vertex_slots() {
+            if let Some(vertex_id) = self.vertex_in_slot(index) {
+                self.move_vertex_to(vertex_id, *map.get(&vertex_id).unwrap());
+            }
         }
     }
 
@@ -56,8 +58,10 @@ impl<T: Clone> Mesh<T>
             self.remove_edge_if_lonely(halfedge_id);
         }
 
-        for vertex_id in self.vertex_iter() {
-            self.remove_vertex_if_lonely(vertex_id);
+        for index in 0..self.num_vertex_slots() {
+            if let Some(vertex_id) = self.vertex_in_slot(index) {
+                self.remove_vertex_if_lonely(vertex_id);
+            }
         }
     }
 
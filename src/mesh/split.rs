@@ -13,32 +13,40 @@ impl<T: Clone> Mesh<T>
     pub fn clone_subset(&self, is_included: &dyn Fn(&Mesh<T>, FaceID) -> bool) -> Mesh<T>
     {
         let mut clone = self.clone();
-        for face_id in clone.face_iter() {
-            if !is_included(&clone, face_id) {
-                let edges: Vec<HalfEdgeID> = clone.face_halfedge_iter(face_id).collect();
-                clone.remove_face_unsafe(face_id);
-                for halfedge_id in edges {
-                    let mut walker = clone.walker_from_halfedge(halfedge_id);
-                    walker.as_twin();
-                    if walker.face_id().is_none()
-                    {
-                        clone.connectivity_info.remove_halfedge(walker.halfedge_id().unwrap());
-                        clone.connectivity_info.remove_halfedge(halfedge_id);
+        for index in 0..clone.num_face_slots() {
+            if let Some(face_id) = clone.face_in_slot(index) {
+                if !is_included(&clone, face_id) {
+                    let edges: Vec<HalfEdgeID> = clone.face_halfedge_iter(face_id).collect();
+                    clone.remove_face_unsafe(face_id);
+                    for halfedge_id in edges {
+                        let mut walker = clone.walker_from_halfedge(halfedge_id);
+                        walker.as_twin();
+                        if walker.face_id().is_none()
+                        {
+                            clone.connectivity_info.remove_halfedge(walker.halfedge_id().unwrap());
+                            clone.connectivity_info.remove_halfedge(halfedge_id);
+                        }
                     }
                 }
             }
         }
 
-        for vertex_id in clone.vertex_iter() {
-            clone.connectivity_info.set_vertex_halfedge(vertex_id, None);
+        for index in 0..clone.num_vertex_slots() {
+            if let Some(vertex_id) = clone.vertex_in_slot(index) {
+                clone.connectivity_info.set_vertex_halfedge(vertex_id, None);
+            }
         }
 
-        for halfedge_id in clone.halfedge_iter() {
-            let walker = clone.walker_from_halfedge(halfedge_id);
-            clone.connectivity_info.set_vertex_halfedge(walker.vertex_id().unwrap(), walker.twin_id());
+        for index in 0..clone.num_halfedge_slots() {
+            if let Some(halfedge_id) = clone.halfedge_in_slot(index) {
+                let walker = clone.walker_from_halfedge(halfedge_id);
+                clone.connectivity_info.set_vertex_halfedge(walker.vertex_id().unwrap(), walker.twin_id());
+            }
         }
-        for vertex_id in clone.vertex_iter() {
-            clone.remove_vertex_if_lonely(vertex_id);
+        for index in 0..clone.num_vertex_slots() {
+            if let Some(vertex_id) = clone.vertex_in_slot(index) {
+                clone.remove_vertex_if_lonely(vertex_id);
+            }
         }
         clone
     }
@@ -496,12 +504,12 @@ mod tests {
     {
         let mut mesh1 = MeshBuilder::<()>::new().icosahedron().build().unwrap();
         for _ in 0..1 {
-            for face_id in mesh1.face_iter() {
+            for face_id in mesh1.face_iter().collect::<Vec<_>>() {
                 let p = mesh1.face_center(face_id).normalize();
                 mesh1.split_face(face_id, p);
             }
             mesh1.smooth_vertices(1.0);
-            for vertex_id in mesh1.vertex_iter() {
+            for vertex_id in mesh1.vertex_iter().collect::<Vec<_>>() {
                 let p = mesh1.vertex_position(vertex_id).normalize();
                 mesh1.move_vertex_to(vertex_id, p)
             }
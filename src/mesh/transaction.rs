@@ -0,0 +1,334 @@
+//! See [Mesh](crate::mesh::Mesh).
+
+use crate::mesh::*;
+use crate::mesh::connectivity_info::Snapshot;
+
+///
+/// The changes made to a mesh between [begin_transaction](crate::mesh::Mesh::begin_transaction) and
+/// [commit_transaction](crate::mesh::Mesh::commit_transaction).
+/// Contains the state of all changed vertices, half-edges, faces and attribute values both before and after the changes,
+/// so the changes can be [rolled back](crate::mesh::Mesh::rollback) and [replayed](crate::mesh::Mesh::replay).
+///
+#[derive(Clone, Debug)]
+pub struct Transaction<T>
+{
+    before: Snapshot<T>,
+    after: Snapshot<T>
+}
+
+///
+/// # Transactions
+///
+/// Methods for recording the changes made to the mesh, for example by [flip_edge](#method.flip_edge), [split_edge](#method.split_edge),
+/// [split_face](#method.split_face), [collapse_edge](#method.collapse_edge), [move_vertex_to](#method.move_vertex_to),
+/// [remove_face](#method.remove_face) or [merge_with](#method.merge_with), such that the changes can be undone and redone.
+/// All changes to the connectivity, the positions and the values of the [vertex](#vertex-attributes) and [corner](#corner-attributes) attributes are recorded.
+/// Adding or removing an attribute and [compact](#method.compact) are not recorded and the latter ends the transaction in progress.
+///
+/// A transaction only stores the primitives which are changed, so the cost is proportional to the size of the change, not the size of the mesh.
+///
+/// # Examples
+///
+/// ```
+/// # use tri_mesh::prelude::*;
+/// # fn main() -> Result<(), tri_mesh::mesh::Error> {
+/// let mut mesh = MeshBuilder::<()>::new().cube().build().unwrap();
+///
+/// mesh.begin_transaction()?;
+/// let face_id = mesh.face_iter().next().unwrap();
+/// mesh.split_face(face_id, mesh.face_center(face_id));
+/// let transaction = mesh.commit_transaction()?;
+/// assert_eq!(mesh.num_faces(), 14);
+///
+/// // Undo
+/// mesh.rollback(&transaction)?;
+/// assert_eq!(mesh.num_faces(), 12);
+///
+/// // Redo
+/// mesh.replay(&transaction)?;
+/// assert_eq!(mesh.num_faces(), 14);
+/// # Ok(())
+/// # }
+/// ```
+///
+impl<T: Clone> Mesh<T>
+{
+    ///
+    /// Starts recording all changes made to the mesh until [commit_transaction](#method.commit_transaction) is called.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if a transaction is already in progress.
+    ///
+    pub fn begin_transaction(&mut self) -> Result<(), Error>
+    {
+        if self.is_transaction_in_progress() {
+            return Err(Error::TransactionError { message: "A transaction is already in progress".to_string() });
+        }
+        self.connectivity_info.begin_journal();
+        Ok(())
+    }
+
+    /// Returns whether or not a transaction has been started and not yet committed or aborted.
+    pub fn is_transaction_in_progress(&self) -> bool
+    {
+        self.connectivity_info.is_journaling()
+    }
+
+    ///
+    /// Stops recording the changes and returns the [transaction](crate::mesh::transaction::Transaction)
+    /// which can be used to roll back or replay the changes.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if no transaction is in progress.
+    ///
+    pub fn commit_transaction(&mut self) -> Result<Transaction<T>, Error>
+    {
+        let (before, after) = self.connectivity_info.end_journal()
+            .ok_or_else(|| Error::TransactionError { message: "No transaction is in progress".to_string() })?;
+        Ok(Transaction { before, after })
+    }
+
+    ///
+    /// Stops recording the changes and reverts all changes made since [begin_transaction](#method.begin_transaction) was called.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if no transaction is in progress.
+    ///
+    pub fn abort_transaction(&mut self) -> Result<(), Error>
+    {
+        let transaction = self.commit_transaction()?;
+        self.connectivity_info.restore(&transaction.before);
+        Ok(())
+    }
+
+    ///
+    /// Reverts the changes recorded in the given transaction, ie. undo.
+    /// The mesh must be in the state right after the transaction, so several transactions must be rolled back in the opposite order of which they were committed.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if a transaction is in progress or if the vertices, half-edges and faces changed by the transaction
+    /// are not in the state right after the transaction, in which case the mesh is not changed.
+    ///
+    pub fn rollback(&mut self, transaction: &Transaction<T>) -> Result<(), Error>
+    {
+        if self.is_transaction_in_progress() {
+            return Err(Error::TransactionError { message: "Cannot roll back while a transaction is in progress".to_string() });
+        }
+        if !self.connectivity_info.is_in_state(&transaction.after) {
+            return Err(Error::TransactionError { message: "Cannot roll back, since the mesh is not in the state right after the transaction".to_string() });
+        }
+        self.connectivity_info.restore(&transaction.before);
+        Ok(())
+    }
+
+    ///
+    /// Applies the changes recorded in the given transaction again, ie. redo.
+    /// The mesh must be in the state right before the transaction, for example after the transaction is [rolled back](#method.rollback).
+    ///
+    /// # Error
+    ///
+    /// Returns an error if a transaction is in progress or if the vertices, half-edges and faces changed by the transaction
+    /// are not in the state right before the transaction, in which case the mesh is not changed.
+    ///
+    pub fn replay(&mut self, transaction: &Transaction<T>) -> Result<(), Error>
+    {
+        if self.is_transaction_in_progress() {
+            return Err(Error::TransactionError { message: "Cannot replay while a transaction is in progress".to_string() });
+        }
+        if !self.connectivity_info.is_in_state(&transaction.before) {
+            return Err(Error::TransactionError { message: "Cannot replay, since the mesh is not in the state right before the transaction".to_string() });
+        }
+        self.connectivity_info.restore(&transaction.after);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MeshBuilder;
+
+    type State = (Vec<VertexID>, Vec<HalfEdgeID>, Vec<FaceID>, Vec<Vec3>, Vec<u32>, Vec<Option<f64>>);
+
+    fn state(mesh: &Mesh<u32>) -> State
+    {
+        (mesh.vertex_iter().collect(), mesh.halfedge_iter().collect(), mesh.face_iter().collect(),
+            mesh.vertex_iter().map(|vertex_id| mesh.vertex_position(vertex_id)).collect(),
+            mesh.face_iter().map(|face_id| mesh.face_tag(face_id)).collect(),
+            mesh.vertex_iter().map(|vertex_id| mesh.vertex_attribute::<f64>("x", vertex_id)).collect())
+    }
+
+    fn create_mesh() -> Mesh<u32>
+    {
+        let mut mesh = MeshBuilder::<u32>::new().with_tags((0..20).collect()).icosahedron().build().unwrap();
+        mesh.add_vertex_attribute("x", |values: &[(f64, f64)]| values.iter().map(|(value, weight)| weight * value).sum());
+        for vertex_id in mesh.vertex_iter().collect::<Vec<_>>() {
+            let x = mesh.vertex_position(vertex_id).x;
+            mesh.set_vertex_attribute("x", vertex_id, x).unwrap();
+        }
+        mesh
+    }
+
+    #[test]
+    fn test_rollback_and_replay()
+    {
+        let mut mesh = create_mesh();
+        let original = state(&mesh);
+
+        mesh.begin_transaction().unwrap();
+        let halfedge_id = mesh.halfedge_iter().next().unwrap();
+        mesh.flip_edge(halfedge_id).unwrap();
+        let halfedge_id = mesh.halfedge_iter().nth(7).unwrap();
+        let (p0, p1) = mesh.edge_positions(halfedge_id);
+        mesh.split_edge(halfedge_id, 0.5 * (p0 + p1));
+        let face_id = mesh.face_iter().nth(3).unwrap();
+        let vertex_id = mesh.split_face(face_id, mesh.face_center(face_id));
+        let halfedge_id = mesh.vertex_halfedge_iter(vertex_id).next().unwrap();
+        mesh.collapse_edge(halfedge_id);
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        mesh.move_vertex_by(vertex_id, vec3(0.1, 0.2, 0.3));
+        let transaction = mesh.commit_transaction().unwrap();
+        mesh.is_valid().unwrap();
+        let changed = state(&mesh);
+        assert_ne!(original, changed);
+
+        mesh.rollback(&transaction).unwrap();
+        mesh.is_valid().unwrap();
+        assert_eq!(original, state(&mesh));
+
+        mesh.replay(&transaction).unwrap();
+        mesh.is_valid().unwrap();
+        assert_eq!(changed, state(&mesh));
+    }
+
+    #[test]
+    fn test_undo_redo_several_transactions()
+    {
+        let mut mesh = create_mesh();
+        let mut states = vec![state(&mesh)];
+        let mut transactions = Vec::new();
+        for i in 0..5 {
+            mesh.begin_transaction().unwrap();
+            if i % 2 == 0 {
+                let face_id = mesh.face_iter().nth(i).unwrap();
+                mesh.remove_face(face_id);
+            }
+            else {
+                let halfedge_id = mesh.edge_iter().find(|halfedge_id| !mesh.is_edge_on_boundary(*halfedge_id)).unwrap();
+                mesh.collapse_edge(halfedge_id);
+            }
+            transactions.push(mesh.commit_transaction().unwrap());
+            states.push(state(&mesh));
+        }
+
+        for i in (0..5).rev() {
+            mesh.rollback(&transactions[i]).unwrap();
+            assert_eq!(states[i], state(&mesh));
+        }
+        for i in 0..5 {
+            mesh.replay(&transactions[i]).unwrap();
+            assert_eq!(states[i+1], state(&mesh));
+        }
+    }
+
+    #[test]
+    fn test_rollback_merge()
+    {
+        let mut mesh1 = MeshBuilder::<()>::new().with_indices(vec![0, 1, 2]).with_positions(vec![-2.0, 0.0, -2.0,  -2.0, 0.0, 2.0,  2.0, 0.0, 0.0]).build().unwrap();
+        let mesh2 = MeshBuilder::<()>::new().with_indices(vec![0, 1, 2]).with_positions(vec![-2.0, 0.0, 2.0,  -2.0, 0.0, -2.0,  -2.0, 0.5, 0.0]).build().unwrap();
+
+        mesh1.begin_transaction().unwrap();
+        mesh1.merge_with(&mesh2).unwrap();
+        let transaction = mesh1.commit_transaction().unwrap();
+        assert_eq!(mesh1.num_faces(), 2);
+        assert_eq!(mesh1.num_vertices(), 4);
+
+        mesh1.rollback(&transaction).unwrap();
+        mesh1.is_valid().unwrap();
+        assert_eq!(mesh1.num_faces(), 1);
+        assert_eq!(mesh1.num_vertices(), 3);
+    }
+
+    #[test]
+    fn test_abort_transaction()
+    {
+        let mut mesh = create_mesh();
+        let original = state(&mesh);
+
+        mesh.begin_transaction().unwrap();
+        assert!(mesh.begin_transaction().is_err());
+        let halfedge_id = mesh.halfedge_iter().next().unwrap();
+        mesh.collapse_edge(halfedge_id);
+        mesh.abort_transaction().unwrap();
+
+        assert!(!mesh.is_transaction_in_progress());
+        assert!(mesh.commit_transaction().is_err());
+        mesh.is_valid().unwrap();
+        assert_eq!(original, state(&mesh));
+    }
+
+    #[test]
+    fn test_rollback_and_replay_in_wrong_state()
+    {
+        let mut mesh = create_mesh();
+        let mut transactions = Vec::new();
+        for i in 0..2 {
+            mesh.begin_transaction().unwrap();
+            let face_id = mesh.face_iter().nth(i).unwrap();
+            mesh.split_face(face_id, mesh.face_center(face_id));
+            transactions.push(mesh.commit_transaction().unwrap());
+        }
+        let changed = state(&mesh);
+
+        // The transactions must be rolled back in the opposite order and can only be replayed after they are rolled back
+        assert!(mesh.rollback(&transactions[0]).is_err());
+        assert!(mesh.replay(&transactions[1]).is_err());
+        assert_eq!(changed, state(&mesh));
+
+        mesh.rollback(&transactions[1]).unwrap();
+        assert!(mesh.rollback(&transactions[1]).is_err());
+        mesh.rollback(&transactions[0]).unwrap();
+        mesh.is_valid().unwrap();
+        assert!(mesh.replay(&transactions[1]).is_err());
+
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        mesh.move_vertex_by(vertex_id, vec3(0.1, 0.0, 0.0));
+        assert!(mesh.replay(&transactions[0]).is_err());
+        mesh.move_vertex_by(vertex_id, vec3(-0.1, 0.0, 0.0));
+        mesh.replay(&transactions[0]).unwrap();
+        mesh.replay(&transactions[1]).unwrap();
+        assert_eq!(changed, state(&mesh));
+    }
+
+    #[test]
+    fn test_clone_during_transaction()
+    {
+        let mut mesh = create_mesh();
+        let original = state(&mesh);
+
+        mesh.begin_transaction().unwrap();
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        mesh.set_vertex_attribute("x", vertex_id, 10.0).unwrap();
+        let halfedge_id = mesh.halfedge_iter().next().unwrap();
+        mesh.collapse_edge(halfedge_id);
+        let mut clone = mesh.clone();
+        assert!(clone.is_transaction_in_progress());
+
+        let changed = state(&clone);
+        let transaction = clone.commit_transaction().unwrap();
+        assert!(!clone.is_transaction_in_progress());
+        clone.rollback(&transaction).unwrap();
+        clone.is_valid().unwrap();
+        assert_eq!(original, state(&clone));
+        clone.replay(&transaction).unwrap();
+        assert_eq!(changed, state(&clone));
+
+        // The transaction of the original mesh is not affected by the clone
+        mesh.abort_transaction().unwrap();
+        assert_eq!(original, state(&mesh));
+    }
+}
@@ -41,9 +41,11 @@ impl<T: Clone> Mesh<T>
     ///
     pub fn scale(&mut self, scale: f64)
     {
-        for vertex_id in self.vertex_iter() {
-            let p = self.vertex_position(vertex_id);
-            self.move_vertex_to(vertex_id, p * scale);
+        for index in 0..self.num_vertex_slots() {
+            if let Some(vertex_id) = self.vertex_in_slot(index) {
+                let p = self.vertex_position(vertex_id);
+                self.move_vertex_to(vertex_id, p * scale);
+            }
         }
     }
 
@@ -77,9 +79,11 @@ impl<T: Clone> Mesh<T>
     ///
     pub fn non_uniform_scale(&mut self, scale_x: f64, scale_y: f64, scale_z: f64)
     {
-        for vertex_id in self.vertex_iter() {
-            let p = self.vertex_position(vertex_id);
-            self.move_vertex_to(vertex_id, vec3(p.x * scale_x, p.y * scale_y, p.z * scale_z));
+        for index in 0..self.num_vertex_slots() {
+            if let Some(vertex_id) = self.vertex_in_slot(index) {
+                let p = self.vertex_position(vertex_id);
+                self.move_vertex_to(vertex_id, vec3(p.x * scale_x, p.y * scale_y, p.z * scale_z));
+            }
         }
     }
 
@@ -111,8 +115,10 @@ impl<T: Clone> Mesh<T>
     ///
     pub fn translate(&mut self, translation: Vec3)
     {
-        for vertex_id in self.vertex_iter() {
-            self.move_vertex_by(vertex_id, translation);
+        for index in 0..self.num_vertex_slots() {
+            if let Some(vertex_id) = self.vertex_in_slot(index) {
+                self.move_vertex_by(vertex_id, translation);
+            }
         }
     }
 
@@ -145,9 +151,11 @@ impl<T: Clone> Mesh<T>
     ///
     pub fn rotate(&mut self, rotation: Mat3)
     {
-        for vertex_id in self.vertex_iter() {
-            let p = self.vertex_position(vertex_id);
-            self.move_vertex_to(vertex_id, rotation * p);
+        for index in 0..self.num_vertex_slots() {
+            if let Some(vertex_id) = self.vertex_in_slot(index) {
+                let p = self.vertex_position(vertex_id);
+                self.move_vertex_to(vertex_id, rotation * p);
+            }
         }
     }
 
@@ -180,10 +188,12 @@ impl<T: Clone> Mesh<T>
     ///
     pub fn apply_transformation(&mut self, transformation: Mat4)
     {
-        for vertex_id in self.vertex_iter() {
-            let p = self.vertex_position(vertex_id);
-            let p_new = (transformation * p.extend(1.0)).truncate();
-            self.move_vertex_to(vertex_id, p_new);
+        for index in 0..self.num_vertex_slots() {
+            if let Some(vertex_id) = self.vertex_in_slot(index) {
+                let p = self.vertex_position(vertex_id);
+                let p_new = (transformation * p.extend(1.0)).truncate();
+                self.move_vertex_to(vertex_id, p_new);
+            }
         }
     }
 
@@ -3,6 +3,26 @@
 //!
 
 use crate::mesh::Mesh;
+use crate::mesh::ids::{VertexID, FaceID};
+#[cfg(feature = "obj-io")]
+use crate::mesh::material::Material;
+
+#[cfg(feature = "ply-io")]
+use crate::mesh::math::{Vec3, Vec4, InnerSpace};
+
+#[cfg(feature = "ply-io")]
+mod ply;
+#[cfg(feature = "gltf-io")]
+mod gltf;
+#[cfg(feature = "off-io")]
+mod off;
+#[cfg(feature = "vtk-io")]
+mod vtk;
+mod validation;
+mod weld;
+mod triangulation;
+mod isosurface;
+mod delaunay;
 
 /// MeshBuilder errors.
 #[derive(Debug)]
@@ -17,9 +37,56 @@ pub enum Error {
         /// Error reason.
         message: String
     },
+    /// Returned from the build function when the number of positions is not a multiple of three,
+    /// from [convex_hull](crate::mesh_builder::MeshBuilder::convex_hull) when all points are in the same plane
+    /// and from [delaunay](crate::mesh_builder::MeshBuilder::delaunay) and [constrained_delaunay](crate::mesh_builder::MeshBuilder::constrained_delaunay) when all points are on a line.
+    InvalidPositions {
+        /// Error reason.
+        message: String
+    },
+    /// Returned from the build function when the number of indices is not a multiple of three or an index refers to a vertex which does not exist.
+    InvalidIndices {
+        /// Error reason.
+        message: String
+    },
+    /// Returned from the build function when the number of tags is not equal to the number of faces.
+    InvalidTags {
+        /// Error reason.
+        message: String
+    },
+    /// Returned from the build function when a face refers to the same vertex more than once or two of its vertices are welded into one vertex.
+    DegenerateFace {
+        /// Error reason.
+        message: String
+    },
+    /// Returned from the build function when an edge is shared by more than two faces or two faces use an edge in the same direction.
+    NonManifoldEdge {
+        /// Error reason.
+        message: String
+    },
+    /// Returned from the build function when the faces around a vertex are not connected to each other through their edges.
+    NonManifoldVertex {
+        /// Error reason.
+        message: String
+    },
+    /// Returned from the build function when a parameter given to the builder is invalid, for example a negative weld tolerance
+    /// or too few subdivisions of a primitive.
+    InvalidParameter {
+        /// Error reason.
+        message: String
+    },
+    /// Returned from [constrained_delaunay](crate::mesh_builder::MeshBuilder::constrained_delaunay) when a segment refers to a point which does not exist,
+    /// the segments cross each other or they do not enclose any area.
+    InvalidSegments {
+        /// Error reason.
+        message: String
+    },
     /// Invalid 3d file format
     #[cfg(feature = "3d-io")]
-    Bincode(bincode::Error)
+    Bincode(bincode::Error),
+    /// IO error
+    #[cfg(feature = "gltf-io")]
+    IO(std::io::Error)
 }
 
 #[cfg(feature = "3d-io")]
@@ -29,9 +96,69 @@ impl From<bincode::Error> for Error {
     }
 }
 
+#[cfg(feature = "gltf-io")]
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Error {
+        Error::IO(err)
+    }
+}
+
+///
+/// Describes the changes made to the input by [build_with_repair](crate::mesh_builder::MeshBuilder::build_with_repair).
+/// Faces are identified by their index in the input, i.e. face `x` is defined by the indices `3*x`, `3*x+1` and `3*x+2`.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RepairReport {
+    /// The number of coordinates removed from the end of the positions because the number of positions was not a multiple of three.
+    pub removed_coordinates: usize,
+    /// The number of indices removed from the end of the indices because the number of indices was not a multiple of three.
+    pub removed_indices: usize,
+    /// The number of default tags added because there were fewer tags than faces.
+    pub added_tags: usize,
+    /// The number of tags removed from the end of the tags because there were more tags than faces.
+    pub removed_tags: usize,
+    /// The faces which were removed because they refer to a vertex which does not exist.
+    pub out_of_range_faces: Vec<usize>,
+    /// The faces which were removed because they refer to the same vertex more than once.
+    pub degenerate_faces: Vec<usize>,
+    /// The faces which were removed because two of their vertices are welded into one vertex, see [with_welding](crate::mesh_builder::MeshBuilder::with_welding).
+    pub welded_faces: Vec<usize>,
+    /// The faces which were removed because they share an edge with two other faces or use an edge in the same direction as another face,
+    /// which is the case for duplicated faces and faces with the opposite orientation of their neighbours.
+    pub non_manifold_faces: Vec<usize>,
+    /// The faces which were removed because they are only connected to the first faces around one of their vertices through that vertex.
+    pub non_manifold_vertex_faces: Vec<usize>,
+    /// The vertices which were removed because they are not part of any face.
+    pub unreferenced_vertices: Vec<usize>
+}
+
+impl RepairReport {
+    /// Returns whether or not the input was changed.
+    pub fn is_empty(&self) -> bool
+    {
+        *self == RepairReport::default()
+    }
+}
+
+///
+/// Describes what a face in an .obj file belongs to, see [with_obj_attributes](crate::mesh_builder::MeshBuilder::with_obj_attributes).
+///
+#[cfg(feature = "obj-io")]
+#[derive(Debug)]
+pub struct ObjFace<'a> {
+    /// The name of the object.
+    pub object: &'a str,
+    /// The names of the groups (`g`).
+    pub groups: &'a [String],
+    /// The name of the material (`usemtl`), if any.
+    pub material: Option<&'a str>,
+    /// The smoothing groups (`s`).
+    pub smoothing_groups: &'a [u32]
+}
+
 ///
 /// `MeshBuilder` contains functionality to build a mesh from either raw data (indices, positions)
-/// or from simple geometric shapes (box, icosahedron, cylinder, ..) or from file source (.obj).
+/// or from simple geometric shapes (box, icosahedron, cylinder, ..) or from file source (.obj, .stl, .ply, .gltf, .off, .vtk).
 /// A tag can optionally be set for each face.
 ///
 #[derive(Debug, Default)]
@@ -40,6 +167,38 @@ pub struct MeshBuilder<T> {
     tags: Option<Vec<T>>,
     default_tag: Option<T>,
     positions: Option<Vec<f64>>,
+    weld_tolerance: Option<f64>,
+    attributes: Attributes<T>,
+    /// The first error caused by an invalid parameter, which is returned when the mesh is built.
+    error: Option<Error>,
+}
+
+/// Adds an attribute to the built mesh.
+type AttributeInitializer<T> = Box<dyn FnOnce(&mut Mesh<T>, &InputMapping) + Send + Sync>;
+
+/// Maps the vertices and faces of the input to the vertices and faces of the built mesh, which can differ if the input is repaired.
+struct InputMapping {
+    /// The indices of the input.
+    indices: Vec<u32>,
+    /// The vertex built from each vertex of the input, if it is kept.
+    vertices: Vec<Option<VertexID>>,
+    /// The face built from each face of the input, if it is kept.
+    faces: Vec<Option<FaceID>>
+}
+
+/// The attributes which are added to the mesh when it is built.
+struct Attributes<T>(Vec<AttributeInitializer<T>>);
+
+impl<T> Default for Attributes<T> {
+    fn default() -> Self {
+        Attributes(Vec::new())
+    }
+}
+
+impl<T> std::fmt::Debug for Attributes<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} attributes", self.0.len())
+    }
 }
 
 impl<T: Clone + Default> MeshBuilder<T> {
@@ -47,7 +206,7 @@ impl<T: Clone + Default> MeshBuilder<T> {
     /// Creates a new [MeshBuilder](crate::mesh_builder::MeshBuilder) instance.
     pub fn new() -> Self
     {
-        MeshBuilder {indices: None, tags: None, default_tag: None, positions: None}
+        MeshBuilder {indices: None, tags: None, default_tag: None, positions: None, weld_tolerance: None, attributes: Attributes::default(), error: None}
     }
 
     ///
@@ -162,6 +321,207 @@ impl<T: Clone + Default> MeshBuilder<T> {
         self
     }
 
+    ///
+    /// Set the positions of each vertex (see [with_positions](#method.with_positions)) and the faces from polygons with any number of corners,
+    /// which can be concave and have holes. Each polygon is given as a list of loops of vertex indices, where the first loop is the outline
+    /// and the other loops are holes. The polygons must be planar or almost planar and are triangulated by ear clipping,
+    /// where the triangles are oriented like the outline and added polygon by polygon.
+    /// A polygon with an index in any of its loops which refers to a vertex which does not exist is not triangulated,
+    /// instead [build](#method.build) returns an [InvalidIndices](crate::mesh_builder::Error::InvalidIndices) error
+    /// and [build_with_repair](#method.build_with_repair) leaves out the polygon.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::mesh_builder::{MeshBuilder, Error};
+    /// #
+    /// # fn main() -> Result<(), Box<Error>> {
+    /// // A square with a triangular hole
+    /// let positions: Vec<f64> = vec![0.0, 0.0, 0.0,  3.0, 0.0, 0.0,  3.0, 3.0, 0.0,  0.0, 3.0, 0.0,
+    ///                                1.0, 1.0, 0.0,  2.0, 1.0, 0.0,  1.5, 2.0, 0.0];
+    /// let mesh = MeshBuilder::<()>::new().with_polygons(positions, &[vec![vec![0, 1, 2, 3], vec![4, 5, 6]]]).build()?;
+    ///
+    /// assert_eq!(mesh.num_faces(), 7);
+    /// assert_eq!(mesh.num_vertices(), 7);
+    ///
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn with_polygons(self, positions: Vec<f64>, polygons: &[Vec<Vec<u32>>]) -> Self
+    {
+        use crate::mesh::math::Vec3;
+        let position = |index: u32| {
+            let i = 3 * index as usize;
+            positions.get(i..i + 3).map(|p| Vec3::new(p[0], p[1], p[2]))
+        };
+        let mut indices = Vec::new();
+        for polygon in polygons.iter().filter(|polygon| !polygon.is_empty()) {
+            // A polygon which refers to a vertex which does not exist in any of its loops is replaced by a face referring to that vertex,
+            // so it is reported when the mesh is built
+            if let Some(index) = polygon.iter().flatten().find(|index| position(**index).is_none()) {
+                indices.extend_from_slice(&[*index; 3]);
+                continue;
+            }
+            let loops = polygon.iter().map(|indices| indices.iter().map(|index| position(*index).unwrap()).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let corners = polygon.iter().flatten().collect::<Vec<_>>();
+            for triangle in triangulation::triangulate_3d(&loops[0], &loops[1..]) {
+                indices.extend(triangle.iter().map(|corner| *corners[*corner as usize]));
+            }
+        }
+        self.with_positions(positions).with_indices(indices)
+    }
+
+    ///
+    /// Adds a [vertex attribute](crate::mesh::Mesh#vertex-attributes) with the given name and interpolation function to the mesh when it is built,
+    /// where `values[x]` is the value of vertex `x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::mesh_builder::{MeshBuilder, Error};
+    /// #
+    /// # fn main() -> Result<(), Box<Error>> {
+    /// let positions: Vec<f64> = vec![0.0, 0.0, 0.0,  1.0, 0.0, -0.5,  -1.0, 0.0, -0.5];
+    /// let temperatures: Vec<f64> = vec![20.0, 21.0, 19.5];
+    /// let mesh = MeshBuilder::<()>::new().with_positions(positions)
+    ///     .with_vertex_attribute("temperature", temperatures, |values: &[(f64, f64)]| values.iter().map(|(value, weight)| weight * value).sum())
+    ///     .build()?;
+    ///
+    /// let vertex_id = mesh.vertex_iter().last().unwrap();
+    /// assert_eq!(mesh.vertex_attribute::<f64>("temperature", vertex_id), Some(19.5));
+    ///
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn with_vertex_attribute<A: Clone + Send + Sync + 'static>(self, name: &str, values: Vec<A>,
+                                                                  interpolate: impl Fn(&[(A, f64)]) -> A + Send + Sync + 'static) -> Self
+    {
+        self.with_partial_vertex_attribute(name, values.into_iter().map(Some).collect(), interpolate)
+    }
+
+    /// Same as [with_vertex_attribute](#method.with_vertex_attribute), except that vertices with no value are left without a value.
+    fn with_partial_vertex_attribute<A: Clone + Send + Sync + 'static>(mut self, name: &str, values: Vec<Option<A>>,
+                                                                      interpolate: impl Fn(&[(A, f64)]) -> A + Send + Sync + 'static) -> Self
+    {
+        let name = name.to_string();
+        self.attributes.0.push(Box::new(move |mesh: &mut Mesh<T>, mapping: &InputMapping| {
+            mesh.add_vertex_attribute(&name, interpolate);
+            for (vertex_id, value) in mapping.vertices.iter().zip(values) {
+                // A vertex which several vertices of the input are welded into gets the first value
+                if let (Some(vertex_id), Some(value)) = (vertex_id, value) {
+                    if mesh.vertex_attribute::<A>(&name, *vertex_id).is_none() {
+                        mesh.set_vertex_attribute(&name, *vertex_id, value).unwrap();
+                    }
+                }
+            }
+        }));
+        self
+    }
+
+    ///
+    /// Adds a [corner attribute](crate::mesh::Mesh#corner-attributes) with the given name and interpolation function to the mesh when it is built,
+    /// where `values[3*x + i]` is the value of the corner of face `x` at the vertex with index `indices[3*x + i]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::mesh_builder::{MeshBuilder, Error};
+    /// #
+    /// # fn main() -> Result<(), Box<Error>> {
+    /// let indices: Vec<u32> = vec![0, 1, 2,  0, 2, 3];
+    /// let positions: Vec<f64> = vec![0.0, 0.0, 0.0,  1.0, 0.0, 0.0,  1.0, 1.0, 0.0,  0.0, 1.0, 0.0];
+    /// // Each face has its own texture, so the texture coordinates are different for the two faces at the shared vertices
+    /// let uvs: Vec<(f64, f64)> = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0),  (0.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+    /// let mesh = MeshBuilder::<()>::new().with_indices(indices).with_positions(positions)
+    ///     .with_corner_attribute("uv", uvs, |values: &[((f64, f64), f64)]| values[0].0)
+    ///     .build()?;
+    ///
+    /// for face_id in mesh.face_iter() {
+    ///     for halfedge_id in mesh.face_halfedge_iter(face_id) {
+    ///         assert!(mesh.corner_attribute::<(f64, f64)>("uv", halfedge_id).is_some());
+    ///     }
+    /// }
+    ///
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn with_corner_attribute<A: Clone + Send + Sync + 'static>(self, name: &str, values: Vec<A>,
+                                                                  interpolate: impl Fn(&[(A, f64)]) -> A + Send + Sync + 'static) -> Self
+    {
+        self.with_partial_corner_attribute(name, values.into_iter().map(Some).collect(), interpolate)
+    }
+
+    /// Same as [with_corner_attribute](#method.with_corner_attribute), except that corners with no value are left without a value.
+    fn with_partial_corner_attribute<A: Clone + Send + Sync + 'static>(mut self, name: &str, values: Vec<Option<A>>,
+                                                                      interpolate: impl Fn(&[(A, f64)]) -> A + Send + Sync + 'static) -> Self
+    {
+        let name = name.to_string();
+        self.attributes.0.push(Box::new(move |mesh: &mut Mesh<T>, mapping: &InputMapping| {
+            mesh.add_corner_attribute(&name, interpolate);
+            for (corner, value) in values.into_iter().enumerate() {
+                if let (Some(value), Some(Some(face_id))) = (value, mapping.faces.get(corner / 3)) {
+                    let vertex_id = mapping.vertices[mapping.indices[corner] as usize].unwrap();
+                    let halfedge_id = mesh.face_halfedge_iter(*face_id)
+                        .find(|halfedge_id| mesh.walker_from_halfedge(*halfedge_id).vertex_id() == Some(vertex_id)).unwrap();
+                    mesh.set_corner_attribute(&name, halfedge_id, value).unwrap();
+                }
+            }
+        }));
+        self
+    }
+
+    ///
+    /// Welds the vertices which are within the given distance of each other into one vertex when the mesh is built,
+    /// which for example turns a triangle soup, where each triangle has its own three vertices, into a connected mesh.
+    /// A vertex is welded into the first vertex within the tolerance and keeps the position of that vertex.
+    /// A tolerance of zero only welds vertices with exactly the same position,
+    /// and a tolerance which is negative or not finite results in an [InvalidParameter](crate::mesh_builder::Error::InvalidParameter) error when the mesh is built.
+    /// Faces which collapse because two of their vertices are welded result in a [DegenerateFace](crate::mesh_builder::Error::DegenerateFace) error
+    /// when the mesh is built, unless it is built with [build_with_repair](#method.build_with_repair) which removes them.
+    /// [Vertex attributes](#method.with_vertex_attribute) get the value of the first of the welded vertices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::mesh_builder::{MeshBuilder, Error};
+    /// #
+    /// # fn main() -> Result<(), Box<Error>> {
+    /// let mesh = MeshBuilder::<()>::new().unconnected_cube().with_welding(0.0001).build()?;
+    ///
+    /// assert_eq!(mesh.num_faces(), 12);
+    /// assert_eq!(mesh.num_vertices(), 8);
+    /// assert!(mesh.is_closed());
+    ///
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn with_welding(mut self, tolerance: f64) -> Self
+    {
+        if !tolerance.is_finite() || tolerance < 0.0 {
+            return self.with_error(Error::InvalidParameter { message: format!("The weld tolerance ({}) must be zero or a positive number", tolerance) });
+        }
+        self.weld_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Stores the error, which is returned when the mesh is built, unless an error has already been stored.
+    fn with_error(mut self, error: Error) -> Self
+    {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+        self
+    }
+
     ///
     /// Parses the .obj file and extracts the connectivity information (indices) and positions which is used to construct a mesh when the `build` method is called.
     /// If the .obj file contains multiple objects, all objects are added to the mesh, but they will not be connected.
@@ -186,12 +546,16 @@ impl<T: Clone + Default> MeshBuilder<T> {
     /// and the materials.
     /// If the .obj file contains multiple objects, all objects are added to the mesh, but they will not be connected.
     ///
+    /// This is the legacy way of reading materials, which does not read the .mtl file. The `material_fn` function is only given
+    /// the index of the group of faces using the same material within its object, in the order in which the groups appear in the file.
+    /// Use [with_obj_and_mtl](#method.with_obj_and_mtl) to resolve the materials from the accompanying .mtl file instead.
+    ///
     /// # Examples
     ///
     /// ```no_run
     /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
     ///     let obj_source = std::fs::read_to_string("foo.obj").expect("Something went wrong reading the file");
-    ///     let mesh = tri_mesh::mesh_builder::MeshBuilder::<()>::new().with_obj(obj_source).build()?;
+    ///     let mesh = tri_mesh::mesh_builder::MeshBuilder::<usize>::new().with_obj_multimaterial(obj_source, |group| group).build()?;
     /// #    Ok(())
     /// # }
     /// ```
@@ -223,6 +587,7 @@ impl<T: Clone + Default> MeshBuilder<T> {
     /// Parses the .obj file and extracts the connectivity information (indices) and positions which is used to construct a mesh when the `build` method is called,
     /// as well as the materials.
     /// Only the object with the given name is extracted from the file.
+    /// The materials are given to `material_fn` as described for the legacy [with_obj_multimaterial](#method.with_obj_multimaterial).
     ///
     /// # Examples
     ///
@@ -236,7 +601,7 @@ impl<T: Clone + Default> MeshBuilder<T> {
     #[cfg(feature = "obj-io")]
     pub fn with_named_obj_multimaterial(mut self, source: String, object_name: &str, mut material_fn: impl FnMut(usize) -> T) -> Self
     {
-        let objs = wavefront_obj::obj::parse(source).unwrap();
+        let objs = wavefront_obj::obj::parse(triangulation::triangulate_obj_faces(source)).unwrap();
         let mut positions = Vec::new();
         let mut indices = Vec::new();
         let mut tags = Vec::new();
@@ -273,99 +638,615 @@ impl<T: Clone + Default> MeshBuilder<T> {
     }
 
     ///
-    /// Parses the .3d file and extracts the connectivity information (indices) and positions which is used to construct a mesh when the `build` method is called.
+    /// Parses the .obj file like [with_obj](#method.with_obj), but in addition keeps the texture coordinates and normals of the corners
+    /// and lets the `tag_fn` function compute the tag of each face from the object, groups, material and smoothing groups the face belongs to.
+    ///
+    /// Since texture coordinates and normals are specified for each corner of each face in the .obj format, they are stored in [corner attributes](crate::mesh::Mesh#corner-attributes),
+    /// the texture coordinates in the `"uv"` attribute of type [Vec2](crate::mesh::math::Vec2) and the normals in the `"normal"` attribute of type [Vec3](crate::mesh::math::Vec3).
+    /// A corner without a texture coordinate or normal in the file has no value in the corresponding attribute,
+    /// and an attribute is only added if at least one corner has a value.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # use tri_mesh::mesh_builder::ObjFace;
     /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
-    ///     let bytes = std::fs::read("foo.3d").expect("Something went wrong reading the file");
-    ///     let mesh = tri_mesh::mesh_builder::MeshBuilder::<()>::new().with_3d(&bytes)?.build()?;
+    /// let source = "o square
+    ///     v 0 0 0
+    ///     v 1 0 0
+    ///     v 1 1 0
+    ///     v 0 1 0
+    ///     vt 0 0
+    ///     vt 1 0
+    ///     vt 1 1
+    ///     vt 0 1
+    ///     g left
+    ///     f 1/1 2/2 4/4
+    ///     g right
+    ///     f 2/2 3/3 4/4".to_string();
+    ///
+    /// let mesh = MeshBuilder::<String>::new().with_obj_attributes(source, |face: &ObjFace| face.groups.join(" "))?.build()?;
+    ///
+    /// let face_id = mesh.face_iter().last().unwrap();
+    /// assert_eq!(mesh.face_tag(face_id), "right");
+    /// let halfedge_id = mesh.face_halfedge_iter(face_id).next().unwrap();
+    /// assert!(mesh.corner_attribute::<Vec2>("uv", halfedge_id).is_some());
     /// #    Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "3d-io")]
-    pub fn with_3d(mut self, bytes: &[u8]) -> Result<Self, Error>
+    ///
+    /// # Errors
+    ///
+    /// If the source is not a valid .obj file, an [InvalidFile](crate::mesh_builder::Error::InvalidFile) error is returned.
+    ///
+    #[cfg(feature = "obj-io")]
+    pub fn with_obj_attributes(mut self, source: String, mut tag_fn: impl FnMut(&ObjFace) -> T) -> Result<Self, Error>
     {
-        let decoded: crate::mesh::IOMesh = bincode::deserialize(bytes)?;
-        if decoded.magic_number != 61 {
-            Err(Error::InvalidFile {message: "Invalid 3d file!".to_string()})
+        use crate::mesh::math::{vec2, vec3, Vec2, Vec3, InnerSpace};
+        let objs = wavefront_obj::obj::parse(triangulation::triangulate_obj_faces(source)).map_err(|e| Error::InvalidFile {message: format!("Invalid obj file at line {}: {}", e.line_number, e.message)})?;
+        let mut positions = Vec::new();
+        let mut indices = Vec::new();
+        let mut tags = Vec::new();
+        let mut uvs = Vec::new();
+        let mut normals = Vec::new();
+
+        for obj in objs.objects.iter() {
+            let start_index = positions.len()/3;
+            for v in obj.vertices.iter() {
+                positions.extend_from_slice(&[v.x, v.y, v.z]);
+            }
+
+            for geometry in obj.geometry.iter() {
+                for shape in geometry.shapes.iter() {
+                    if let wavefront_obj::obj::Primitive::Triangle(i0, i1, i2) = shape.primitive {
+                        for (vertex_index, uv_index, normal_index) in [i0, i1, i2].iter() {
+                            indices.push((start_index + vertex_index) as u32);
+                            uvs.push(uv_index.map(|i| vec2(obj.tex_vertices[i].u, obj.tex_vertices[i].v)));
+                            normals.push(normal_index.map(|i| vec3(obj.normals[i].x, obj.normals[i].y, obj.normals[i].z)));
+                        }
+                        tags.push(tag_fn(&ObjFace {
+                            object: &obj.name,
+                            groups: &shape.groups,
+                            material: geometry.material_name.as_deref(),
+                            smoothing_groups: &shape.smoothing_groups
+                        }));
+                    }
+                }
+            }
+        }
+
+        if uvs.iter().any(|uv| uv.is_some()) {
+            self = self.with_partial_corner_attribute("uv", uvs, |values: &[(Vec2, f64)]|
+                values.iter().map(|(value, weight)| *weight * *value).sum());
         }
-        else {
-            self.positions = Some(decoded.positions.iter().map(|x| *x as f64).collect());
-            self.indices = Some(decoded.indices);
-            Ok(self)
+        if normals.iter().any(|normal| normal.is_some()) {
+            self = self.with_partial_corner_attribute("normal", normals, |values: &[(Vec3, f64)]|
+                values.iter().map(|(value, weight)| *weight * *value).sum::<Vec3>().normalize());
         }
+        self.positions = Some(positions);
+        self.indices = Some(indices);
+        self.tags = Some(tags);
+        Ok(self)
     }
 
     ///
-    /// Builds the mesh. Returns the mesh if the definition is valid and otherwise an error.
+    /// Parses the .obj file like [with_obj_attributes](#method.with_obj_attributes) together with the accompanying .mtl file,
+    /// and lets the `material_fn` function compute the tag of each face from the [material](crate::mesh::material::Material) the face uses.
+    /// The material is `None` if the face does not use a material or if the material is not defined in the .mtl file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// let obj_source = "mtllib square.mtl
+    ///     v 0 0 0
+    ///     v 1 0 0
+    ///     v 1 1 0
+    ///     v 0 1 0
+    ///     usemtl red
+    ///     f 1 2 4
+    ///     usemtl green
+    ///     f 2 3 4".to_string();
+    /// let mtl_source = "newmtl red
+    ///     Kd 1 0 0
+    ///     newmtl green
+    ///     Kd 0 1 0";
+    ///
+    /// // Tag the faces with the red component of the diffuse color
+    /// let mesh = MeshBuilder::<u8>::new()
+    ///     .with_obj_and_mtl(obj_source, mtl_source, |material| material.and_then(|m| m.diffuse_color).map(|c| (255.0 * c.x) as u8).unwrap_or(255))?
+    ///     .build()?;
+    ///
+    /// let face_id = mesh.face_iter().last().unwrap();
+    /// assert_eq!(mesh.face_tag(face_id), 0);
+    /// #    Ok(())
+    /// # }
+    /// ```
     ///
     /// # Errors
     ///
-    /// If no positions are specified, [NoPositionsSpecified](crate::mesh_builder::Error::NoPositionsSpecified) error is returned.
+    /// If the source is not a valid .obj file or the .mtl source is not a valid .mtl file, an [InvalidFile](crate::mesh_builder::Error::InvalidFile) error is returned.
     ///
-    pub fn build(self) -> Result<Mesh<T>, Error>
+    #[cfg(feature = "obj-io")]
+    pub fn with_obj_and_mtl(self, source: String, mtl_source: &str, mut material_fn: impl FnMut(Option<&Material>) -> T) -> Result<Self, Error>
     {
-        let positions = self.positions.ok_or(
-            Error::NoPositionsSpecified {message: format!("Did you forget to specify the vertex positions?")})?;
-        let indices = self.indices.unwrap_or_else(|| (0..positions.len() as u32/3).collect());
-        let default_tag = self.default_tag.unwrap_or(T::default());
-        let tags = self.tags.unwrap_or_else(|| vec![default_tag; indices.len() / 3]);
-        Ok(Mesh::new(indices, tags, positions))
+        let materials = crate::mesh::material::parse_mtl(mtl_source).map_err(|message| Error::InvalidFile { message })?;
+        // The first definition of a material is used if it is defined more than once
+        let mut materials_by_name = std::collections::HashMap::new();
+        for material in materials.iter() {
+            materials_by_name.entry(material.name.as_str()).or_insert(material);
+        }
+        self.with_obj_attributes(source, |face: &ObjFace| {
+            material_fn(face.material.and_then(|name| materials_by_name.get(name).copied()))
+        })
     }
 
     ///
-    /// Creates a cube.
+    /// Parses the .3d file and extracts the connectivity information (indices) and positions which is used to construct a mesh when the `build` method is called.
+    /// Both version 1 and 2 of the [.3d file format](crate::mesh::format_3d) are supported.
+    /// The vertex attributes stored in a version 2 file are added to the mesh with linear interpolation,
+    /// where an attribute with one component is of type `f64` and attributes with two, three or four components are of type
+    /// [Vec2](crate::mesh::math::Vec2), [Vec3](crate::mesh::math::Vec3) and [Vec4](crate::mesh::math::Vec4).
+    /// The face tags are ignored, use [with_tagged_3d](#method.with_tagged_3d) to read them.
     ///
     /// # Examples
     ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    ///     let bytes = std::fs::read("foo.3d").expect("Something went wrong reading the file");
+    ///     let mesh = tri_mesh::mesh_builder::MeshBuilder::<()>::new().with_3d(&bytes)?.build()?;
+    /// #    Ok(())
+    /// # }
     /// ```
-    /// # use tri_mesh::mesh_builder::{MeshBuilder, Error};
-    /// #
-    /// # fn main() -> Result<(), Box<Error>> {
-    /// let mesh = MeshBuilder::<()>::new().cube().build()?;
     ///
-    /// assert_eq!(mesh.num_faces(), 12);
-    /// assert_eq!(mesh.num_vertices(), 8);
+    /// # Errors
     ///
-    /// #   mesh.is_valid().unwrap();
-    /// #   Ok(())
-    /// # }
-    /// ```
+    /// If the bytes are not a valid .3d file of a supported version, an [InvalidFile](crate::mesh_builder::Error::InvalidFile)
+    /// or a [Bincode](crate::mesh_builder::Error::Bincode) error is returned.
     ///
-    pub fn cube(self) -> Self
+    #[cfg(feature = "3d-io")]
+    pub fn with_3d(self, bytes: &[u8]) -> Result<Self, Error>
     {
-        self.with_positions(vec![
-            1.0, -1.0, -1.0,
-            1.0, -1.0, 1.0,
-            -1.0, -1.0, 1.0,
-            -1.0, -1.0, -1.0,
-            1.0, 1.0, -1.0,
-            1.0, 1.0, 1.0,
-            -1.0, 1.0, 1.0,
-            -1.0, 1.0, -1.0
-        ]).with_indices(vec![
-            0, 1, 2,
-            0, 2, 3,
-            4, 7, 6,
-            4, 6, 5,
-            0, 4, 5,
-            0, 5, 1,
-            1, 5, 6,
-            1, 6, 2,
-            2, 6, 7,
-            2, 7, 3,
-            4, 0, 3,
-            4, 3, 7]
-        )
+        Ok(self.with_decoded_3d(bytes)?.0)
     }
 
-    /// Creates a cube where each face is not connected to any other face.
-    pub fn unconnected_cube(self) -> Self
+    ///
+    /// Parses the .3d file like [with_3d](#method.with_3d) and in addition reads the face tags
+    /// written by [parse_as_tagged_3d](crate::mesh::Mesh::parse_as_tagged_3d).
+    /// If the file does not contain any tags, the faces are given the default tag.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are not a valid .3d file of a supported version or the tags cannot be deserialized into the tag type,
+    /// an [InvalidFile](crate::mesh_builder::Error::InvalidFile) or a [Bincode](crate::mesh_builder::Error::Bincode) error is returned.
+    ///
+    #[cfg(feature = "3d-io")]
+    pub fn with_tagged_3d(self, bytes: &[u8]) -> Result<Self, Error>
+        where T: serde::de::DeserializeOwned
     {
-        self.with_positions(vec![
-            1.0, 1.0, -1.0,
+        let (mut builder, tags) = self.with_decoded_3d(bytes)?;
+        if let Some(tags) = tags {
+            let tags: Vec<T> = bincode::deserialize(&tags)?;
+            let num_faces = builder.indices.as_ref().map(|indices| indices.len() / 3).unwrap_or(0);
+            if tags.len() != num_faces {
+                return Err(Error::InvalidFile { message: format!("The 3d file contains {} tags, but {} faces", tags.len(), num_faces) });
+            }
+            builder.tags = Some(tags);
+        }
+        Ok(builder)
+    }
+
+    /// Reads the positions, indices and vertex attributes of the .3d file into the builder and returns the serialized tags.
+    #[cfg(feature = "3d-io")]
+    fn with_decoded_3d(mut self, bytes: &[u8]) -> Result<(Self, Option<Vec<u8>>), Error>
+    {
+        use crate::mesh::math::{vec2, vec3, vec4, Vec2, Vec3, Vec4};
+        let decoded = crate::mesh::format_3d::decode(bytes)?;
+        for crate::mesh::format_3d::DecodedAttribute { name, components, values } in decoded.vertex_attributes {
+            self = match components {
+                1 => self.with_partial_vertex_attribute(&name, values.into_iter().map(|v| v.map(|v| v[0])).collect(),
+                    |values: &[(f64, f64)]| values.iter().map(|(value, weight)| weight * value).sum()),
+                2 => self.with_partial_vertex_attribute(&name, values.into_iter().map(|v| v.map(|v| vec2(v[0], v[1]))).collect(),
+                    |values: &[(Vec2, f64)]| values.iter().map(|(value, weight)| *weight * *value).sum()),
+                3 => self.with_partial_vertex_attribute(&name, values.into_iter().map(|v| v.map(|v| vec3(v[0], v[1], v[2]))).collect(),
+                    |values: &[(Vec3, f64)]| values.iter().map(|(value, weight)| *weight * *value).sum()),
+                _ => self.with_partial_vertex_attribute(&name, values.into_iter().map(|v| v.map(|v| vec4(v[0], v[1], v[2], v[3]))).collect(),
+                    |values: &[(Vec4, f64)]| values.iter().map(|(value, weight)| *weight * *value).sum())
+            };
+        }
+        self.positions = Some(decoded.positions);
+        self.indices = Some(decoded.indices);
+        Ok((self, decoded.tags))
+    }
+
+    ///
+    /// Parses the .stl file and extracts the connectivity information (indices) and positions which is used to construct a mesh when the `build` method is called.
+    /// Both the ASCII and the binary .stl format is supported and the format is detected automatically.
+    /// Since an .stl file stores each triangle separately, corners with exactly the same position are welded into one vertex such that the mesh is connected.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    ///     let bytes = std::fs::read("foo.stl").expect("Something went wrong reading the file");
+    ///     let mesh = tri_mesh::mesh_builder::MeshBuilder::<()>::new().with_stl(&bytes)?.build()?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are neither a valid ASCII nor binary .stl file, an [InvalidFile](crate::mesh_builder::Error::InvalidFile) error is returned.
+    ///
+    #[cfg(feature = "stl-io")]
+    pub fn with_stl(mut self, bytes: &[u8]) -> Result<Self, Error>
+    {
+        let corners = if is_binary_stl(bytes) { parse_binary_stl(bytes)? } else { parse_ascii_stl(bytes)? };
+
+        let mut index_map = std::collections::HashMap::new();
+        let mut positions = Vec::new();
+        let mut indices = Vec::with_capacity(corners.len());
+        for corner in corners {
+            // Adding zero turns -0.0 into 0.0, so both are welded
+            let key = [(corner[0] + 0.0).to_bits(), (corner[1] + 0.0).to_bits(), (corner[2] + 0.0).to_bits()];
+            let index = *index_map.entry(key).or_insert_with(|| {
+                positions.extend_from_slice(&corner);
+                positions.len() as u32 / 3 - 1
+            });
+            indices.push(index);
+        }
+
+        self.positions = Some(positions);
+        self.indices = Some(indices);
+        Ok(self)
+    }
+
+    ///
+    /// Parses the .ply file and extracts the connectivity information (indices) and positions which is used to construct a mesh when the `build` method is called.
+    /// The ASCII as well as the binary little and big endian .ply formats are supported.
+    /// Faces with more than three corners are triangulated as a fan.
+    ///
+    /// If the vertices have normals (properties `nx`, `ny` and `nz`), they are stored in the `"normal"` [vertex attribute](crate::mesh::Mesh#vertex-attributes)
+    /// of type [Vec3](crate::mesh::math::Vec3), and if the vertices have colors (properties `red`, `green`, `blue` and optionally `alpha`),
+    /// they are stored in the `"color"` vertex attribute of type [Vec4](crate::mesh::math::Vec4) with components between zero and one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    ///     let bytes = std::fs::read("foo.ply").expect("Something went wrong reading the file");
+    ///     let mesh = tri_mesh::mesh_builder::MeshBuilder::<()>::new().with_ply(&bytes)?.build()?;
+    ///     let vertex_id = mesh.vertex_iter().next().unwrap();
+    ///     let color = mesh.vertex_attribute::<Vec4>("color", vertex_id);
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are not a valid .ply file, an [InvalidFile](crate::mesh_builder::Error::InvalidFile) error is returned.
+    ///
+    #[cfg(feature = "ply-io")]
+    pub fn with_ply(self, bytes: &[u8]) -> Result<Self, Error>
+    {
+        self.with_ply_and_face_property(bytes, None, |_| T::default())
+    }
+
+    ///
+    /// Parses the .ply file like [with_ply](#method.with_ply) and in addition maps the value of the given face property (for example an integer label)
+    /// to the tag of each face using the `tag_fn` function.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    ///     let bytes = std::fs::read("foo.ply").expect("Something went wrong reading the file");
+    ///     let mesh = tri_mesh::mesh_builder::MeshBuilder::<u32>::new().with_ply_face_property(&bytes, "label", |label| label as u32)?.build()?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are not a valid .ply file or if the faces do not have the given property, an [InvalidFile](crate::mesh_builder::Error::InvalidFile) error is returned.
+    ///
+    #[cfg(feature = "ply-io")]
+    pub fn with_ply_face_property(self, bytes: &[u8], property: &str, tag_fn: impl FnMut(f64) -> T) -> Result<Self, Error>
+    {
+        self.with_ply_and_face_property(bytes, Some(property), tag_fn)
+    }
+
+    #[cfg(feature = "ply-io")]
+    fn with_ply_and_face_property(mut self, bytes: &[u8], property: Option<&str>, tag_fn: impl FnMut(f64) -> T) -> Result<Self, Error>
+    {
+        let ply = ply::parse(bytes, property)?;
+        if let Some(normals) = ply.normals {
+            self = self.with_vertex_attribute("normal", normals, |values: &[(Vec3, f64)]|
+                values.iter().map(|(value, weight)| *weight * *value).sum::<Vec3>().normalize());
+        }
+        if let Some(colors) = ply.colors {
+            self = self.with_vertex_attribute("color", colors, |values: &[(Vec4, f64)]|
+                values.iter().map(|(value, weight)| *weight * *value).sum());
+        }
+        if let Some(face_values) = ply.face_values {
+            self.tags = Some(face_values.into_iter().map(tag_fn).collect());
+        }
+        self.positions = Some(ply.positions);
+        self.indices = Some(ply.indices);
+        Ok(self)
+    }
+
+    ///
+    /// Reads the .gltf or .glb (glTF 2.0) file at the given path and extracts the connectivity information (indices) and positions
+    /// of the first mesh in the file which is used to construct a mesh when the `build` method is called.
+    /// All triangle primitives of the mesh are added, while primitives with other modes (points, lines, ..) are ignored.
+    /// The buffers can be embedded in the file, stored in the binary chunk of a .glb file or stored in separate files relative to the given path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    ///     let mesh = tri_mesh::mesh_builder::MeshBuilder::<()>::new().with_gltf("foo.glb")?.build()?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read, an [IO](crate::mesh_builder::Error::IO) error is returned and if it is not a valid glTF file
+    /// or does not contain any triangles, an [InvalidFile](crate::mesh_builder::Error::InvalidFile) error is returned.
+    ///
+    #[cfg(feature = "gltf-io")]
+    pub fn with_gltf(self, path: impl AsRef<std::path::Path>) -> Result<Self, Error>
+    {
+        self.with_gltf_multiprimitive(path, |_| T::default())
+    }
+
+    ///
+    /// Reads the .gltf or .glb file like [with_gltf](#method.with_gltf) and in addition sets the tag of each face
+    /// to the value returned by `primitive_fn` for the index of the primitive that contains the face.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    ///     let mesh = tri_mesh::mesh_builder::MeshBuilder::<usize>::new().with_gltf_multiprimitive("foo.gltf", |primitive| primitive)?.build()?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "gltf-io")]
+    pub fn with_gltf_multiprimitive(mut self, path: impl AsRef<std::path::Path>, primitive_fn: impl FnMut(usize) -> T) -> Result<Self, Error>
+    {
+        let gltf = gltf::parse(path.as_ref())?;
+        self.positions = Some(gltf.positions);
+        self.indices = Some(gltf.indices);
+        self.tags = Some(gltf.primitives.into_iter().map(primitive_fn).collect());
+        Ok(self)
+    }
+
+    ///
+    /// Parses the .off file and extracts the connectivity information (indices) and positions which is used to construct a mesh when the `build` method is called.
+    /// Faces with more than three corners are triangulated as a fan.
+    /// Additional data, like normals and colors, is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    ///     let source = std::fs::read_to_string("foo.off").expect("Something went wrong reading the file");
+    ///     let mesh = tri_mesh::mesh_builder::MeshBuilder::<()>::new().with_off(&source)?.build()?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the source is not a valid .off file, an [InvalidFile](crate::mesh_builder::Error::InvalidFile) error is returned.
+    ///
+    #[cfg(feature = "off-io")]
+    pub fn with_off(mut self, source: &str) -> Result<Self, Error>
+    {
+        let (positions, indices) = off::parse(source)?;
+        self.positions = Some(positions);
+        self.indices = Some(indices);
+        Ok(self)
+    }
+
+    ///
+    /// Parses the legacy .vtk file and extracts the connectivity information (indices) and positions which is used to construct a mesh when the `build` method is called.
+    /// Only ASCII files with a polydata dataset are supported. Polygons are triangulated as a fan and triangle strips are split into triangles.
+    /// Each scalar field of the points with a single component is stored in a [vertex attribute](crate::mesh::Mesh#vertex-attributes) of type `f64`
+    /// with the same name as the field.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    ///     let source = std::fs::read_to_string("foo.vtk").expect("Something went wrong reading the file");
+    ///     let mesh = tri_mesh::mesh_builder::MeshBuilder::<()>::new().with_vtk(&source)?.build()?;
+    ///     let vertex_id = mesh.vertex_iter().next().unwrap();
+    ///     let curvature = mesh.vertex_attribute::<f64>("curvature", vertex_id);
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the source is not a valid ASCII legacy .vtk file with a polydata dataset, an [InvalidFile](crate::mesh_builder::Error::InvalidFile) error is returned.
+    ///
+    #[cfg(feature = "vtk-io")]
+    pub fn with_vtk(mut self, source: &str) -> Result<Self, Error>
+    {
+        let vtk = vtk::parse(source)?;
+        for (name, values) in vtk.point_scalars {
+            self = self.with_vertex_attribute(&name, values, |values: &[(f64, f64)]| values.iter().map(|(value, weight)| weight * value).sum());
+        }
+        self.positions = Some(vtk.positions);
+        self.indices = Some(vtk.indices);
+        Ok(self)
+    }
+
+    ///
+    /// Builds the mesh. Returns the mesh if the definition is valid and otherwise an error.
+    /// Use [build_with_repair](#method.build_with_repair) to build a mesh from input which is not valid.
+    ///
+    /// **Note:** The faces must have consistent orientations, i.e. two neighbouring faces must use their shared edge in opposite directions.
+    /// In version 0.5 and earlier, faces with inconsistent orientations were accepted, now they give a [NonManifoldEdge](crate::mesh_builder::Error::NonManifoldEdge) error.
+    /// For such input, [build_with_repair](#method.build_with_repair) removes the faces which do not match the orientation of the faces before them
+    /// and lists them in [non_manifold_faces](crate::mesh_builder::RepairReport::non_manifold_faces).
+    ///
+    /// # Errors
+    ///
+    /// If a parameter given to the builder is invalid, an [InvalidParameter](crate::mesh_builder::Error::InvalidParameter) error is returned.
+    ///
+    /// If no positions are specified, [NoPositionsSpecified](crate::mesh_builder::Error::NoPositionsSpecified) error is returned.
+    ///
+    /// If the number of positions is not a multiple of three, an [InvalidPositions](crate::mesh_builder::Error::InvalidPositions) error is returned.
+    ///
+    /// If the number of indices is not a multiple of three or an index is out of range, an [InvalidIndices](crate::mesh_builder::Error::InvalidIndices) error is returned.
+    ///
+    /// If the tags are specified, but the number of tags is not equal to the number of faces, an [InvalidTags](crate::mesh_builder::Error::InvalidTags) error is returned.
+    ///
+    /// If a face refers to the same vertex more than once or collapses because two of its vertices are [welded](#method.with_welding),
+    /// a [DegenerateFace](crate::mesh_builder::Error::DegenerateFace) error is returned.
+    ///
+    /// If an edge is shared by more than two faces or by two faces which use it in the same direction,
+    /// a [NonManifoldEdge](crate::mesh_builder::Error::NonManifoldEdge) error is returned.
+    ///
+    /// If the faces around a vertex are not connected to each other through their edges, for example two triangles which only share a vertex,
+    /// a [NonManifoldVertex](crate::mesh_builder::Error::NonManifoldVertex) error is returned.
+    ///
+    pub fn build(self) -> Result<Mesh<T>, Error>
+    {
+        Ok(self.build_internal(false)?.0)
+    }
+
+    ///
+    /// Builds the mesh like [build](#method.build), except that invalid input is repaired instead of returning an error.
+    /// Faces which refer to a vertex which does not exist, refer to the same vertex more than once, collapse because of welding, share an edge with two other faces
+    /// or use an edge in the same direction as another face are removed, where the first faces are kept in case of a non-manifold edge.
+    /// Then the faces around a non-manifold vertex which are not connected to the first face around the vertex through edges are removed
+    /// and afterwards vertices which are not part of any face are removed.
+    /// Superfluous positions, indices and tags are removed and missing tags are set to the default tag.
+    /// Returns the mesh together with a report of the changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::mesh_builder::{MeshBuilder, Error};
+    /// #
+    /// # fn main() -> Result<(), Box<Error>> {
+    /// let positions: Vec<f64> = vec![0.0, 0.0, 0.0,  1.0, 0.0, 0.0,  0.0, 1.0, 0.0,  0.0, -1.0, 0.0,  0.0, 0.0, 1.0];
+    /// // Four faces share the edge between vertex 0 and 1 and the last face refers to vertex 1 twice
+    /// let indices: Vec<u32> = vec![0, 1, 2,  1, 0, 3,  0, 1, 4,  4, 1, 0,  1, 1, 2];
+    ///
+    /// let (mesh, report) = MeshBuilder::<()>::new().with_indices(indices).with_positions(positions).build_with_repair()?;
+    ///
+    /// assert_eq!(mesh.num_faces(), 2);
+    /// assert_eq!(report.non_manifold_faces, vec![2, 3]);
+    /// assert_eq!(report.degenerate_faces, vec![4]);
+    /// assert_eq!(report.unreferenced_vertices, vec![4]);
+    ///
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If a parameter given to the builder is invalid, an [InvalidParameter](crate::mesh_builder::Error::InvalidParameter) error is returned.
+    ///
+    /// If no positions are specified, [NoPositionsSpecified](crate::mesh_builder::Error::NoPositionsSpecified) error is returned.
+    ///
+    pub fn build_with_repair(self) -> Result<(Mesh<T>, RepairReport), Error>
+    {
+        self.build_internal(true)
+    }
+
+    fn build_internal(self, repair: bool) -> Result<(Mesh<T>, RepairReport), Error>
+    {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let positions = self.positions.ok_or(
+            Error::NoPositionsSpecified {message: format!("Did you forget to specify the vertex positions?")})?;
+        let attribute_indices = if self.attributes.0.is_empty() { Vec::new() } else {
+            self.indices.clone().unwrap_or_else(|| (0..positions.len() as u32/3).collect())
+        };
+        let default_tag = self.default_tag.unwrap_or(T::default());
+        let (validated, report) = validation::validate(positions, self.indices, self.tags, default_tag, self.weld_tolerance, repair)?;
+
+        let mut mesh = Mesh::new(validated.indices, validated.tags, validated.positions);
+        if !self.attributes.0.is_empty() {
+            let vertex_ids = mesh.vertex_iter().collect::<Vec<_>>();
+            let mut face_ids = mesh.face_iter();
+            let mapping = InputMapping {
+                indices: attribute_indices,
+                vertices: validated.vertices.iter().map(|vertex| vertex.map(|vertex| vertex_ids[vertex as usize])).collect(),
+                faces: validated.kept_faces.iter().map(|kept| if *kept { face_ids.next() } else { None }).collect()
+            };
+            for initializer in self.attributes.0 {
+                initializer(&mut mesh, &mapping);
+            }
+        }
+        Ok((mesh, report))
+    }
+
+    ///
+    /// Creates a cube.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::mesh_builder::{MeshBuilder, Error};
+    /// #
+    /// # fn main() -> Result<(), Box<Error>> {
+    /// let mesh = MeshBuilder::<()>::new().cube().build()?;
+    ///
+    /// assert_eq!(mesh.num_faces(), 12);
+    /// assert_eq!(mesh.num_vertices(), 8);
+    ///
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn cube(self) -> Self
+    {
+        self.with_positions(vec![
+            1.0, -1.0, -1.0,
+            1.0, -1.0, 1.0,
+            -1.0, -1.0, 1.0,
+            -1.0, -1.0, -1.0,
+            1.0, 1.0, -1.0,
+            1.0, 1.0, 1.0,
+            -1.0, 1.0, 1.0,
+            -1.0, 1.0, -1.0
+        ]).with_indices(vec![
+            0, 1, 2,
+            0, 2, 3,
+            4, 7, 6,
+            4, 6, 5,
+            0, 4, 5,
+            0, 5, 1,
+            1, 5, 6,
+            1, 6, 2,
+            2, 6, 7,
+            2, 7, 3,
+            4, 0, 3,
+            4, 3, 7]
+        )
+    }
+
+    /// Creates a cube where each face is not connected to any other face.
+    pub fn unconnected_cube(self) -> Self
+    {
+        self.with_positions(vec![
+            1.0, 1.0, -1.0,
             -1.0, 1.0, -1.0,
             1.0, 1.0, 1.0,
             -1.0, 1.0, 1.0,
@@ -412,27 +1293,17 @@ impl<T: Clone + Default> MeshBuilder<T> {
     /// Creates an icosahedron, i.e. a discretised sphere.
     pub fn icosahedron(self) -> Self
     {
-        let x = 0.525731112119133606;
-        let z = 0.850650808352039932;
-
-        self.with_positions(vec!(
-            -x, 0.0, z, x, 0.0, z, -x, 0.0, -z, x, 0.0, -z,
-            0.0, z, x, 0.0, z, -x, 0.0, -z, x, 0.0, -z, -x,
-            z, x, 0.0, -z, x, 0.0, z, -x, 0.0, -z, -x, 0.0
-        )).with_indices(vec!(
-            0, 1, 4, 0, 4, 9, 9, 4, 5, 4, 8, 5, 4, 1, 8,
-            8, 1, 10, 8, 10, 3, 5, 8, 3, 5, 3, 2, 2, 3, 7,
-            7, 3, 10, 7, 10, 6, 7, 6, 11, 11, 6, 0, 0, 6, 1,
-            6, 10, 1, 9, 11, 0, 9, 2, 11, 9, 5, 2, 7, 11, 2
-        ))
+        self.with_positions(ICOSAHEDRON_POSITIONS.to_vec()).with_indices(ICOSAHEDRON_INDICES.to_vec())
     }
 
     /// Creates a cylinder with the x-direction as axis, length 1 and radius 1.
-    /// `x_subdivisions` defines the number of subdivisions in the x-direction
-    /// and `angle_subdivisions` defines the number of circular subdivisions.
+    /// `x_subdivisions` (at least 2) defines the number of subdivisions in the x-direction
+    /// and `angle_subdivisions` (at least 3) defines the number of circular subdivisions.
     pub fn cylinder(self, x_subdivisions: usize, angle_subdivisions: usize) -> Self
     {
-        if x_subdivisions < 2 || angle_subdivisions < 3 { return self; }
+        if let Err(error) = check_minimums(&[("x_subdivisions", x_subdivisions, 2), ("angle_subdivisions", angle_subdivisions, 3)]) {
+            return self.with_error(error);
+        }
         let mut positions = Vec::new();
         for i in 0..x_subdivisions + 1 {
             let x = i as f64 / x_subdivisions as f64;
@@ -445,19 +1316,7 @@ impl<T: Clone + Default> MeshBuilder<T> {
             }
         }
 
-        let mut indices = Vec::new();
-        for i in 0..x_subdivisions as u32 {
-            for j in 0..angle_subdivisions as u32 {
-                indices.push(i * angle_subdivisions as u32 + j);
-                indices.push(i * angle_subdivisions as u32 + (j + 1) % angle_subdivisions as u32);
-                indices.push((i + 1) * angle_subdivisions as u32 + (j + 1) % angle_subdivisions as u32);
-
-                indices.push(i * angle_subdivisions as u32 + j);
-                indices.push((i + 1) * angle_subdivisions as u32 + (j + 1) % angle_subdivisions as u32);
-                indices.push((i + 1) * angle_subdivisions as u32 + j);
-            }
-        }
-        self.with_positions(positions).with_indices(indices)
+        self.with_positions(positions).with_indices(grid_indices(angle_subdivisions, x_subdivisions + 1, true, false))
     }
 
     /// Creates a triangle in `x = [-3, 3]`, `y = [-1, 2]` and `z = 0` which covers a square in `x = [-1, 1]`, `y = [-1, 1]` and `z = 0`.
@@ -497,15 +1356,830 @@ impl<T: Clone + Default> MeshBuilder<T> {
         ];
         self.with_indices(plane_indices).with_positions(plane_positions)
     }
-}
 
+    /// Creates a sphere with radius 1 made of rings of vertices, with the x-direction as axis.
+    /// `latitude_subdivisions` (at least 2) defines the number of subdivisions from pole to pole
+    /// and `longitude_subdivisions` (at least 3) defines the number of circular subdivisions.
+    pub fn uv_sphere(self, latitude_subdivisions: usize, longitude_subdivisions: usize) -> Self
+    {
+        if let Err(error) = check_minimums(&[("latitude_subdivisions", latitude_subdivisions, 2), ("longitude_subdivisions", longitude_subdivisions, 3)]) {
+            return self.with_error(error);
+        }
+        let profile = (0..latitude_subdivisions + 1).map(|i| {
+            let angle = std::f64::consts::PI * i as f64 / latitude_subdivisions as f64;
+            (-angle.cos(), angle.sin())
+        }).collect::<Vec<_>>();
+        self.surface_of_revolution(&profile, longitude_subdivisions)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Creates a sphere with radius 1 by subdividing each triangle of an [icosahedron](#method.icosahedron) into four triangles
+    /// `subdivisions` times and projecting the vertices onto the sphere, so the triangles have almost the same size.
+    pub fn geodesic_sphere(self, subdivisions: usize) -> Self
+    {
+        use crate::mesh::math::{vec3, InnerSpace};
+        let mut positions = ICOSAHEDRON_POSITIONS.chunks(3).map(|p| vec3(p[0], p[1], p[2])).collect::<Vec<_>>();
+        let mut indices = ICOSAHEDRON_INDICES.to_vec();
+        for _ in 0..subdivisions {
+            let mut midpoints = std::collections::HashMap::new();
+            let mut midpoint = |a: u32, b: u32| *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                positions.push((positions[a as usize] + positions[b as usize]).normalize());
+                positions.len() as u32 - 1
+            });
+            indices = indices.chunks(3).flat_map(|face| {
+                let (ab, bc, ca) = (midpoint(face[0], face[1]), midpoint(face[1], face[2]), midpoint(face[2], face[0]));
+                vec![face[0], ab, ca,  ab, face[1], bc,  ca, bc, face[2],  ab, bc, ca]
+            }).collect();
+        }
+        self.with_positions(positions.iter().flat_map(|p| vec![p.x, p.y, p.z]).collect()).with_indices(indices)
+    }
 
-    #[test]
-    fn test_with_obj() {
+    /// Creates a torus with the x-direction as axis, where `major_radius` is the distance from the axis to the center of the tube
+    /// and `minor_radius` is the radius of the tube.
+    /// `major_subdivisions` (at least 3) defines the number of subdivisions around the axis
+    /// and `minor_subdivisions` (at least 3) defines the number of subdivisions around the tube.
+    pub fn torus(self, major_radius: f64, minor_radius: f64, major_subdivisions: usize, minor_subdivisions: usize) -> Self
+    {
+        if let Err(error) = check_minimums(&[("major_subdivisions", major_subdivisions, 3), ("minor_subdivisions", minor_subdivisions, 3)]) {
+            return self.with_error(error);
+        }
+        let mut positions = Vec::new();
+        for i in 0..major_subdivisions {
+            let major_angle = 2.0 * std::f64::consts::PI * i as f64 / major_subdivisions as f64;
+            for j in 0..minor_subdivisions {
+                let minor_angle = 2.0 * std::f64::consts::PI * j as f64 / minor_subdivisions as f64;
+                let radius = major_radius + minor_radius * minor_angle.cos();
+                positions.push(minor_radius * minor_angle.sin());
+                positions.push(radius * major_angle.cos());
+                positions.push(radius * major_angle.sin());
+            }
+        }
+
+        let vertex = |i: usize, j: usize| ((i % major_subdivisions) * minor_subdivisions + j % minor_subdivisions) as u32;
+        let mut indices = Vec::new();
+        for i in 0..major_subdivisions {
+            for j in 0..minor_subdivisions {
+                indices.extend_from_slice(&[vertex(i, j), vertex(i + 1, j), vertex(i + 1, j + 1)]);
+                indices.extend_from_slice(&[vertex(i, j), vertex(i + 1, j + 1), vertex(i, j + 1)]);
+            }
+        }
+        self.with_positions(positions).with_indices(indices)
+    }
+
+    /// Creates a closed cone with the x-direction as axis, length 1 and a base with radius 1 at `x = 0`.
+    /// `angle_subdivisions` (at least 3) defines the number of circular subdivisions.
+    pub fn cone(self, angle_subdivisions: usize) -> Self
+    {
+        self.surface_of_revolution(&[(0.0, 0.0), (0.0, 1.0), (1.0, 0.0)], angle_subdivisions)
+    }
+
+    /// Creates a capsule, i.e. a [cylinder](#method.cylinder) with the x-direction as axis, length 1 and radius 1
+    /// which is closed by a hemisphere at each end.
+    /// `angle_subdivisions` (at least 3) defines the number of circular subdivisions
+    /// and `cap_subdivisions` (at least 1) defines the number of subdivisions of each hemisphere from the pole to the cylinder.
+    pub fn capsule(self, angle_subdivisions: usize, cap_subdivisions: usize) -> Self
+    {
+        if let Err(error) = check_minimums(&[("angle_subdivisions", angle_subdivisions, 3), ("cap_subdivisions", cap_subdivisions, 1)]) {
+            return self.with_error(error);
+        }
+        let cap = (0..cap_subdivisions).map(|i| {
+            let angle = 0.5 * std::f64::consts::PI * i as f64 / cap_subdivisions as f64;
+            (angle.cos(), angle.sin())
+        }).collect::<Vec<_>>();
+        let profile = cap.iter().map(|(x, r)| (-x, *r)).chain(vec![(0.0, 1.0), (1.0, 1.0)])
+            .chain(cap.iter().rev().map(|(x, r)| (1.0 + x, *r))).collect::<Vec<_>>();
+        self.surface_of_revolution(&profile, angle_subdivisions)
+    }
+
+    /// Creates a disk with radius 1 in the plane `z = 0`.
+    /// `radial_subdivisions` (at least 1) defines the number of subdivisions from the center to the boundary
+    /// and `angle_subdivisions` (at least 3) defines the number of circular subdivisions.
+    pub fn disk(self, radial_subdivisions: usize, angle_subdivisions: usize) -> Self
+    {
+        if let Err(error) = check_minimums(&[("radial_subdivisions", radial_subdivisions, 1), ("angle_subdivisions", angle_subdivisions, 3)]) {
+            return self.with_error(error);
+        }
+        let mut positions = vec![0.0, 0.0, 0.0];
+        for i in 1..radial_subdivisions + 1 {
+            let radius = i as f64 / radial_subdivisions as f64;
+            for j in 0..angle_subdivisions {
+                let angle = 2.0 * std::f64::consts::PI * j as f64 / angle_subdivisions as f64;
+                positions.extend_from_slice(&[radius * angle.cos(), radius * angle.sin(), 0.0]);
+            }
+        }
+
+        let vertex = |i: usize, j: usize| (1 + (i - 1) * angle_subdivisions + j % angle_subdivisions) as u32;
+        let mut indices = Vec::new();
+        for j in 0..angle_subdivisions {
+            indices.extend_from_slice(&[0, vertex(1, j), vertex(1, j + 1)]);
+        }
+        for i in 1..radial_subdivisions {
+            for j in 0..angle_subdivisions {
+                indices.extend_from_slice(&[vertex(i, j), vertex(i + 1, j), vertex(i + 1, j + 1)]);
+                indices.extend_from_slice(&[vertex(i, j), vertex(i + 1, j + 1), vertex(i, j + 1)]);
+            }
+        }
+        self.with_positions(positions).with_indices(indices)
+    }
+
+    /// Creates a grid of squares, each divided into two triangles, in `x = [-1, 1]`, `y = [-1, 1]` and `z = 0`.
+    /// `x_subdivisions` and `y_subdivisions` (at least 1) defines the number of squares in the x- and y-direction.
+    pub fn grid(self, x_subdivisions: usize, y_subdivisions: usize) -> Self
+    {
+        if let Err(error) = check_minimums(&[("x_subdivisions", x_subdivisions, 1), ("y_subdivisions", y_subdivisions, 1)]) {
+            return self.with_error(error);
+        }
+        let mut positions = Vec::new();
+        for j in 0..y_subdivisions + 1 {
+            for i in 0..x_subdivisions + 1 {
+                positions.push(2.0 * i as f64 / x_subdivisions as f64 - 1.0);
+                positions.push(2.0 * j as f64 / y_subdivisions as f64 - 1.0);
+                positions.push(0.0);
+            }
+        }
+
+        let vertex = |i: usize, j: usize| (j * (x_subdivisions + 1) + i) as u32;
+        let mut indices = Vec::new();
+        for j in 0..y_subdivisions {
+            for i in 0..x_subdivisions {
+                indices.extend_from_slice(&[vertex(i, j), vertex(i + 1, j), vertex(i, j + 1)]);
+                indices.extend_from_slice(&[vertex(i, j + 1), vertex(i + 1, j), vertex(i + 1, j + 1)]);
+            }
+        }
+        self.with_positions(positions).with_indices(indices)
+    }
+
+    ///
+    /// Creates a surface from the function `surface` which maps the parameters `(u, v)` in `[0, 1] x [0, 1]` to a position.
+    /// The parameter domain is divided into a grid with `u_subdivisions` times `v_subdivisions` cells (each at least 1), each divided into two triangles,
+    /// and the faces are oriented such that the normal points in the direction of the cross product of the derivatives with respect to `u` and `v`.
+    /// If `wrap_u` is true, the surface is assumed to be periodic in `u`, i.e. the positions at `u = 0` and `u = 1` are the same,
+    /// so the positions at `u = 1` are not evaluated and the surface is connected across the seam instead (and the same for `wrap_v`).
+    /// Wrapping requires at least 3 subdivisions in that direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// // A torus is periodic in both directions, so the mesh is closed
+    /// let mesh = MeshBuilder::<()>::new().parametric_surface(32, 16, true, true, |u, v| {
+    ///     let (u, v) = (2.0 * std::f64::consts::PI * u, 2.0 * std::f64::consts::PI * v);
+    ///     vec3((2.0 + v.cos()) * u.cos(), (2.0 + v.cos()) * u.sin(), v.sin())
+    /// }).build()?;
+    ///
+    /// assert_eq!(mesh.num_vertices(), 32 * 16);
+    /// assert!(mesh.is_closed());
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn parametric_surface(self, u_subdivisions: usize, v_subdivisions: usize, wrap_u: bool, wrap_v: bool,
+                              surface: impl Fn(f64, f64) -> crate::mesh::math::Vec3) -> Self
+    {
+        let minimum = |wrap: bool| if wrap { 3 } else { 1 };
+        if let Err(error) = check_minimums(&[("u_subdivisions", u_subdivisions, minimum(wrap_u)), ("v_subdivisions", v_subdivisions, minimum(wrap_v))]) {
+            return self.with_error(error);
+        }
+        let u_count = if wrap_u { u_subdivisions } else { u_subdivisions + 1 };
+        let v_count = if wrap_v { v_subdivisions } else { v_subdivisions + 1 };
+        let mut positions = Vec::with_capacity(3 * u_count * v_count);
+        for j in 0..v_count {
+            for i in 0..u_count {
+                let position = surface(i as f64 / u_subdivisions as f64, j as f64 / v_subdivisions as f64);
+                positions.extend_from_slice(&[position.x, position.y, position.z]);
+            }
+        }
+        self.with_positions(positions).with_indices(grid_indices(u_count, v_count, wrap_u, wrap_v))
+    }
+
+    ///
+    /// Creates a terrain from a heightfield, where `heights[row][column]` is the height (y-coordinate) of the vertex
+    /// at `x = column * spacing` and `z = row * spacing`. The faces are oriented such that the normals point upwards.
+    /// If the rows have different lengths, only the columns which all rows have are used.
+    /// At least two rows and two columns are required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// let heights = vec![vec![0.0, 0.5, 0.0],
+    ///                    vec![0.5, 1.0, 0.5],
+    ///                    vec![0.0, 0.5, 0.0]];
+    /// let mesh = MeshBuilder::<()>::new().heightfield(&heights, 0.5).build()?;
+    ///
+    /// assert_eq!(mesh.num_vertices(), 9);
+    /// assert_eq!(mesh.num_faces(), 8);
+    /// assert!(mesh.face_iter().all(|face_id| mesh.face_normal(face_id).y > 0.0));
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn heightfield(self, heights: &[Vec<f64>], spacing: f64) -> Self
+    {
+        let columns = heights.iter().map(|row| row.len()).min().unwrap_or(0);
+        if let Err(error) = check_minimums(&[("The number of rows of heights", heights.len(), 2), ("The number of columns of heights", columns, 2)]) {
+            return self.with_error(error);
+        }
+        let mut positions = Vec::with_capacity(3 * columns * heights.len());
+        for (row, row_heights) in heights.iter().enumerate() {
+            for (column, height) in row_heights[..columns].iter().enumerate() {
+                positions.extend_from_slice(&[column as f64 * spacing, *height, row as f64 * spacing]);
+            }
+        }
+        // The cross product of the x- and z-direction points downwards, so the grid is flipped
+        let mut indices = grid_indices(columns, heights.len(), false, false);
+        indices.chunks_mut(3).for_each(|face| face.swap(1, 2));
+        self.with_positions(positions).with_indices(indices)
+    }
+
+    ///
+    /// Creates the convex hull of the points, i.e. the smallest convex closed mesh which contains all of the points.
+    /// Only the points which are corners of the hull are used as vertices, so points which are duplicated or on the faces or edges of the hull are left out.
+    /// At least four points which are not in the same plane are required. See also [Mesh::convex_hull](crate::mesh::Mesh::convex_hull).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// // The corners of a cube and its center
+    /// let points = vec![vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), vec3(1.0, 1.0, 0.0),
+    ///                   vec3(0.0, 0.0, 1.0), vec3(1.0, 0.0, 1.0), vec3(0.0, 1.0, 1.0), vec3(1.0, 1.0, 1.0), vec3(0.5, 0.5, 0.5)];
+    /// let mesh = MeshBuilder::<()>::new().convex_hull(&points)?.build()?;
+    ///
+    /// assert_eq!(mesh.num_vertices(), 8);
+    /// assert!(mesh.is_closed());
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If there are not four points which are not in the same plane, an [InvalidPositions](crate::mesh_builder::Error::InvalidPositions) error is returned.
+    ///
+    pub fn convex_hull(self, points: &[crate::mesh::math::Vec3]) -> Result<Self, Error>
+    {
+        let (positions, indices) = crate::mesh::convex_hull::quickhull(points).ok_or_else(|| Error::InvalidPositions {
+            message: format!("Cannot create a convex hull of {} points which are all in the same plane", points.len())
+        })?;
+        Ok(self.with_positions(positions.iter().flat_map(|p| vec![p.x, p.y, p.z]).collect()).with_indices(indices))
+    }
+
+    ///
+    /// Creates the isosurface where the scalar field `field` equals `iso_value`, for example the surface of a signed distance function when the iso value is zero.
+    /// The field is sampled at the points of a grid in the box from `min` to `max`, with `subdivisions` (each at least 1) cells in the x-, y- and z-direction,
+    /// and the points where the field is below the iso value are inside.
+    /// See [isosurface_from_grid](#method.isosurface_from_grid) for the details of the construction and the cases where [build](#method.build) returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// // The signed distance function of a sphere with radius 1
+    /// let mesh = MeshBuilder::<()>::new().isosurface(vec3(-1.5, -1.5, -1.5), vec3(1.5, 1.5, 1.5), [8, 8, 8], 0.0,
+    ///                                                |p| p.magnitude() - 1.0).build()?;
+    ///
+    /// assert!(mesh.is_closed());
+    /// assert!(mesh.vertex_iter().all(|vertex_id| (mesh.vertex_position(vertex_id).magnitude() - 1.0).abs() < 0.1));
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn isosurface(self, min: crate::mesh::math::Vec3, max: crate::mesh::math::Vec3, subdivisions: [usize; 3], iso_value: f64,
+                      field: impl Fn(crate::mesh::math::Vec3) -> f64) -> Self
+    {
+        use crate::mesh::math::Vec3;
+        if let Err(error) = check_minimums(&[("subdivisions[0]", subdivisions[0], 1), ("subdivisions[1]", subdivisions[1], 1), ("subdivisions[2]", subdivisions[2], 1)]) {
+            return self.with_error(error);
+        }
+        let size = [subdivisions[0] + 1, subdivisions[1] + 1, subdivisions[2] + 1];
+        let spacing = Vec3::new((max.x - min.x) / subdivisions[0] as f64, (max.y - min.y) / subdivisions[1] as f64, (max.z - min.z) / subdivisions[2] as f64);
+        let mut values = Vec::with_capacity(size[0] * size[1] * size[2]);
+        for z in 0..size[2] {
+            for y in 0..size[1] {
+                for x in 0..size[0] {
+                    values.push(field(min + Vec3::new(x as f64 * spacing.x, y as f64 * spacing.y, z as f64 * spacing.z)));
+                }
+            }
+        }
+        self.with_isosurface(&values, size, min, spacing, iso_value)
+    }
+
+    ///
+    /// Creates the isosurface where the scalar field given on a grid equals `iso_value`, for example from a voxel grid of densities.
+    /// `values[x + size[0] * (y + size[1] * z)]` is the value at the point `origin + spacing * (x, y, z)` and the points where the value is below
+    /// the iso value are inside, so negate the values if higher values are inside.
+    ///
+    /// The surface is extracted by marching tetrahedra, where each cube of the grid is divided into six tetrahedra, which unlike marching cubes
+    /// has no ambiguous cases. A vertex is created for each edge of the grid which the surface crosses and is shared by all faces crossing that edge,
+    /// so the result is a closed manifold mesh with the faces oriented towards the outside. A surface which reaches the boundary of the grid
+    /// is closed half a cell outside the grid. The vertices are kept at least 5% of the edge length away from the grid points, so no faces are degenerate,
+    /// even if some values are exactly the iso value. Small faces can be removed with [with_welding](#method.with_welding),
+    /// which merges the vertices closer than a tolerance.
+    ///
+    /// If a dimension of the grid is zero, the number of values does not match the size of the grid, a value or the iso value is not finite
+    /// or the field does not cross the iso value anywhere, [build](#method.build) returns an [InvalidParameter](crate::mesh_builder::Error::InvalidParameter) error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// // A density grid with two separate solid voxels
+    /// let mut densities = vec![0.0; 5 * 3 * 3];
+    /// densities[1 + 5 * (1 + 3 * 1)] = 1.0;
+    /// densities[3 + 5 * (1 + 3 * 1)] = 1.0;
+    /// let values = densities.iter().map(|density| -density).collect::<Vec<_>>();
+    /// let mesh = MeshBuilder::<()>::new().isosurface_from_grid(&values, [5, 3, 3], vec3(0.0, 0.0, 0.0), 1.0, -0.5).build()?;
+    ///
+    /// assert!(mesh.is_closed());
+    /// assert_eq!(mesh.connected_components().len(), 2);
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn isosurface_from_grid(self, values: &[f64], size: [usize; 3], origin: crate::mesh::math::Vec3, spacing: f64, iso_value: f64) -> Self
+    {
+        use crate::mesh::math::Vec3;
+        if let Err(error) = check_minimums(&[("size[0]", size[0], 1), ("size[1]", size[1], 1), ("size[2]", size[2], 1)]) {
+            return self.with_error(error);
+        }
+        if values.len() != size[0] * size[1] * size[2] {
+            return self.with_error(Error::InvalidParameter {
+                message: format!("The number of values ({}) is not equal to the number of grid points ({})", values.len(), size[0] * size[1] * size[2])
+            });
+        }
+        self.with_isosurface(values, size, origin, Vec3::new(spacing, spacing, spacing), iso_value)
+    }
+
+    /// Extracts the isosurface from the values on the grid, see [isosurface_from_grid](#method.isosurface_from_grid).
+    fn with_isosurface(self, values: &[f64], size: [usize; 3], origin: crate::mesh::math::Vec3, spacing: crate::mesh::math::Vec3, iso_value: f64) -> Self
+    {
+        if !iso_value.is_finite() {
+            return self.with_error(Error::InvalidParameter { message: format!("The iso value ({}) is not finite", iso_value) });
+        }
+        if let Some(index) = values.iter().position(|value| !value.is_finite()) {
+            let point = [index % size[0], (index / size[0]) % size[1], index / (size[0] * size[1])];
+            return self.with_error(Error::InvalidParameter { message: format!("The value ({}) at grid point {:?} is not finite", values[index], point) });
+        }
+        let (positions, indices) = isosurface::marching_tetrahedra(values, size, origin, spacing, iso_value);
+        if indices.is_empty() {
+            return self.with_error(Error::InvalidParameter { message: format!("The field does not cross the iso value ({}) anywhere in the grid", iso_value) });
+        }
+        self.with_positions(positions).with_indices(indices)
+    }
+
+    ///
+    /// Creates the Delaunay triangulation of the points projected onto the xy-plane, i.e. the triangulation of the convex hull of the points
+    /// where no point is inside the circumcircle of a triangle, which maximizes the minimum angle of the triangles.
+    /// The points keep their z-coordinate, so for example a terrain can be created from height samples.
+    /// See [constrained_delaunay](#method.constrained_delaunay) for forcing edges into the triangulation and removing areas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// let points = vec![vec3(0.0, 0.0, 0.0), vec3(2.0, 0.0, 0.1), vec3(2.0, 2.0, 0.0), vec3(0.0, 2.0, 0.2), vec3(1.0, 1.2, 0.5)];
+    /// let mesh = MeshBuilder::<()>::new().delaunay(&points)?.build()?;
+    ///
+    /// assert_eq!(mesh.num_vertices(), 5);
+    /// assert_eq!(mesh.num_faces(), 4);
+    /// assert!(mesh.face_iter().all(|face_id| mesh.face_normal(face_id).z > 0.0));
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If there are not three points which are not on a line, an [InvalidPositions](crate::mesh_builder::Error::InvalidPositions) error is returned.
+    ///
+    pub fn delaunay(self, points: &[crate::mesh::math::Vec3]) -> Result<Self, Error>
+    {
+        let (positions, indices) = delaunay::constrained_delaunay(points, &[], &[])?;
+        Ok(self.with_positions(positions).with_indices(indices))
+    }
+
+    ///
+    /// Creates the constrained Delaunay triangulation of the points projected onto the xy-plane, which contains the `segments` between pairs of points
+    /// as edges and is otherwise as close to the [Delaunay triangulation](#method.delaunay) as possible. The points keep their z-coordinate
+    /// and the faces are oriented such that the normals point in the positive z-direction.
+    ///
+    /// If there are segments, the faces outside the segments, i.e. the faces which can be reached from the boundary of the convex hull of the points
+    /// without crossing a segment, are removed, so the segments must enclose the area to triangulate, for example the outline of a floor plan.
+    /// The faces which can be reached from one of the `holes` points without crossing a segment are also removed.
+    ///
+    /// Points with the same x- and y-coordinates as a previous point are ignored. The segments must not cross each other,
+    /// but a segment which passes through a point is split at that point. At least three points which are not on a line are required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// // An L-shaped floor plan with a square column
+    /// let points = vec![vec3(0.0, 0.0, 0.0), vec3(4.0, 0.0, 0.0), vec3(4.0, 2.0, 0.0), vec3(2.0, 2.0, 0.0), vec3(2.0, 4.0, 0.0), vec3(0.0, 4.0, 0.0),
+    ///                   vec3(0.5, 0.5, 0.0), vec3(1.0, 0.5, 0.0), vec3(1.0, 1.0, 0.0), vec3(0.5, 1.0, 0.0)];
+    /// let segments = vec![[0, 1], [1, 2], [2, 3], [3, 4], [4, 5], [5, 0],  [6, 7], [7, 8], [8, 9], [9, 6]];
+    /// let mesh = MeshBuilder::<()>::new().constrained_delaunay(&points, &segments, &[vec2(0.75, 0.75)])?.build()?;
+    ///
+    /// let area = mesh.face_iter().map(|face_id| mesh.face_area(face_id)).sum::<f64>();
+    /// assert!((area - (12.0 - 0.25)).abs() < 0.000001);
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If all points are on a line, an [InvalidPositions](crate::mesh_builder::Error::InvalidPositions) error is returned.
+    ///
+    /// If a segment refers to a point which does not exist, the segments cross each other or no faces are left after the faces outside the segments
+    /// and in the holes are removed, for example because the segments do not form closed loops, an [InvalidSegments](crate::mesh_builder::Error::InvalidSegments) error is returned.
+    ///
+    pub fn constrained_delaunay(self, points: &[crate::mesh::math::Vec3], segments: &[[u32; 2]], holes: &[crate::mesh::math::Vec2]) -> Result<Self, Error>
+    {
+        let (positions, indices) = delaunay::constrained_delaunay(points, segments, holes)?;
+        Ok(self.with_positions(positions).with_indices(indices))
+    }
+
+    ///
+    /// Creates a prism by extruding the polygon with the given outline and holes in the plane `z = 0` along `direction`,
+    /// with a copy of the polygon as cap at each end. See [sweep](#method.sweep) for extruding along a curved path.
+    /// The outline and holes can have any orientation, the faces are always oriented outwards.
+    /// The outline and each hole must have at least three points and `direction` must not be parallel to the plane `z = 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// // A square frame with a square hole
+    /// let outline = vec![vec2(-2.0, -2.0), vec2(2.0, -2.0), vec2(2.0, 2.0), vec2(-2.0, 2.0)];
+    /// let hole = vec![vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(1.0, 1.0), vec2(-1.0, 1.0)];
+    /// let mesh = MeshBuilder::<()>::new().extrude(&outline, &[hole], vec3(0.0, 0.0, 0.5)).build()?;
+    ///
+    /// assert_eq!(mesh.num_vertices(), 16);
+    /// assert!(mesh.is_closed());
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn extrude(self, outline: &[crate::mesh::math::Vec2], holes: &[Vec<crate::mesh::math::Vec2>], direction: crate::mesh::math::Vec3) -> Self
+    {
+        use crate::mesh::math::{vec2, Vec3};
+        if direction.z == 0.0 || !direction.z.is_finite() {
+            return self.with_error(Error::InvalidParameter { message: format!("The z-coordinate of the direction ({}) must be a number which is not zero", direction.z) });
+        }
+        // The cross product of the axes of the frames must point along the direction, so the polygon is mirrored if the direction points downwards
+        let sign = direction.z.signum();
+        let mirror = |points: &[crate::mesh::math::Vec2]| points.iter().map(|p| vec2(p.x, sign * p.y)).collect::<Vec<_>>();
+        let frames = [(Vec3::new(0.0, 0.0, 0.0), Vec3::unit_x(), sign * Vec3::unit_y()), (direction, Vec3::unit_x(), sign * Vec3::unit_y())];
+        self.sweep_polygon(&mirror(outline), &holes.iter().map(|hole| mirror(hole)).collect::<Vec<_>>(), &frames, false)
+    }
+
+    ///
+    /// Creates a closed surface by revolving the polyline `profile` around the x-axis (also called lathing),
+    /// where each point of the profile is given as the x-coordinate and the distance to the axis.
+    /// If the first or last point of the profile is not on the axis, that end is closed by a flat cap.
+    /// Only the first and last point may be on the axis and the profile must have at least two points.
+    /// `angle_subdivisions` (at least 3) defines the number of circular subdivisions.
+    /// The faces are oriented outwards independent of the direction of the profile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// // A vase with a flat bottom, which is closed at the top
+    /// let profile = vec![vec2(0.0, 1.0), vec2(1.0, 1.5), vec2(2.0, 0.8), vec2(3.0, 0.5), vec2(3.0, 0.0)];
+    /// let mesh = MeshBuilder::<()>::new().lathe(&profile, 16).build()?;
+    ///
+    /// assert_eq!(mesh.num_vertices(), 2 + 4 * 16);
+    /// assert!(mesh.is_closed());
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn lathe(self, profile: &[crate::mesh::math::Vec2], angle_subdivisions: usize) -> Self
+    {
+        use crate::mesh::math::vec2;
+        if let Err(error) = check_minimums(&[("The number of points of the profile", profile.len(), 2)]) {
+            return self.with_error(error);
+        }
+        let mut points = profile.iter().map(|p| (p.x, p.y.abs())).collect::<Vec<_>>();
+        if points[0].1 > 0.0 {
+            points.insert(0, (points[0].0, 0.0));
+        }
+        let last = points[points.len() - 1];
+        if last.1 > 0.0 {
+            points.push((last.0, 0.0));
+        }
+        // The faces are oriented outwards if the profile, closed along the axis, is clockwise
+        if triangulation::signed_area(&points.iter().map(|(x, radius)| vec2(*x, *radius)).collect::<Vec<_>>()) > 0.0 {
+            points.reverse();
+        }
+        self.surface_of_revolution(&points, angle_subdivisions)
+    }
+
+    ///
+    /// Creates a tube by sweeping the polygon with the given outline and holes along the polyline `path`, with a copy of the polygon as cap at each end.
+    /// At each point of the path, the polygon is placed in the plane orthogonal to the path, where the x- and y-axis of the polygon
+    /// follow rotation minimizing frames, i.e. frames which are transported along the path without twisting around it.
+    /// If `closed` is true, the last point of the path is connected to the first, so no caps are added,
+    /// and the twist between the last and the first frame is distributed evenly along the path.
+    /// The path must have at least two points (three if it is closed) and must not turn back on itself.
+    /// The outline and holes can have any orientation, the faces are always oriented outwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tri_mesh::prelude::*;
+    /// # fn main() -> Result<(), Box<tri_mesh::mesh_builder::Error>> {
+    /// // A triangular tube along a helix
+    /// let profile = vec![vec2(0.2, 0.0), vec2(-0.1, 0.15), vec2(-0.1, -0.15)];
+    /// let path = (0..50).map(|i| {
+    ///     let angle = 0.2 * i as f64;
+    ///     vec3(angle.cos(), angle.sin(), 0.05 * angle)
+    /// }).collect::<Vec<_>>();
+    /// let mesh = MeshBuilder::<()>::new().sweep(&profile, &[], &path, false).build()?;
+    ///
+    /// assert_eq!(mesh.num_vertices(), 3 * 50);
+    /// assert!(mesh.is_closed());
+    /// #   mesh.is_valid().unwrap();
+    /// #   Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn sweep(self, outline: &[crate::mesh::math::Vec2], holes: &[Vec<crate::mesh::math::Vec2>], path: &[crate::mesh::math::Vec3], closed: bool) -> Self
+    {
+        use crate::mesh::math::{Vec3, InnerSpace};
+        let mut path = path.to_vec();
+        path.dedup();
+        if closed && path.len() > 1 && path[0] == path[path.len() - 1] {
+            path.pop();
+        }
+        let n = path.len();
+        if let Err(error) = check_minimums(&[("The number of distinct points of the path", n, if closed { 3 } else { 2 })]) {
+            return self.with_error(error);
+        }
+
+        let tangents = (0..n).map(|i| {
+            let previous = if i > 0 { Some(path[i] - path[i - 1]) } else if closed { Some(path[0] - path[n - 1]) } else { None };
+            let next = if i + 1 < n { Some(path[i + 1] - path[i]) } else if closed { Some(path[0] - path[n - 1]) } else { None };
+            match (previous, next) {
+                (Some(previous), Some(next)) => (previous.normalize() + next.normalize()).normalize(),
+                (Some(tangent), None) | (None, Some(tangent)) => tangent.normalize(),
+                (None, None) => unreachable!()
+            }
+        }).collect::<Vec<_>>();
+
+        // Rotates the normal by the smallest rotation which takes one tangent into the other
+        let transport = |normal: Vec3, from: Vec3, to: Vec3| {
+            let axis = from.cross(to);
+            let cos = from.dot(to);
+            let rotated = if cos > -1.0 + 1.0e-12 { normal * cos + axis.cross(normal) + axis * (axis.dot(normal) / (1.0 + cos)) } else { normal };
+            (rotated - to * to.dot(rotated)).normalize()
+        };
+        let t = tangents[0];
+        let axis = if t.x.abs() <= t.y.abs() && t.x.abs() <= t.z.abs() { Vec3::unit_x() }
+            else if t.y.abs() <= t.z.abs() { Vec3::unit_y() }
+            else { Vec3::unit_z() };
+        let mut normals = vec![(axis - t * t.dot(axis)).normalize()];
+        for i in 1..n {
+            normals.push(transport(normals[i - 1], tangents[i - 1], tangents[i]));
+        }
+        if closed {
+            let end = transport(normals[n - 1], tangents[n - 1], tangents[0]);
+            let twist = t.dot(end.cross(normals[0])).atan2(end.dot(normals[0]));
+            for i in 1..n {
+                let angle = twist * i as f64 / n as f64;
+                normals[i] = normals[i] * angle.cos() + tangents[i].cross(normals[i]) * angle.sin();
+            }
+        }
+
+        let frames = (0..n).map(|i| (path[i], normals[i], tangents[i].cross(normals[i]))).collect::<Vec<_>>();
+        self.sweep_polygon(outline, holes, &frames, closed)
+    }
+
+    ///
+    /// Creates a closed surface by revolving the profile around the x-axis, where each point of the profile is given as
+    /// the x-coordinate and the distance to the axis. The first and last point of the profile must be on the axis,
+    /// and the other points must not.
+    ///
+    fn surface_of_revolution(self, profile: &[(f64, f64)], angle_subdivisions: usize) -> Self
+    {
+        if let Err(error) = check_minimums(&[("angle_subdivisions", angle_subdivisions, 3)]) {
+            return self.with_error(error);
+        }
+        if profile.len() < 3 {
+            return self.with_error(Error::InvalidParameter { message: "The profile must have a point which is not on the axis".to_string() });
+        }
+        let rings = profile.len() - 2;
+        let mut positions = vec![profile[0].0, 0.0, 0.0];
+        for (x, radius) in profile[1..profile.len() - 1].iter() {
+            for j in 0..angle_subdivisions {
+                let angle = 2.0 * std::f64::consts::PI * j as f64 / angle_subdivisions as f64;
+                positions.extend_from_slice(&[*x, radius * angle.cos(), radius * angle.sin()]);
+            }
+        }
+        positions.extend_from_slice(&[profile[profile.len() - 1].0, 0.0, 0.0]);
+
+        let last_pole = (1 + rings * angle_subdivisions) as u32;
+        let vertex = |i: usize, j: usize| (1 + i * angle_subdivisions + j % angle_subdivisions) as u32;
+        let mut indices = Vec::new();
+        for j in 0..angle_subdivisions {
+            indices.extend_from_slice(&[0, vertex(0, j + 1), vertex(0, j)]);
+            indices.extend_from_slice(&[last_pole, vertex(rings - 1, j), vertex(rings - 1, j + 1)]);
+        }
+        indices.extend(grid_indices(angle_subdivisions, rings, true, false).into_iter().map(|index| 1 + index));
+        self.with_positions(positions).with_indices(indices)
+    }
+
+    ///
+    /// Places the polygon with the given outline and holes in each frame, given as an origin and the directions of the x- and y-axis of the polygon,
+    /// and connects the loops of consecutive frames. The cross product of the axes must point towards the next frame.
+    /// If `closed` is false, the first and last frame are closed by triangulating the polygon, otherwise the last frame is connected to the first.
+    ///
+    fn sweep_polygon(self, outline: &[crate::mesh::math::Vec2], holes: &[Vec<crate::mesh::math::Vec2>],
+                     frames: &[(crate::mesh::math::Vec3, crate::mesh::math::Vec3, crate::mesh::math::Vec3)], closed: bool) -> Self
+    {
+        let mut loops = vec![("The number of points of the outline", outline.len(), 3)];
+        loops.extend(holes.iter().map(|hole| ("The number of points of a hole", hole.len(), 3)));
+        if let Err(error) = check_minimums(&loops) {
+            return self.with_error(error);
+        }
+        // The outline must be counterclockwise and the holes clockwise for the sides to be oriented outwards
+        let oriented = |points: &[crate::mesh::math::Vec2], counterclockwise: bool| {
+            let mut points = points.to_vec();
+            if (triangulation::signed_area(&points) > 0.0) != counterclockwise {
+                points.reverse();
+            }
+            points
+        };
+        let loops = std::iter::once(oriented(outline, true)).chain(holes.iter().map(|hole| oriented(hole, false))).collect::<Vec<_>>();
+
+        let mut positions = Vec::new();
+        let mut indices = Vec::new();
+        let mut offsets = Vec::with_capacity(loops.len());
+        for points in loops.iter() {
+            let offset = (positions.len() / 3) as u32;
+            offsets.push(offset);
+            for (origin, x_axis, y_axis) in frames.iter() {
+                for p in points.iter() {
+                    let position = origin + x_axis * p.x + y_axis * p.y;
+                    positions.extend_from_slice(&[position.x, position.y, position.z]);
+                }
+            }
+            indices.extend(grid_indices(points.len(), frames.len(), true, closed).into_iter().map(|index| offset + index));
+        }
+
+        if !closed {
+            // The triangulation refers to the points of the outline followed by the points of each hole
+            let corners = loops.iter().enumerate().flat_map(|(l, points)| (0..points.len()).map(move |i| (l, i))).collect::<Vec<_>>();
+            let vertex = |corner: u32, frame: usize| {
+                let (l, i) = corners[corner as usize];
+                offsets[l] + (frame * loops[l].len() + i) as u32
+            };
+            let last = frames.len() - 1;
+            for [a, b, c] in triangulation::triangulate(&loops[0], &loops[1..]) {
+                indices.extend_from_slice(&[vertex(a, 0), vertex(c, 0), vertex(b, 0)]);
+                indices.extend_from_slice(&[vertex(a, last), vertex(b, last), vertex(c, last)]);
+            }
+        }
+        self.with_positions(positions).with_indices(indices)
+    }
+}
+
+///
+/// Returns an [InvalidParameter](crate::mesh_builder::Error::InvalidParameter) error for the first of the parameters,
+/// given by their name, value and minimum value, which is less than its minimum value.
+///
+fn check_minimums(parameters: &[(&str, usize, usize)]) -> Result<(), Error>
+{
+    match parameters.iter().find(|(_, value, minimum)| value < minimum) {
+        Some((name, value, minimum)) => Err(Error::InvalidParameter {
+            message: format!("{} ({}) must be at least {}", name, value, minimum)
+        }),
+        None => Ok(())
+    }
+}
+
+///
+/// Returns the indices of the triangles of a grid of `u_count` times `v_count` vertices, where vertex `(i, j)` has index `j * u_count + i`.
+/// The faces are oriented such that the normal is in the direction of the cross product of the u- and v-direction.
+/// If the grid wraps in a direction, the last vertices in that direction are connected to the first.
+///
+fn grid_indices(u_count: usize, v_count: usize, wrap_u: bool, wrap_v: bool) -> Vec<u32>
+{
+    let vertex = |i: usize, j: usize| ((j % v_count) * u_count + i % u_count) as u32;
+    let u_cells = if wrap_u { u_count } else { u_count - 1 };
+    let v_cells = if wrap_v { v_count } else { v_count - 1 };
+    let mut indices = Vec::with_capacity(6 * u_cells * v_cells);
+    for j in 0..v_cells {
+        for i in 0..u_cells {
+            indices.extend_from_slice(&[vertex(i, j), vertex(i + 1, j), vertex(i + 1, j + 1)]);
+            indices.extend_from_slice(&[vertex(i, j), vertex(i + 1, j + 1), vertex(i, j + 1)]);
+        }
+    }
+    indices
+}
+
+const ICOSAHEDRON_X: f64 = 0.525731112119133606;
+const ICOSAHEDRON_Z: f64 = 0.850650808352039932;
+
+const ICOSAHEDRON_POSITIONS: [f64; 36] = {
+    let (x, z) = (ICOSAHEDRON_X, ICOSAHEDRON_Z);
+    [
+        -x, 0.0, z, x, 0.0, z, -x, 0.0, -z, x, 0.0, -z,
+        0.0, z, x, 0.0, z, -x, 0.0, -z, x, 0.0, -z, -x,
+        z, x, 0.0, -z, x, 0.0, z, -x, 0.0, -z, -x, 0.0
+    ]
+};
+
+const ICOSAHEDRON_INDICES: [u32; 60] = [
+    0, 1, 4, 0, 4, 9, 9, 4, 5, 4, 8, 5, 4, 1, 8,
+    8, 1, 10, 8, 10, 3, 5, 8, 3, 5, 3, 2, 2, 3, 7,
+    7, 3, 10, 7, 10, 6, 7, 6, 11, 11, 6, 0, 0, 6, 1,
+    6, 10, 1, 9, 11, 0, 9, 2, 11, 9, 5, 2, 7, 11, 2
+];
+
+#[cfg(feature = "stl-io")]
+fn is_binary_stl(bytes: &[u8]) -> bool
+{
+    // An ASCII file may also start with 'solid', so the size is the most reliable indicator
+    if bytes.len() >= 84 {
+        let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+        if bytes.len() == 84 + 50 * count {
+            return true;
+        }
+    }
+    let start = bytes.iter().position(|byte| !byte.is_ascii_whitespace()).unwrap_or(bytes.len());
+    !bytes[start..].starts_with(b"solid")
+}
+
+#[cfg(feature = "stl-io")]
+fn parse_binary_stl(bytes: &[u8]) -> Result<Vec<[f64; 3]>, Error>
+{
+    if bytes.len() < 84 {
+        return Err(Error::InvalidFile {message: "Binary stl file is too short to contain a header".to_string()});
+    }
+    let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+    if bytes.len() < 84 + 50 * count {
+        return Err(Error::InvalidFile {message: format!("Binary stl file contains {} bytes but {} triangles are specified", bytes.len(), count)});
+    }
+
+    let read_f32 = |offset: usize| f32::from_le_bytes([bytes[offset], bytes[offset+1], bytes[offset+2], bytes[offset+3]]) as f64;
+    let mut corners = Vec::with_capacity(3 * count);
+    for triangle in 0..count {
+        // Skip the normal, which is recomputed from the positions
+        let offset = 84 + 50 * triangle + 12;
+        for corner in 0..3 {
+            let offset = offset + 12 * corner;
+            corners.push([read_f32(offset), read_f32(offset + 4), read_f32(offset + 8)]);
+        }
+    }
+    Ok(corners)
+}
+
+#[cfg(feature = "stl-io")]
+fn parse_ascii_stl(bytes: &[u8]) -> Result<Vec<[f64; 3]>, Error>
+{
+    let source = std::str::from_utf8(bytes).map_err(|e| Error::InvalidFile {message: format!("Ascii stl file is not valid utf8: {}", e)})?;
+    let mut corners = Vec::new();
+    for (line_number, line) in source.lines().enumerate() {
+        let mut words = line.split_whitespace();
+        if words.next() == Some("vertex") {
+            let mut corner = [0.0; 3];
+            for value in corner.iter_mut() {
+                *value = words.next().and_then(|word| word.parse().ok()).ok_or_else(||
+                    Error::InvalidFile {message: format!("Invalid vertex in ascii stl file at line {}", line_number + 1)})?;
+            }
+            corners.push(corner);
+        }
+    }
+    if corners.len() % 3 != 0 {
+        return Err(Error::InvalidFile {message: format!("Ascii stl file contains {} vertices which is not a multiple of three", corners.len())});
+    }
+    Ok(corners)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_obj() {
         let source = "o Cube
         v 1.000000 -1.000000 -1.000000
         v 1.000000 -1.000000 1.000000
@@ -535,4 +2209,710 @@ mod tests {
 
         mesh.is_valid().unwrap();
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "stl-io")]
+    #[test]
+    fn test_with_stl_ascii() {
+        let source = "solid tetrahedron
+        facet normal 0 0 -1
+            outer loop
+                vertex 0 0 0
+                vertex 0 1 0
+                vertex 1 0 0
+            endloop
+        endfacet
+        facet normal 0 -1 0
+            outer loop
+                vertex 0 0 0
+                vertex 1 0 0
+                vertex 0 0 1
+            endloop
+        endfacet
+        facet normal -1 0 0
+            outer loop
+                vertex 0 0 0
+                vertex 0 0 1
+                vertex 0 1 0
+            endloop
+        endfacet
+        facet normal 1 1 1
+            outer loop
+                vertex 1 0 0
+                vertex 0 1 0
+                vertex 0 0 1
+            endloop
+        endfacet
+        endsolid tetrahedron";
+
+        let mesh = MeshBuilder::<()>::new().with_stl(source.as_bytes()).unwrap().build().unwrap();
+
+        assert_eq!(mesh.num_faces(), 4);
+        assert_eq!(mesh.num_vertices(), 4);
+        assert!(mesh.edge_iter().all(|halfedge_id| !mesh.is_edge_on_boundary(halfedge_id)));
+
+        mesh.is_valid().unwrap();
+    }
+
+    #[cfg(feature = "stl-io")]
+    #[test]
+    fn test_with_stl_binary_with_solid_header() {
+        let mut bytes = b"solid but actually binary".to_vec();
+        bytes.resize(80, 0);
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        for value in &[0.0f32, 0.0, 1.0,  0.0, 0.0, 0.0,  1.0, 0.0, 0.0,  0.0, 1.0, 0.0] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(&[0, 0]);
+
+        let mesh = MeshBuilder::<()>::new().with_stl(&bytes).unwrap().build().unwrap();
+
+        assert_eq!(mesh.num_faces(), 1);
+        assert_eq!(mesh.num_vertices(), 3);
+        mesh.is_valid().unwrap();
+    }
+
+    #[cfg(feature = "stl-io")]
+    #[test]
+    fn test_with_invalid_stl() {
+        assert!(MeshBuilder::<()>::new().with_stl(b"solid broken\nvertex 0 0\n").is_err());
+        assert!(MeshBuilder::<()>::new().with_stl(&[0; 40]).is_err());
+    }
+
+    #[cfg(feature = "ply-io")]
+    #[test]
+    fn test_with_ply_ascii() {
+        let source = "ply
+        format ascii 1.0
+        comment a square with a quad face and a triangle face
+        element vertex 5
+        property float x
+        property float y
+        property float z
+        property uchar red
+        property uchar green
+        property uchar blue
+        element face 2
+        property list uchar int vertex_indices
+        property int label
+        end_header
+        0 0 0 255 0 0
+        1 0 0 0 255 0
+        1 1 0 0 0 255
+        0 1 0 255 255 255
+        0.5 2 0 0 0 0
+        4 0 1 2 3 7
+        3 3 2 4 9
+        ";
+
+        let mesh = MeshBuilder::<i32>::new().with_ply_face_property(source.as_bytes(), "label", |label| label as i32).unwrap().build().unwrap();
+
+        assert_eq!(mesh.num_faces(), 3);
+        assert_eq!(mesh.num_vertices(), 5);
+        assert_eq!(mesh.face_iter().map(|face_id| mesh.face_tag(face_id)).collect::<Vec<_>>(), vec![7, 7, 9]);
+        let vertex_id = mesh.vertex_iter().nth(1).unwrap();
+        assert_eq!(mesh.vertex_attribute::<Vec4>("color", vertex_id), Some(crate::mesh::math::vec4(0.0, 1.0, 0.0, 1.0)));
+        assert!(!mesh.has_vertex_attribute("normal"));
+
+        mesh.is_valid().unwrap();
+    }
+
+    #[cfg(feature = "ply-io")]
+    #[test]
+    fn test_with_ply_binary_big_endian() {
+        let mut bytes = b"ply\nformat binary_big_endian 1.0\nelement vertex 3\nproperty double x\nproperty double y\nproperty double z\n\
+            property float nx\nproperty float ny\nproperty float nz\nelement face 1\nproperty list uchar uint vertex_indices\nend_header\n".to_vec();
+        for position in &[[0.0f64, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]] {
+            for value in position.iter() {
+                bytes.extend_from_slice(&value.to_be_bytes());
+            }
+            for value in &[0.0f32, 0.0, 1.0] {
+                bytes.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+        bytes.push(3);
+        for index in &[0u32, 1, 2] {
+            bytes.extend_from_slice(&index.to_be_bytes());
+        }
+
+        let mesh = MeshBuilder::<()>::new().with_ply(&bytes).unwrap().build().unwrap();
+
+        assert_eq!(mesh.num_faces(), 1);
+        assert_eq!(mesh.num_vertices(), 3);
+        let vertex_id = mesh.vertex_iter().nth(1).unwrap();
+        assert_eq!(mesh.vertex_position(vertex_id), crate::mesh::math::vec3(1.0, 0.0, 0.0));
+        assert_eq!(mesh.vertex_attribute::<Vec3>("normal", vertex_id), Some(crate::mesh::math::vec3(0.0, 0.0, 1.0)));
+
+        mesh.is_valid().unwrap();
+    }
+
+    #[cfg(feature = "ply-io")]
+    #[test]
+    fn test_with_invalid_ply() {
+        let source = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\n\
+            element face 1\nproperty list uchar int vertex_indices\nend_header\n0 0 0\n1 0 0\n0 1 0\n3 0 1 3\n";
+        assert!(MeshBuilder::<()>::new().with_ply(source.as_bytes()).is_err());
+        assert!(MeshBuilder::<()>::new().with_ply_face_property(source.as_bytes(), "label", |_| ()).is_err());
+        assert!(MeshBuilder::<()>::new().with_ply(b"ply\nformat ascii 1.0\n").is_err());
+    }
+
+    #[cfg(feature = "gltf-io")]
+    #[test]
+    fn test_with_invalid_gltf() {
+        let positions = [0.0f32, 0.0, 0.0,  1.0, 0.0, 0.0,  0.0, 1.0, 0.0];
+        let data = base64::encode(positions.iter().flat_map(|p| p.to_le_bytes()).collect::<Vec<_>>());
+        let path = std::env::temp_dir().join("tri_mesh_test_with_invalid_gltf.gltf");
+        let read = |accessor: &str| {
+            std::fs::write(&path, format!(r#"{{"asset": {{"version": "2.0"}},
+                "buffers": [{{"byteLength": 36, "uri": "data:application/octet-stream;base64,{}"}}],
+                "bufferViews": [{{"buffer": 0, "byteLength": 36}}],
+                "accessors": [{}],
+                "meshes": [{{"primitives": [{{"attributes": {{"POSITION": 0}}}}]}}]}}"#, data, accessor)).unwrap();
+            MeshBuilder::<()>::new().with_gltf(&path).map(|builder| builder.build())
+        };
+
+        assert!(read(r#"{"bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3"}"#).unwrap().is_ok());
+        assert!(read(r#"{"bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3"}"#).is_err());
+        assert!(read(r#"{"bufferView": 0, "componentType": 5126, "count": 4000000000000, "type": "VEC3"}"#).is_err());
+        assert!(read(r#"{"bufferView": 0, "componentType": 5126, "count": 18446744073709551615, "type": "VEC3"}"#).is_err());
+        assert!(read(r#"{"bufferView": 0, "byteOffset": 18446744073709551615, "componentType": 5126, "count": 3, "type": "VEC3"}"#).is_err());
+        assert!(read(r#"{"componentType": 5126, "count": 4000000000000, "type": "VEC3"}"#).is_err());
+        assert!(read(r#"{"bufferView": 0, "componentType": 5126, "count": 9, "type": "SCALAR"}"#).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "off-io")]
+    #[test]
+    fn test_with_off() {
+        let source = "# A square with a quad face and a triangle face
+        COFF 5 2 0
+        0 0 0  255 0 0 255
+        1 0 0  255 0 0 255
+        1 1 0  255 0 0 255
+        0 1 0  255 0 0 255
+        0.5 2 0  255 0 0 255
+        4 0 1 2 3  0 0 255
+        3 3 2 4
+        ";
+
+        let mesh = MeshBuilder::<()>::new().with_off(source).unwrap().build().unwrap();
+
+        assert_eq!(mesh.num_faces(), 3);
+        assert_eq!(mesh.num_vertices(), 5);
+        mesh.is_valid().unwrap();
+
+        assert!(MeshBuilder::<()>::new().with_off("OFF\n3 1 0\n0 0 0\n1 0 0\n0 1 0\n3 0 1 3\n").is_err());
+        assert!(MeshBuilder::<()>::new().with_off("PLY\n3 1 0\n").is_err());
+        assert!(MeshBuilder::<()>::new().with_off("OFF\n2000000000000 1 0\n0 0 0").is_err());
+        assert!(MeshBuilder::<()>::new().with_off("OFF\n18446744073709551615 18446744073709551615 0\n0 0 0").is_err());
+    }
+
+    #[cfg(feature = "vtk-io")]
+    #[test]
+    fn test_with_vtk() {
+        let source = "# vtk DataFile Version 5.1
+        a square and a triangle strip
+        ASCII
+        DATASET POLYDATA
+        POINTS 6 float
+        0 0 0  1 0 0  1 1 0  0 1 0  0 2 0  1 2 0
+        POLYGONS 2 4
+        OFFSETS vtktypeint64
+        0 4
+        CONNECTIVITY vtktypeint64
+        0 1 2 3
+        TRIANGLE_STRIPS 2 4
+        OFFSETS vtktypeint64
+        0 4
+        CONNECTIVITY vtktypeint64
+        3 2 4 5
+        CELL_DATA 3
+        SCALARS label int
+        LOOKUP_TABLE default
+        0 1 1
+        POINT_DATA 6
+        NORMALS normal float
+        0 0 1  0 0 1  0 0 1  0 0 1  0 0 1  0 0 1
+        SCALARS temperature float 1
+        LOOKUP_TABLE default
+        1 2 3 4 5 6
+        ";
+
+        let mesh = MeshBuilder::<()>::new().with_vtk(source).unwrap().build().unwrap();
+
+        assert_eq!(mesh.num_faces(), 4);
+        assert_eq!(mesh.num_vertices(), 6);
+        assert!(!mesh.has_vertex_attribute("label"));
+        let vertex_id = mesh.vertex_iter().nth(4).unwrap();
+        assert_eq!(mesh.vertex_attribute::<f64>("temperature", vertex_id), Some(5.0));
+        mesh.is_valid().unwrap();
+
+        assert!(MeshBuilder::<()>::new().with_vtk("# vtk DataFile Version 3.0\ntitle\nBINARY\nDATASET POLYDATA\n").is_err());
+        assert!(MeshBuilder::<()>::new().with_vtk("# vtk DataFile Version 3.0\ntitle\nASCII\nDATASET UNSTRUCTURED_GRID\n").is_err());
+        let header = "# vtk DataFile Version 3.0\ntitle\nASCII\nDATASET POLYDATA\nPOINTS 3 float\n0 0 0 1 0 0 0 1 0\n";
+        assert!(MeshBuilder::<()>::new().with_vtk(&format!("{}POLYGONS 4000000000000 3\n3 0 1 2\n", header)).is_err());
+        assert!(MeshBuilder::<()>::new().with_vtk(&format!("{}POLYGONS 18446744073709551615 3\n3 0 1 2\n", header)).is_err());
+        assert!(MeshBuilder::<()>::new().with_vtk("# vtk DataFile Version 3.0\ntitle\nASCII\nDATASET POLYDATA\nPOINTS 18446744073709551615 float\n0 0 0").is_err());
+        assert!(MeshBuilder::<()>::new().with_vtk(&format!("{}POLYGONS 1 4\n3 0 1 2\nPOINT_DATA 18446744073709551615\nVECTORS v float\n0 0 0", header)).is_err());
+    }
+
+    #[cfg(feature = "obj-io")]
+    #[test]
+    fn test_with_obj_attributes() {
+        use crate::mesh::math::*;
+        let source = "o first
+        v 0 0 0
+        v 1 0 0
+        v 1 1 0
+        v 0 1 0
+        vt 0 0
+        vt 1 0
+        vt 1 1
+        vt 0 1
+        vt 0.5 0.5
+        vn 0 0 1
+        usemtl red
+        s 1
+        f 1/1/1 2/2/1 3/3/1
+        f 1/5/1 3/3/1 4/4/1
+        o second
+        v 0 0 1
+        v 1 0 1
+        v 0 1 1
+        g top
+        f 5 6 7".to_string();
+
+        let mesh = MeshBuilder::<String>::new().with_obj_attributes(source, |face: &ObjFace| {
+            format!("{} {:?} {:?} {:?}", face.object, face.groups, face.material, face.smoothing_groups)
+        }).unwrap().build().unwrap();
+
+        assert_eq!(mesh.num_faces(), 3);
+        assert_eq!(mesh.num_vertices(), 7);
+        mesh.is_valid().unwrap();
+
+        let faces = mesh.face_iter().collect::<Vec<_>>();
+        assert_eq!(mesh.face_tag(faces[0]), "first [] Some(\"red\") [1]");
+        assert_eq!(mesh.face_tag(faces[2]), "second [\"top\"] None []");
+
+        // The first vertex has a different texture coordinate in each of the two faces
+        let vertex_id = mesh.vertex_iter().next().unwrap();
+        let uv = |face_id: crate::mesh::ids::FaceID| mesh.face_halfedge_iter(face_id)
+            .find(|halfedge_id| mesh.walker_from_halfedge(*halfedge_id).vertex_id() == Some(vertex_id))
+            .and_then(|halfedge_id| mesh.corner_attribute::<Vec2>("uv", halfedge_id));
+        assert_eq!(uv(faces[0]), Some(vec2(0.0, 0.0)));
+        assert_eq!(uv(faces[1]), Some(vec2(0.5, 0.5)));
+        for halfedge_id in mesh.face_halfedge_iter(faces[1]) {
+            assert_eq!(mesh.corner_attribute::<Vec3>("normal", halfedge_id), Some(vec3(0.0, 0.0, 1.0)));
+        }
+        for halfedge_id in mesh.face_halfedge_iter(faces[2]) {
+            assert_eq!(mesh.corner_attribute::<Vec2>("uv", halfedge_id), None);
+        }
+
+        assert!(MeshBuilder::<()>::new().with_obj_attributes("f 1 2 3 4 x".to_string(), |_| ()).is_err());
+    }
+
+    #[cfg(feature = "obj-io")]
+    #[test]
+    fn test_with_obj_polygons() {
+        // A concave quadrilateral, which is not triangulated correctly as a fan from the first corner, and a pentagon
+        let source = "v 0 0 0
+        v 2 1 0
+        v 0 2 0
+        v 1 1 0
+        vt 0 0
+        f 1/1 2/1 3/1 4/1
+        v 3 0 0
+        v 4 0 0
+        v 4 1 0
+        v 3.5 2 0
+        v 3 1 0
+        f 5 6 7 8 9".to_string();
+
+        let mesh = MeshBuilder::<()>::new().with_obj(source).build().unwrap();
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.num_faces(), 2 + 3);
+        assert!(mesh.face_iter().all(|face_id| mesh.face_normal(face_id).z > 0.99));
+        assert!((mesh.face_iter().map(|face_id| mesh.face_area(face_id)).sum::<f64>() - (1.0 + 1.5)).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_with_polygons() {
+        // A slightly non-planar hexagon with a hole and an invalid polygon
+        let positions = vec![0.0, 0.0, 0.0,  2.0, 0.0, 0.1,  3.0, 1.0, 0.0,  2.0, 2.0, -0.1,  0.0, 2.0, 0.0,  1.0, 1.0, 0.05,
+                             1.5, 0.8, 0.0,  1.8, 1.0, 0.0,  1.5, 1.2, 0.0];
+        let mesh = MeshBuilder::<()>::new().with_polygons(positions.clone(), &[vec![vec![0, 1, 2, 3, 4, 5], vec![6, 8, 7]]]).build().unwrap();
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.num_faces(), 9 - 2 + 2);
+        assert!(mesh.face_iter().all(|face_id| mesh.face_normal(face_id).z > 0.9));
+
+        let result = MeshBuilder::<()>::new().with_polygons(positions.clone(), &[vec![vec![0, 1, 2, 9]]]).build();
+        assert!(matches!(result, Err(Error::InvalidIndices { .. })));
+
+        // A hole which refers to a vertex which does not exist
+        let polygons = [vec![vec![0, 1, 3, 4]], vec![vec![0, 1, 2, 3, 4, 5], vec![6, 8, 99]]];
+        let result = MeshBuilder::<()>::new().with_polygons(positions.clone(), &polygons).build();
+        assert!(matches!(result, Err(Error::InvalidIndices { .. })));
+        let (mesh, report) = MeshBuilder::<()>::new().with_polygons(positions, &polygons).build_with_repair().unwrap();
+        assert_eq!(mesh.num_faces(), 2);
+        assert_eq!(report.out_of_range_faces, vec![2]);
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let positions = vec![0.0, 0.0, 0.0,  1.0, 0.0, 0.0,  0.0, 1.0, 0.0,  0.0, -1.0, 0.0,  0.0, 0.0, 1.0];
+        let build = |positions: Vec<f64>, indices: Vec<u32>| MeshBuilder::<u32>::new().with_positions(positions).with_indices(indices).build();
+
+        assert!(matches!(build(positions[..14].to_vec(), vec![0, 1, 2]), Err(Error::InvalidPositions {..})));
+        assert!(matches!(build(positions.clone(), vec![0, 1, 2, 0]), Err(Error::InvalidIndices {..})));
+        assert!(matches!(build(positions.clone(), vec![0, 1, 5]), Err(Error::InvalidIndices {..})));
+        assert!(matches!(build(positions.clone(), vec![0, 1, 2, 2, 1, 2]), Err(Error::DegenerateFace {..})));
+        assert!(matches!(build(positions.clone(), vec![0, 1, 2, 1, 0, 3, 0, 1, 4]), Err(Error::NonManifoldEdge {..})));
+        assert!(matches!(build(positions.clone(), vec![0, 1, 2, 0, 1, 3]), Err(Error::NonManifoldEdge {..})));
+        assert!(matches!(build(positions.clone(), vec![0, 1, 2, 0, 1, 2]), Err(Error::NonManifoldEdge {..})));
+        assert!(matches!(build(positions.clone(), vec![0, 1, 2, 0, 3, 4]), Err(Error::NonManifoldVertex {..})));
+        assert!(matches!(MeshBuilder::<u32>::new().with_positions(positions.clone()).with_indices(vec![0, 1, 2]).with_tags(vec![1, 2]).build(),
+                         Err(Error::InvalidTags {..})));
+        assert!(build(positions, vec![0, 1, 2, 1, 0, 3, 2, 1, 4]).is_ok());
+    }
+
+    #[test]
+    fn test_build_with_repair() {
+        let positions = vec![0.0, 0.0, 0.0,  1.0, 0.0, 0.0,  0.0, 1.0, 0.0,  9.0, 9.0, 9.0,  0.0, -1.0, 0.0,  0.0, 0.0, 1.0,  7.0];
+        let indices = vec![0, 1, 2,  1, 0, 4,  0, 1, 5,  0, 6, 1,  2, 2, 1,  0];
+        let uvs = (0..16).map(|i| i as f64).collect::<Vec<_>>();
+        let (mesh, report) = MeshBuilder::<u32>::new().with_positions(positions).with_indices(indices).with_tags(vec![10, 11, 12, 13])
+            .with_vertex_attribute("height", (0..6).map(|i| i as f64).collect(), |values: &[(f64, f64)]| values[0].0)
+            .with_corner_attribute("uv", uvs, |values: &[(f64, f64)]| values[0].0)
+            .build_with_repair().unwrap();
+        mesh.is_valid().unwrap();
+
+        assert_eq!(report, RepairReport {
+            removed_coordinates: 1,
+            removed_indices: 1,
+            added_tags: 1,
+            removed_tags: 0,
+            out_of_range_faces: vec![3],
+            degenerate_faces: vec![4],
+            welded_faces: vec![],
+            non_manifold_faces: vec![2],
+            non_manifold_vertex_faces: vec![],
+            unreferenced_vertices: vec![3, 5]
+        });
+        assert!(!report.is_empty());
+        assert_eq!(mesh.num_faces(), 2);
+        assert_eq!(mesh.num_vertices(), 4);
+        assert_eq!(mesh.face_iter().map(|face_id| mesh.face_tag(face_id)).collect::<Vec<_>>(), vec![10, 11]);
+
+        let vertex_ids = mesh.vertex_iter().collect::<Vec<_>>();
+        let heights = vertex_ids.iter().map(|vertex_id| mesh.vertex_attribute::<f64>("height", *vertex_id).unwrap()).collect::<Vec<_>>();
+        assert_eq!(heights, vec![0.0, 1.0, 2.0, 4.0]);
+        for (face, face_id) in mesh.face_iter().enumerate() {
+            for halfedge_id in mesh.face_halfedge_iter(face_id) {
+                let vertex_id = mesh.walker_from_halfedge(halfedge_id).vertex_id().unwrap();
+                let input_vertex = mesh.vertex_attribute::<f64>("height", vertex_id).unwrap();
+                let corner = [[0.0, 1.0, 2.0], [1.0, 0.0, 4.0]][face].iter().position(|v| *v == input_vertex).unwrap();
+                assert_eq!(mesh.corner_attribute::<f64>("uv", halfedge_id), Some((3 * face + corner) as f64));
+            }
+        }
+
+        let (_, report) = MeshBuilder::<()>::new().cube().build_with_repair().unwrap();
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn test_build_with_repair_of_corrupt_connectivity() {
+        let positions = vec![0.0, 0.0, 0.0,  1.0, 0.0, 0.0,  0.0, 1.0, 0.0,  0.0, -1.0, 0.0,  0.0, 0.0, 1.0,  -1.0, 0.0, 0.0];
+        let repair = |indices: Vec<u32>| {
+            let (mesh, report) = MeshBuilder::<()>::new().with_positions(positions.clone()).with_indices(indices).build_with_repair().unwrap();
+            mesh.is_valid().unwrap();
+            (mesh, report)
+        };
+
+        // A face with the opposite orientation of its neighbour
+        let (mesh, report) = repair(vec![0, 1, 2,  0, 1, 3]);
+        assert_eq!(mesh.num_faces(), 1);
+        assert_eq!(report.non_manifold_faces, vec![1]);
+        assert_eq!(report.unreferenced_vertices, vec![3, 4, 5]);
+
+        // A duplicated face
+        let (mesh, report) = repair(vec![0, 1, 2,  0, 1, 2]);
+        assert_eq!(mesh.num_faces(), 1);
+        assert_eq!(report.non_manifold_faces, vec![1]);
+
+        // Two triangles which only share a vertex
+        let (mesh, report) = repair(vec![0, 1, 2,  0, 3, 4]);
+        assert_eq!(mesh.num_faces(), 1);
+        assert!(report.non_manifold_faces.is_empty());
+        assert_eq!(report.non_manifold_vertex_faces, vec![1]);
+        assert_eq!(report.unreferenced_vertices, vec![3, 4, 5]);
+
+        // Two fans around vertex 0, where the fan containing the first face is kept
+        let (mesh, report) = repair(vec![0, 3, 4,  0, 1, 2,  0, 2, 5]);
+        assert_eq!(mesh.num_faces(), 1);
+        assert_eq!(report.non_manifold_vertex_faces, vec![1, 2]);
+        assert_eq!(report.unreferenced_vertices, vec![1, 2, 5]);
+    }
+
+    #[test]
+    fn test_with_welding() {
+        let original = MeshBuilder::<()>::new().icosahedron().build().unwrap();
+        let indices = original.indices_buffer();
+        let original_positions = original.positions_buffer();
+        // Each triangle gets its own slightly displaced vertices
+        let mut positions = Vec::new();
+        for (corner, index) in indices.iter().enumerate() {
+            for i in 0..3 {
+                positions.push(original_positions[3 * *index as usize + i] + 0.00001 * ((corner + i) % 3) as f64);
+            }
+        }
+        // A sliver triangle which collapses when welded
+        let sliver = [positions[0], positions[1], positions[2],  positions[0] + 0.00001, positions[1], positions[2],
+                      positions[3], positions[4], positions[5]];
+        positions.extend_from_slice(&sliver);
+        let num_faces = positions.len() / 9;
+
+        let builder = || MeshBuilder::<usize>::new().with_positions(positions.clone()).with_tags((0..num_faces).collect())
+            .with_corner_attribute("corner", (0..3 * num_faces).collect(), |values: &[(usize, f64)]| values[0].0)
+            .with_welding(0.001);
+        assert!(matches!(builder().build(), Err(Error::DegenerateFace { .. })));
+        let (mesh, report) = builder().build_with_repair().unwrap();
+        assert_eq!(report.welded_faces, vec![num_faces - 1]);
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        assert_eq!(mesh.num_vertices(), original.num_vertices());
+        assert_eq!(mesh.num_faces(), original.num_faces());
+        for face_id in mesh.face_iter() {
+            let face = mesh.face_tag(face_id);
+            let mut corners = mesh.face_halfedge_iter(face_id).map(|halfedge_id| mesh.corner_attribute::<usize>("corner", halfedge_id).unwrap()).collect::<Vec<_>>();
+            corners.sort_unstable();
+            assert_eq!(corners, vec![3 * face, 3 * face + 1, 3 * face + 2]);
+        }
+
+        assert_eq!(MeshBuilder::<()>::new().unconnected_cube().build().unwrap().num_vertices(), 36);
+        assert_eq!(MeshBuilder::<()>::new().unconnected_cube().with_welding(0.0).build().unwrap().num_vertices(), 8);
+
+        let mesh = MeshBuilder::<()>::new().with_positions(vec![1e6, 0.0, 0.0,  0.0, 1e6, 0.0,  0.0, 0.0, 1e6]).with_welding(1e-14).build().unwrap();
+        assert_eq!(mesh.num_vertices(), 3);
+        for tolerance in [-1.0, f64::NAN, f64::INFINITY].iter() {
+            let result = MeshBuilder::<()>::new().unconnected_cube().with_welding(*tolerance).build();
+            assert!(matches!(result, Err(Error::InvalidParameter { .. })));
+        }
+    }
+
+    #[test]
+    fn test_primitives() {
+        use crate::mesh::math::{Vec3, InnerSpace};
+        // The signed volume is positive for a closed mesh with the faces oriented outwards
+        let volume = |mesh: &Mesh<()>| mesh.face_iter().map(|face_id| {
+            let (p0, p1, p2) = mesh.face_positions(face_id);
+            p0.dot(p1.cross(p2)) / 6.0
+        }).sum::<f64>();
+        let pi = std::f64::consts::PI;
+
+        let closed = [
+            (MeshBuilder::<()>::new().uv_sphere(8, 16), 2 + 7 * 16, 4.0 / 3.0 * pi),
+            (MeshBuilder::<()>::new().geodesic_sphere(2), 162, 4.0 / 3.0 * pi),
+            (MeshBuilder::<()>::new().torus(2.0, 0.5, 16, 8), 16 * 8, 2.0 * pi * pi * 2.0 * 0.25),
+            (MeshBuilder::<()>::new().cone(16), 2 + 16, pi / 3.0),
+            (MeshBuilder::<()>::new().capsule(16, 4), 2 + 8 * 16, pi + 4.0 / 3.0 * pi),
+        ];
+        for (builder, num_vertices, expected_volume) in closed {
+            let mesh = builder.build().unwrap();
+            mesh.is_valid().unwrap();
+            assert!(mesh.is_closed());
+            assert_eq!(mesh.num_vertices(), num_vertices);
+            assert!((volume(&mesh) - expected_volume).abs() < 0.15 * expected_volume);
+        }
+
+        for mesh in vec![MeshBuilder::<()>::new().disk(4, 16).build().unwrap(), MeshBuilder::<()>::new().grid(3, 5).build().unwrap()] {
+            mesh.is_valid().unwrap();
+            assert!(!mesh.is_closed());
+            assert!(mesh.face_iter().all(|face_id| (mesh.face_normal(face_id) - Vec3::unit_z()).magnitude() < 0.00001));
+        }
+        assert_eq!(MeshBuilder::<()>::new().grid(3, 5).build().unwrap().num_faces(), 30);
+        assert_eq!(MeshBuilder::<()>::new().disk(4, 16).build().unwrap().num_vertices(), 1 + 4 * 16);
+
+        let invalid = vec![MeshBuilder::<()>::new().cylinder(1, 16), MeshBuilder::<()>::new().uv_sphere(8, 2), MeshBuilder::<()>::new().torus(2.0, 0.5, 2, 2),
+            MeshBuilder::<()>::new().cone(2), MeshBuilder::<()>::new().capsule(16, 0), MeshBuilder::<()>::new().disk(0, 16), MeshBuilder::<()>::new().grid(3, 0)];
+        for builder in invalid {
+            assert!(matches!(builder.build(), Err(Error::InvalidParameter { .. })));
+        }
+        match MeshBuilder::<()>::new().torus(2.0, 0.5, 2, 2).build() {
+            Err(Error::InvalidParameter { message }) => assert!(message.contains("major_subdivisions")),
+            _ => panic!("Expected an invalid parameter error")
+        }
+    }
+
+    #[test]
+    fn test_parametric_surface() {
+        use crate::mesh::math::{vec3, InnerSpace};
+        let mesh = MeshBuilder::<()>::new().parametric_surface(4, 3, false, false, |u, v| vec3(u, v, u * v)).build().unwrap();
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.num_vertices(), 5 * 4);
+        assert_eq!(mesh.num_faces(), 2 * 4 * 3);
+        assert!(mesh.face_iter().all(|face_id| mesh.face_normal(face_id).z > 0.0));
+
+        // A cylinder which wraps around the axis, where the normal points outwards
+        let mesh = MeshBuilder::<()>::new().parametric_surface(16, 2, true, false, |u, v| {
+            let angle = 2.0 * std::f64::consts::PI * u;
+            vec3(angle.cos(), angle.sin(), v)
+        }).build().unwrap();
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.num_vertices(), 16 * 3);
+        assert_eq!(mesh.num_faces(), 2 * 16 * 2);
+        assert!(mesh.face_iter().all(|face_id| {
+            let center = mesh.face_center(face_id);
+            mesh.face_normal(face_id).dot(vec3(center.x, center.y, 0.0)) > 0.0
+        }));
+
+        let result = MeshBuilder::<()>::new().parametric_surface(2, 2, true, false, |u, v| vec3(u, v, 0.0)).build();
+        assert!(matches!(result, Err(Error::InvalidParameter { .. })));
+        let result = MeshBuilder::<()>::new().heightfield(&[vec![0.0, 1.0]], 1.0).build();
+        assert!(matches!(result, Err(Error::InvalidParameter { .. })));
+        let result = MeshBuilder::<()>::new().heightfield(&[vec![0.0, 1.0], vec![0.0]], 1.0).build();
+        assert!(matches!(result, Err(Error::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn test_extrude_lathe_and_sweep() {
+        use crate::mesh::math::{vec2, vec3, InnerSpace};
+        let volume = |mesh: &Mesh<()>| mesh.face_iter().map(|face_id| {
+            let (p0, p1, p2) = mesh.face_positions(face_id);
+            p0.dot(p1.cross(p2)) / 6.0
+        }).sum::<f64>();
+        let pi = std::f64::consts::PI;
+        let square = |size: f64| vec![vec2(-size, -size), vec2(size, -size), vec2(size, size), vec2(-size, size)];
+        let circle = |radius: f64, count: usize| (0..count).map(|i| {
+            let angle = 2.0 * pi * i as f64 / count as f64;
+            vec2(radius * angle.cos(), radius * angle.sin())
+        }).collect::<Vec<_>>();
+        let reversed = |points: Vec<_>| points.into_iter().rev().collect::<Vec<_>>();
+
+        let cases = [
+            (MeshBuilder::<()>::new().extrude(&square(2.0), &[square(1.0)], vec3(0.0, 0.0, 1.0)), 16, 12.0),
+            // An oblique and downwards extrusion with holes of both orientations
+            (MeshBuilder::<()>::new().extrude(&reversed(square(2.0)), &[circle(0.5, 8).iter().map(|p| p + vec2(1.0, 1.0)).collect(), reversed(square(0.5))],
+                                              vec3(1.0, 0.5, -2.0)), 32, 2.0 * (16.0 - 1.0 - 0.5 * 8.0 * 0.25 * (pi / 4.0).sin())),
+            (MeshBuilder::<()>::new().lathe(&[vec2(0.0, 1.0), vec2(1.0, 1.0)], 32), 2 + 2 * 32, 16.0 * (pi / 16.0).sin()),
+            (MeshBuilder::<()>::new().lathe(&[vec2(1.0, 0.0), vec2(0.5, 1.0), vec2(0.0, 1.0)], 32), 1 + 2 * 32 + 1, 16.0 * (pi / 16.0).sin() * (0.5 + 0.5 / 3.0)),
+            (MeshBuilder::<()>::new().sweep(&square(0.5), &[], &[vec3(0.0, 0.0, 0.0), vec3(1.0, 2.0, 2.0)], false), 8, 3.0),
+            (MeshBuilder::<()>::new().sweep(&circle(0.5, 16), &[circle(0.25, 16)], &[vec3(0.0, 0.0, 0.0), vec3(0.0, 0.0, 1.0)], false), 64, 0.75 * 8.0 * 0.25 * (pi / 8.0).sin()),
+            (MeshBuilder::<()>::new().sweep(&square(0.5), &[], &circle(3.0, 64).iter().map(|p| vec3(p.x, p.y, 0.0)).collect::<Vec<_>>(), true), 4 * 64, 2.0 * pi * 3.0),
+        ];
+        for (builder, num_vertices, expected_volume) in cases {
+            let mesh = builder.build().unwrap();
+            mesh.is_valid().unwrap();
+            assert!(mesh.is_closed());
+            assert_eq!(mesh.num_vertices(), num_vertices);
+            assert!((volume(&mesh) - expected_volume).abs() < 0.01 * expected_volume, "{} != {}", volume(&mesh), expected_volume);
+        }
+
+        // A closed path which is not planar, so the frames twist along the path
+        let path = (0..32).map(|i| {
+            let angle = 2.0 * pi * i as f64 / 32.0;
+            vec3(3.0 * angle.cos(), 3.0 * angle.sin(), (2.0 * angle).sin())
+        }).collect::<Vec<_>>();
+        let mesh = MeshBuilder::<()>::new().sweep(&square(0.2), &[], &path, true).build().unwrap();
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        assert!(volume(&mesh) > 0.0);
+        let invalid = vec![MeshBuilder::<()>::new().extrude(&square(1.0), &[], vec3(1.0, 0.0, 0.0)),
+            MeshBuilder::<()>::new().extrude(&square(1.0), &[vec![vec2(0.0, 0.0), vec2(0.1, 0.0)]], vec3(0.0, 0.0, 1.0)),
+            MeshBuilder::<()>::new().lathe(&[vec2(0.0, 1.0)], 16),
+            MeshBuilder::<()>::new().lathe(&[vec2(0.0, 1.0), vec2(1.0, 1.0)], 2),
+            MeshBuilder::<()>::new().sweep(&square(0.1), &[], &[vec3(0.0, 0.0, 0.0), vec3(0.0, 0.0, 0.0)], false),
+            MeshBuilder::<()>::new().sweep(&square(0.1), &[], &[vec3(0.0, 0.0, 0.0), vec3(0.0, 0.0, 1.0)], true)];
+        for builder in invalid {
+            assert!(matches!(builder.build(), Err(Error::InvalidParameter { .. })));
+        }
+    }
+
+    #[test]
+    fn test_isosurface() {
+        use crate::mesh::math::{vec3, InnerSpace};
+        let volume = |mesh: &Mesh<()>| mesh.face_iter().map(|face_id| {
+            let (p0, p1, p2) = mesh.face_positions(face_id);
+            p0.dot(p1.cross(p2)) / 6.0
+        }).sum::<f64>();
+        let pi = std::f64::consts::PI;
+
+        let mesh = MeshBuilder::<()>::new().isosurface(vec3(-1.2, -1.2, -1.2), vec3(1.2, 1.2, 1.2), [6, 6, 6], 0.0, |p| p.magnitude() - 1.0).build().unwrap();
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        assert!((volume(&mesh) - 4.0 / 3.0 * pi).abs() < 0.1 * 4.0 / 3.0 * pi);
+        assert!(mesh.face_iter().all(|face_id| mesh.face_normal(face_id).dot(mesh.face_center(face_id)) > 0.0));
+
+        // A torus has Euler characteristic 0
+        let mesh = MeshBuilder::<()>::new().isosurface(vec3(-1.5, -1.5, -0.5), vec3(1.5, 1.5, 0.5), [10, 10, 3], 0.0, |p| {
+            (vec3(p.x, p.y, 0.0).magnitude() - 1.0).hypot(p.z) - 0.4
+        }).build().unwrap();
+        assert!(mesh.is_closed());
+        assert_eq!(mesh.num_vertices() as i64 - mesh.num_edges() as i64 + mesh.num_faces() as i64, 0);
+
+        // A random field which reaches the boundary of the grid
+        let mut random = crate::test_utils::random(7);
+        let values = (0..5 * 4 * 3).map(|_| random() - 0.5).collect::<Vec<_>>();
+        let mesh = MeshBuilder::<()>::new().isosurface_from_grid(&values, [5, 4, 3], vec3(0.0, 0.0, 0.0), 0.5, 0.0).build().unwrap();
+        mesh.is_valid().unwrap();
+        assert!(mesh.is_closed());
+        assert!(volume(&mesh) > 0.0);
+
+        let invalid = vec![MeshBuilder::<()>::new().isosurface_from_grid(&[1.0; 8], [2, 2, 2], vec3(0.0, 0.0, 0.0), 1.0, 0.0),
+            MeshBuilder::<()>::new().isosurface_from_grid(&[-1.0; 8], [2, 2, 3], vec3(0.0, 0.0, 0.0), 1.0, 0.0),
+            MeshBuilder::<()>::new().isosurface(vec3(-1.0, -1.0, -1.0), vec3(1.0, 1.0, 1.0), [4, 0, 4], 0.0, |p| p.magnitude() - 0.5),
+            MeshBuilder::<()>::new().isosurface(vec3(-1.0, -1.0, -1.0), vec3(1.0, 1.0, 1.0), [4, 4, 4], 0.0, |p| (p.magnitude() - 0.5).sqrt()),
+            MeshBuilder::<()>::new().isosurface_from_grid(&[-1.0, f64::NAN, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0], [2, 2, 2], vec3(0.0, 0.0, 0.0), 1.0, 0.0)];
+        for builder in invalid {
+            assert!(matches!(builder.build(), Err(Error::InvalidParameter { .. })));
+        }
+    }
+
+    #[test]
+    fn test_constrained_delaunay() {
+        use crate::mesh::math::{vec2, vec3};
+        let area = |mesh: &Mesh<()>| mesh.face_iter().map(|face_id| mesh.face_area(face_id)).sum::<f64>();
+
+        // A terrain keeps the heights of the points
+        let mut points = Vec::new();
+        for i in 0..6 {
+            for j in 0..6 {
+                points.push(vec3(i as f64, j as f64, (i as f64 * 0.7).sin() + (j as f64 * 0.4).cos()));
+            }
+        }
+        let mesh = MeshBuilder::<()>::new().delaunay(&points).unwrap().build().unwrap();
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.num_vertices(), 36);
+        assert_eq!(mesh.num_faces(), 50);
+        assert!(mesh.vertex_iter().all(|vertex_id| points.contains(&mesh.vertex_position(vertex_id))));
+        assert!(mesh.face_iter().all(|face_id| mesh.face_normal(face_id).z > 0.0));
+
+        // A segment across the terrain, which crosses many edges of the Delaunay triangulation
+        let outline = vec![[0, 5], [5, 35], [35, 30], [30, 0]];
+        let mesh = MeshBuilder::<()>::new().constrained_delaunay(&points, &[outline.clone(), vec![[0, 33]]].concat(), &[]).unwrap().build().unwrap();
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.num_faces(), 50);
+        assert!(mesh.edge_iter().any(|halfedge_id| {
+            let (a, b) = mesh.edge_positions(halfedge_id);
+            (a == points[0] && b == points[33]) || (a == points[33] && b == points[0])
+        }));
+
+        // Segments which cross each other, an open polyline and a segment to a point which does not exist
+        assert!(MeshBuilder::<()>::new().constrained_delaunay(&points, &[outline.clone(), vec![[0, 35], [5, 30]]].concat(), &[]).is_err());
+        assert!(MeshBuilder::<()>::new().constrained_delaunay(&points, &[[0, 5], [5, 35], [35, 30]], &[]).is_err());
+        assert!(MeshBuilder::<()>::new().constrained_delaunay(&points, &[outline, vec![[0, 36]]].concat(), &[]).is_err());
+
+        // A square with a segment through a point on its boundary and a square hole, given clockwise
+        let points = vec![vec3(0.0, 0.0, 0.0), vec3(3.0, 0.0, 0.0), vec3(3.0, 3.0, 0.0), vec3(0.0, 3.0, 0.0), vec3(1.5, 0.0, 0.0),
+                          vec3(1.0, 1.0, 0.0), vec3(1.0, 2.0, 0.0), vec3(2.0, 2.0, 0.0), vec3(2.0, 1.0, 0.0)];
+        let segments = vec![[0, 1], [1, 2], [2, 3], [3, 0], [5, 6], [6, 7], [7, 8], [8, 5]];
+        let mesh = MeshBuilder::<()>::new().constrained_delaunay(&points, &segments, &[vec2(1.5, 1.5)]).unwrap().build().unwrap();
+        mesh.is_valid().unwrap();
+        assert_eq!(mesh.num_vertices(), 9);
+        assert!((area(&mesh) - 8.0).abs() < 0.000001);
+        assert!(mesh.face_iter().all(|face_id| {
+            let center = mesh.face_center(face_id);
+            !(1.0..=2.0).contains(&center.x) || !(1.0..=2.0).contains(&center.y)
+        }));
+
+        assert!(matches!(MeshBuilder::<()>::new().delaunay(&[vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0), vec3(2.0, 0.0, 0.0)]), Err(Error::InvalidPositions { .. })));
+    }
+}
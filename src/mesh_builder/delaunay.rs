@@ -0,0 +1,306 @@
+//!
+//! Delaunay and constrained Delaunay triangulation of points in the plane, see [MeshBuilder::constrained_delaunay](crate::mesh_builder::MeshBuilder::constrained_delaunay).
+//!
+
+use crate::mesh::Mesh;
+use crate::mesh::ids::{HalfEdgeID, VertexID};
+use crate::mesh::math::{Vec2, Vec3};
+use crate::mesh_builder::Error;
+use crate::mesh_builder::triangulation::cross;
+use std::collections::{HashMap, HashSet};
+
+/// Positive if `d` is inside the circumcircle of the counterclockwise triangle `a`, `b`, `c`.
+fn in_circle(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> f64
+{
+    let (a, b, c) = (a - d, b - d, c - d);
+    let (a2, b2, c2) = (a.x * a.x + a.y * a.y, b.x * b.x + b.y * b.y, c.x * c.x + c.y * c.y);
+    a.x * (b.y * c2 - b2 * c.y) - a.y * (b.x * c2 - b2 * c.x) + a2 * (b.x * c.y - b.y * c.x)
+}
+
+///
+/// Computes the constrained Delaunay triangulation of the points projected onto the xy-plane and returns the positions and indices of the triangles,
+/// which are oriented counterclockwise in the xy-plane. The triangulation contains the segments as edges and is otherwise Delaunay.
+/// If there are segments, the triangles outside the segments, i.e. which can be reached from the convex hull without crossing a segment, are removed.
+/// The triangles which can be reached from a hole point without crossing a segment are also removed.
+/// Points with the same x- and y-coordinates as a previous point are ignored and the segments must not cross each other,
+/// but a segment which passes through a point is split at that point.
+/// Returns an error if the points are all on a line, a segment refers to a point which does not exist, the segments cross each other
+/// or no triangles are left.
+///
+pub(super) fn constrained_delaunay(points: &[Vec3], segments: &[[u32; 2]], holes: &[Vec2]) -> Result<(Vec<f64>, Vec<u32>), Error>
+{
+    if let Some(index) = segments.iter().flatten().find(|index| **index as usize >= points.len()) {
+        return Err(Error::InvalidSegments { message: format!("A segment refers to point {}, but there are only {} points", index, points.len()) });
+    }
+
+    // Remove duplicated points and sort the remaining points by x and then y
+    let mut order = (0..points.len()).filter(|i| points[*i].x.is_finite() && points[*i].y.is_finite()).collect::<Vec<_>>();
+    order.sort_by(|a, b| (points[*a].x, points[*a].y).partial_cmp(&(points[*b].x, points[*b].y)).unwrap());
+    let mut unique = vec![usize::MAX; points.len()];
+    let mut sorted: Vec<usize> = Vec::with_capacity(order.len());
+    for i in order {
+        match sorted.last() {
+            Some(last) if points[*last].x == points[i].x && points[*last].y == points[i].y => unique[i] = *last,
+            _ => {
+                unique[i] = i;
+                sorted.push(i);
+            }
+        }
+    }
+    let position = |i: usize| Vec2::new(points[i].x, points[i].y);
+    let min = sorted.iter().fold(Vec2::new(f64::MAX, f64::MAX), |min, i| Vec2::new(min.x.min(points[*i].x), min.y.min(points[*i].y)));
+    let max = sorted.iter().fold(Vec2::new(f64::MIN, f64::MIN), |max, i| Vec2::new(max.x.max(points[*i].x), max.y.max(points[*i].y)));
+    let scale = (max.x - min.x).max(max.y - min.y);
+    let epsilon = 1.0e-12 * scale * scale;
+
+    let indices = sweep_triangulation(&sorted.iter().map(|i| position(*i)).collect::<Vec<_>>(), epsilon)
+        .ok_or_else(|| Error::InvalidPositions { message: "Cannot triangulate points which are all on a line".to_string() })?;
+    let num_faces = indices.len() / 3;
+    let mut mesh = Mesh::<()>::new(indices, vec![(); num_faces], sorted.iter().flat_map(|i| vec![points[*i].x, points[*i].y, 0.0]).collect());
+    let vertex_ids = mesh.vertex_iter().collect::<Vec<_>>();
+    let vertex_of_point = sorted.iter().enumerate().map(|(vertex, point)| (*point, vertex_ids[vertex])).collect::<HashMap<_, _>>();
+    let point_of_vertex = vertex_of_point.iter().map(|(point, vertex_id)| (*vertex_id, *point)).collect::<HashMap<_, _>>();
+    let vertex_position = |vertex_id: VertexID| position(point_of_vertex[&vertex_id]);
+
+    let mut constraints = HashSet::new();
+    flip_to_delaunay(&mut mesh, &constraints, &vertex_position, epsilon * scale * scale);
+
+    for segment in segments.iter() {
+        let (u, v) = (unique[segment[0] as usize], unique[segment[1] as usize]);
+        if u == v || u == usize::MAX || v == usize::MAX {
+            continue;
+        }
+        // A segment which passes through other points is split at those points
+        let (pu, pv) = (position(u), position(v));
+        let direction = pv - pu;
+        let length2 = direction.x * direction.x + direction.y * direction.y;
+        let mut on_segment = sorted.iter().filter(|i| **i != u && **i != v).filter_map(|i| {
+            let p = position(*i);
+            let t = ((p - pu).x * direction.x + (p - pu).y * direction.y) / length2;
+            if t > 0.0 && t < 1.0 && cross(pu, pv, p).abs() <= epsilon * length2.sqrt() / scale { Some((t, *i)) } else { None }
+        }).collect::<Vec<_>>();
+        on_segment.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        let chain = std::iter::once(u).chain(on_segment.into_iter().map(|(_, i)| i)).chain(std::iter::once(v)).collect::<Vec<_>>();
+        for pair in chain.windows(2) {
+            let (a, b) = (vertex_of_point[&pair[0]], vertex_of_point[&pair[1]]);
+            if !insert_segment(&mut mesh, a, b, &constraints, &vertex_position, epsilon) {
+                return Err(Error::InvalidSegments {
+                    message: format!("The segment from point {} to {} crosses another segment", segment[0], segment[1])
+                });
+            }
+            constraints.insert((a.min(b), a.max(b)));
+        }
+    }
+    flip_to_delaunay(&mut mesh, &constraints, &vertex_position, epsilon * scale * scale);
+
+    // Remove the triangles outside the segments and in the holes by flooding from the boundary and the hole points
+    let is_constrained = |mesh: &Mesh<()>, halfedge_id: HalfEdgeID| {
+        constraints.contains(&mesh.ordered_edge_vertices(halfedge_id))
+    };
+    let mut removed = HashSet::new();
+    let mut stack = Vec::new();
+    if !segments.is_empty() {
+        for halfedge_id in mesh.halfedge_iter() {
+            let mut walker = mesh.walker_from_halfedge(halfedge_id);
+            if walker.face_id().is_none() && !is_constrained(&mesh, halfedge_id) {
+                stack.push(walker.as_twin().face_id().unwrap());
+            }
+        }
+    }
+    for hole in holes.iter() {
+        if let Some(face_id) = mesh.face_iter().find(|face_id| {
+            let (a, b, c) = mesh.face_vertices(*face_id);
+            let (a, b, c) = (vertex_position(a), vertex_position(b), vertex_position(c));
+            cross(a, b, *hole) >= 0.0 && cross(b, c, *hole) >= 0.0 && cross(c, a, *hole) >= 0.0
+        }) {
+            stack.push(face_id);
+        }
+    }
+    while let Some(face_id) = stack.pop() {
+        if !removed.insert(face_id) {
+            continue;
+        }
+        for halfedge_id in mesh.face_halfedge_iter(face_id) {
+            if !is_constrained(&mesh, halfedge_id) {
+                if let Some(neighbour) = mesh.walker_from_halfedge(halfedge_id).as_twin().face_id() {
+                    stack.push(neighbour);
+                }
+            }
+        }
+    }
+
+    // Only the points which are corners of the remaining triangles are kept, with their original z-coordinate
+    let mut vertices = HashMap::new();
+    let mut positions = Vec::new();
+    let mut indices = Vec::new();
+    for face_id in mesh.face_iter().filter(|face_id| !removed.contains(face_id)) {
+        let (a, b, c) = mesh.face_vertices(face_id);
+        for vertex_id in [a, b, c].iter() {
+            indices.push(*vertices.entry(*vertex_id).or_insert_with(|| {
+                let p = points[point_of_vertex[vertex_id]];
+                positions.extend_from_slice(&[p.x, p.y, p.z]);
+                (positions.len() / 3 - 1) as u32
+            }));
+        }
+    }
+    if indices.is_empty() {
+        return Err(Error::InvalidSegments { message: "No triangles are left, so the segments do not enclose an area outside the holes".to_string() });
+    }
+    Ok((positions, indices))
+}
+
+///
+/// Triangulates the convex hull of the points, which must be sorted by x and then y and contain no duplicates,
+/// by adding the points one at a time and connecting each point to the edges of the hull which it can see.
+/// Returns `None` if the points are all on a line.
+///
+fn sweep_triangulation(points: &[Vec2], epsilon: f64) -> Option<Vec<u32>>
+{
+    // The first point which is not on the line through the first two points
+    let first = (2..points.len()).find(|i| cross(points[0], points[1], points[*i]).abs() > epsilon)?;
+    let mut indices = Vec::new();
+    // The points before the first point are on a line, so they are connected to it by a fan of triangles
+    let counterclockwise = cross(points[0], points[1], points[first]) > 0.0;
+    for i in 0..first - 1 {
+        let (a, b) = if counterclockwise { (i, i + 1) } else { (i + 1, i) };
+        indices.extend_from_slice(&[a as u32, b as u32, first as u32]);
+    }
+    // The hull in counterclockwise order
+    let mut hull = if counterclockwise {
+        (0..first).chain(std::iter::once(first)).collect::<Vec<_>>()
+    } else {
+        std::iter::once(first).chain((0..first).rev()).collect::<Vec<_>>()
+    };
+
+    for point in first + 1..points.len() {
+        let p = points[point];
+        let n = hull.len();
+        // The point is to the right of the visible edges, which are consecutive since the hull is convex
+        let is_visible = |i: usize| cross(points[hull[i]], points[hull[(i + 1) % n]], p) < -epsilon;
+        let start = match (0..n).find(|i| is_visible(*i) && !is_visible((*i + n - 1) % n)) {
+            Some(start) => start,
+            None => continue
+        };
+        let mut end = start;
+        while is_visible(end % n) {
+            let (a, b) = (hull[end % n], hull[(end + 1) % n]);
+            indices.extend_from_slice(&[b as u32, a as u32, point as u32]);
+            end += 1;
+        }
+        // Replace the hull vertices between the first and last visible edge by the point
+        let mut new_hull = Vec::with_capacity(n + 1);
+        for (i, vertex) in hull.iter().enumerate() {
+            let offset = (i + n - start) % n;
+            if offset == 0 {
+                new_hull.push(*vertex);
+                new_hull.push(point);
+            } else if offset >= end - start {
+                new_hull.push(*vertex);
+            }
+        }
+        hull = new_hull;
+    }
+    Some(indices)
+}
+
+/// Returns the vertices of the edge and the vertices opposite to it in the two adjacent faces, or `None` if the edge is on the boundary.
+fn quad(mesh: &Mesh<()>, halfedge_id: HalfEdgeID) -> Option<(VertexID, VertexID, VertexID, VertexID)>
+{
+    let mut walker = mesh.walker_from_halfedge(halfedge_id);
+    walker.face_id()?;
+    let b = walker.vertex_id().unwrap();
+    let c = walker.as_next().vertex_id().unwrap();
+    let a = walker.as_next().vertex_id().unwrap();
+    walker.as_next().as_twin().face_id()?;
+    let d = walker.as_next().vertex_id().unwrap();
+    Some((a, b, c, d))
+}
+
+///
+/// Flips the edges which are not constrained until the triangulation is (constrained) Delaunay,
+/// i.e. no point is inside the circumcircle of a triangle on the other side of an edge which is not constrained (Lawson's algorithm).
+///
+fn flip_to_delaunay(mesh: &mut Mesh<()>, constraints: &HashSet<(VertexID, VertexID)>, position: &dyn Fn(VertexID) -> Vec2, epsilon: f64)
+{
+    let should_flip = |mesh: &Mesh<()>, halfedge_id: HalfEdgeID| match quad(mesh, halfedge_id) {
+        Some((a, b, c, d)) => !constraints.contains(&(a.min(b), a.max(b)))
+            && in_circle(position(a), position(b), position(c), position(d)) > epsilon,
+        None => false
+    };
+    let mut stack = mesh.edge_iter().collect::<Vec<_>>();
+    while let Some(halfedge_id) = stack.pop() {
+        if should_flip(mesh, halfedge_id) && mesh.flip_edge(halfedge_id).is_ok() {
+            let mut walker = mesh.walker_from_halfedge(halfedge_id);
+            stack.push(walker.as_next().halfedge_id().unwrap());
+            stack.push(walker.as_next().halfedge_id().unwrap());
+            stack.push(walker.as_next().as_twin().as_next().halfedge_id().unwrap());
+            stack.push(walker.as_next().halfedge_id().unwrap());
+        }
+    }
+}
+
+///
+/// Makes the segment between the two vertices an edge by flipping the edges crossing it.
+/// An edge crossing the segment is only flipped if the two adjacent triangles form a convex quadrilateral,
+/// which is always the case for at least one of the crossing edges.
+/// Returns whether the segment is an edge afterwards, which is not the case if it crosses a constrained edge.
+///
+fn insert_segment(mesh: &mut Mesh<()>, u: VertexID, v: VertexID, constraints: &HashSet<(VertexID, VertexID)>,
+                  position: &dyn Fn(VertexID) -> Vec2, epsilon: f64) -> bool
+{
+    let (pu, pv) = (position(u), position(v));
+    let crosses = |a: Vec2, b: Vec2| cross(pu, pv, a) * cross(pu, pv, b) < -epsilon * epsilon && cross(a, b, pu) * cross(a, b, pv) < -epsilon * epsilon;
+    loop {
+        let crossing = mesh.edge_iter().filter(|halfedge_id| {
+            let (a, b) = mesh.ordered_edge_vertices(*halfedge_id);
+            crosses(position(a), position(b))
+        }).collect::<Vec<_>>();
+        if crossing.is_empty() {
+            return mesh.connecting_edge(u, v).is_some();
+        }
+        if crossing.iter().any(|halfedge_id| constraints.contains(&mesh.ordered_edge_vertices(*halfedge_id))) {
+            return false;
+        }
+        let mut flipped = false;
+        for halfedge_id in crossing {
+            if let Some((a, b, c, d)) = quad(mesh, halfedge_id) {
+                let (pa, pb, pc, pd) = (position(a), position(b), position(c), position(d));
+                let is_convex = cross(pc, pd, pa) * cross(pc, pd, pb) < 0.0 && cross(pa, pb, pc) * cross(pa, pb, pd) < 0.0;
+                if crosses(pa, pb) && is_convex && mesh.flip_edge(halfedge_id).is_ok() {
+                    flipped = true;
+                }
+            }
+        }
+        // None of the crossing edges can be flipped, which only happens for degenerate input
+        if !flipped {
+            return false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::math::{vec2, vec3};
+
+    #[test]
+    fn test_delaunay_empty_circumcircles() {
+        let mut random = crate::test_utils::random(3);
+        let points = (0..100).map(|_| vec3(random(), random(), random())).collect::<Vec<_>>();
+        let (positions, indices) = constrained_delaunay(&points, &[], &[]).unwrap();
+        assert_eq!(positions.len(), 3 * points.len());
+
+        let position = |index: u32| vec2(positions[3 * index as usize], positions[3 * index as usize + 1]);
+        for triangle in indices.chunks(3) {
+            let (a, b, c) = (position(triangle[0]), position(triangle[1]), position(triangle[2]));
+            assert!(cross(a, b, c) > 0.0);
+            assert!(points.iter().all(|p| in_circle(a, b, c, vec2(p.x, p.y)) < 1.0e-10));
+        }
+    }
+
+    #[test]
+    fn test_constrained_delaunay_collinear() {
+        let points = [vec3(0.0, 0.0, 0.0), vec3(1.0, 1.0, 0.0), vec3(2.0, 2.0, 0.0), vec3(1.0, 1.0, 1.0)];
+        assert!(constrained_delaunay(&points, &[], &[]).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//!
+//! Parser for the .gltf and .glb file formats, see [MeshBuilder::with_gltf](crate::mesh_builder::MeshBuilder::with_gltf).
+//!
+
+use crate::mesh_builder::Error;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The triangles of the first mesh in a glTF file.
+pub(super) struct Gltf {
+    pub positions: Vec<f64>,
+    pub indices: Vec<u32>,
+    /// The index of the primitive that each triangle belongs to.
+    pub primitives: Vec<usize>
+}
+
+const TRIANGLES: u64 = 4;
+
+fn invalid(message: String) -> Error {
+    Error::InvalidFile { message }
+}
+
+///
+/// Parses the .gltf or .glb file at the given path and returns the positions and indices of all triangle primitives of the first mesh.
+/// Primitives which share the same position accessor also share vertices.
+///
+pub(super) fn parse(path: &Path) -> Result<Gltf, Error> {
+    let bytes = std::fs::read(path)?;
+    let (document, binary_chunk) = if bytes.starts_with(b"glTF") { parse_glb(&bytes)? } else { (bytes.as_slice(), None) };
+    let document: Value = serde_json::from_slice(document).map_err(|e| invalid(format!("Invalid gltf json: {}", e)))?;
+
+    let mut buffers = Vec::new();
+    for buffer in array(&document, "buffers") {
+        buffers.push(match buffer["uri"].as_str() {
+            Some(uri) if uri.starts_with("data:") => {
+                let data = uri.find(',').map(|comma| &uri[comma + 1..]).ok_or_else(|| invalid("Invalid data uri in gltf buffer".to_string()))?;
+                base64::decode(data).map_err(|e| invalid(format!("Invalid base64 data in gltf buffer: {}", e)))?
+            },
+            Some(uri) => std::fs::read(path.parent().unwrap_or_else(|| Path::new("")).join(uri))?,
+            None => binary_chunk.ok_or_else(|| invalid("A gltf buffer without uri requires a .glb file".to_string()))?.to_vec()
+        });
+    }
+
+    let mesh = array(&document, "meshes").first().ok_or_else(|| invalid("The gltf file does not contain any meshes".to_string()))?;
+    let mut gltf = Gltf { positions: Vec::new(), indices: Vec::new(), primitives: Vec::new() };
+    let mut vertex_offsets = HashMap::new();
+    for (primitive_index, primitive) in array(mesh, "primitives").iter().enumerate() {
+        if primitive["mode"].as_u64().unwrap_or(TRIANGLES) != TRIANGLES {
+            continue;
+        }
+        let position_accessor = primitive["attributes"]["POSITION"].as_u64()
+            .ok_or_else(|| invalid("A gltf primitive does not have a POSITION attribute".to_string()))? as usize;
+        if accessor(&document, position_accessor)?["type"].as_str() != Some("VEC3") {
+            return Err(invalid("The POSITION attribute of a gltf primitive must have the type VEC3".to_string()));
+        }
+        let offset = match vertex_offsets.get(&position_accessor) {
+            Some(offset) => *offset,
+            None => {
+                let offset = (gltf.positions.len() / 3) as u32;
+                gltf.positions.extend(read_accessor(&document, &buffers, position_accessor)?);
+                vertex_offsets.insert(position_accessor, offset);
+                offset
+            }
+        };
+        let num_vertices = read_accessor_count(&document, position_accessor)? as u32;
+
+        let indices: Vec<u32> = match primitive["indices"].as_u64() {
+            Some(index_accessor) => {
+                if accessor(&document, index_accessor as usize)?["type"].as_str() != Some("SCALAR") {
+                    return Err(invalid("The indices of a gltf primitive must have the type SCALAR".to_string()));
+                }
+                read_accessor(&document, &buffers, index_accessor as usize)?.into_iter().map(|index| index as u32).collect()
+            },
+            None => (0..num_vertices).collect()
+        };
+        if let Some(index) = indices.iter().find(|index| **index >= num_vertices) {
+            return Err(invalid(format!("The vertex index {} is out of range", index)));
+        }
+        let indices = &indices[..indices.len() / 3 * 3];
+        gltf.indices.extend(indices.iter().map(|index| offset + index));
+        gltf.primitives.resize(gltf.primitives.len() + indices.len() / 3, primitive_index);
+    }
+    if gltf.primitives.is_empty() {
+        return Err(invalid("The first mesh in the gltf file does not contain any triangles".to_string()));
+    }
+    Ok(gltf)
+}
+
+/// Returns the json chunk and the binary chunk, if present, of a .glb file.
+fn parse_glb(bytes: &[u8]) -> Result<(&[u8], Option<&[u8]>), Error> {
+    let read_u32 = |offset: usize| bytes.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+        .ok_or_else(|| invalid("Unexpected end of glb file".to_string()));
+    if read_u32(4)? != 2 {
+        return Err(invalid("Only version 2 of the glb format is supported".to_string()));
+    }
+
+    let mut chunks = Vec::new();
+    let mut offset = 12;
+    while offset < bytes.len().min(read_u32(8)?) {
+        let length = read_u32(offset)?;
+        let chunk_type = bytes.get(offset + 4..offset + 8).ok_or_else(|| invalid("Unexpected end of glb file".to_string()))?;
+        let data = bytes.get(offset + 8..offset + 8 + length).ok_or_else(|| invalid("Unexpected end of glb file".to_string()))?;
+        chunks.push((chunk_type, data));
+        offset += 8 + length;
+    }
+    let json = chunks.iter().find(|(chunk_type, _)| *chunk_type == b"JSON").map(|(_, data)| *data)
+        .ok_or_else(|| invalid("The glb file does not contain a json chunk".to_string()))?;
+    let binary = chunks.iter().find(|(chunk_type, _)| *chunk_type == b"BIN\0").map(|(_, data)| *data);
+    Ok((json, binary))
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value[key].as_array().map(|array| array.as_slice()).unwrap_or(&[])
+}
+
+fn accessor(document: &Value, index: usize) -> Result<&Value, Error> {
+    array(document, "accessors").get(index).ok_or_else(|| invalid(format!("The accessor {} does not exist", index)))
+}
+
+fn read_accessor_count(document: &Value, index: usize) -> Result<usize, Error> {
+    Ok(accessor(document, index)?["count"].as_u64().ok_or_else(|| invalid("A gltf accessor does not have a count".to_string()))? as usize)
+}
+
+/// Reads the elements of an accessor, with all components of all elements after each other.
+fn read_accessor(document: &Value, buffers: &[Vec<u8>], index: usize) -> Result<Vec<f64>, Error> {
+    let accessor = accessor(document, index)?;
+    let count = read_accessor_count(document, index)?;
+    let components = match accessor["type"].as_str() {
+        Some("SCALAR") => 1,
+        Some("VEC2") => 2,
+        Some("VEC3") => 3,
+        Some("VEC4") => 4,
+        t => return Err(invalid(format!("Unsupported gltf accessor type {:?}", t)))
+    };
+    let component_type = accessor["componentType"].as_u64().unwrap_or(0);
+    let component_size = match component_type {
+        5120 | 5121 => 1,
+        5122 | 5123 => 2,
+        5125 | 5126 => 4,
+        t => return Err(invalid(format!("Unsupported gltf component type {}", t)))
+    };
+    if accessor.get("sparse").is_some() {
+        return Err(invalid("Sparse gltf accessors are not supported".to_string()));
+    }
+
+    // Without sparse storage, an accessor without a buffer view contains only zeros, which never describe a triangle
+    let view_index = accessor["bufferView"].as_u64()
+        .ok_or_else(|| invalid("Gltf accessors without a buffer view are not supported".to_string()))? as usize;
+    let view = array(document, "bufferViews").get(view_index)
+        .ok_or_else(|| invalid(format!("The buffer view {} does not exist", view_index)))?;
+    let buffer = view["buffer"].as_u64().and_then(|buffer| buffers.get(buffer as usize))
+        .ok_or_else(|| invalid("A gltf buffer view refers to a buffer which does not exist".to_string()))?;
+    let element_size = components * component_size;
+    let start = view["byteOffset"].as_u64().unwrap_or(0).checked_add(accessor["byteOffset"].as_u64().unwrap_or(0));
+    let stride = view["byteStride"].as_u64().unwrap_or(element_size as u64);
+
+    if stride < element_size as u64 {
+        return Err(invalid(format!("The gltf byte stride {} is smaller than the size of an element", stride)));
+    }
+
+    // The count and offsets are read from the file, so the end of the last element is checked against the buffer before anything is allocated
+    let size = match count.checked_sub(1) {
+        Some(last) => (last as u64).checked_mul(stride).and_then(|offset| offset.checked_add(element_size as u64)),
+        None => Some(0)
+    };
+    let start = match (start, size) {
+        (Some(start), Some(size)) if start.checked_add(size).map(|end| end <= buffer.len() as u64).unwrap_or(false) => start as usize,
+        _ => return Err(invalid("A gltf accessor reads outside of its buffer".to_string()))
+    };
+    let stride = stride as usize;
+
+    let mut values = Vec::with_capacity(count * components);
+    for element in 0..count {
+        for component in 0..components {
+            let offset = start + element * stride + component * component_size;
+            let b = &buffer[offset..offset + component_size];
+            values.push(match component_type {
+                5120 => b[0] as i8 as f64,
+                5121 => b[0] as f64,
+                5122 => i16::from_le_bytes([b[0], b[1]]) as f64,
+                5123 => u16::from_le_bytes([b[0], b[1]]) as f64,
+                5125 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                _ => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64
+            });
+        }
+    }
+    Ok(values)
+}
@@ -0,0 +1,114 @@
+//!
+//! Extraction of isosurfaces from scalar fields sampled on a grid, see [MeshBuilder::isosurface](crate::mesh_builder::MeshBuilder::isosurface).
+//!
+
+use crate::mesh::math::{Vec3, InnerSpace};
+use std::collections::HashMap;
+
+/// The minimum distance from a vertex of the surface to the end points of the grid edge it is on, relative to the length of the edge.
+/// This prevents degenerate faces when the surface passes through or close to a grid point.
+const MINIMUM_FRACTION: f64 = 0.05;
+
+/// The corners of the six tetrahedra of a cube, where corner `x + 2y + 4z` is at `(x, y, z)`. All tetrahedra share the diagonal from corner 0 to 7,
+/// so the tetrahedra of neighbouring cubes share their faces.
+const TETRAHEDRA: [[usize; 4]; 6] = [[0, 1, 3, 7], [0, 1, 5, 7], [0, 2, 3, 7], [0, 2, 6, 7], [0, 4, 5, 7], [0, 4, 6, 7]];
+
+///
+/// Extracts the surface where the scalar field equals the iso value by marching tetrahedra and returns the positions and indices of the triangles.
+/// `values[x + size[0] * (y + size[1] * z)]` is the value at the grid point `origin + (x * spacing.x, y * spacing.y, z * spacing.z)`
+/// and the values below the iso value are inside.
+///
+/// Each cube of the grid is divided into six tetrahedra, which in contrast to marching cubes has no ambiguous cases.
+/// A vertex is created for each edge of the grid which the surface crosses and is shared by all triangles crossing that edge,
+/// so the result is a closed manifold surface with the faces oriented towards the outside. The vertices are kept a minimum distance
+/// from the grid points, so no faces are degenerate, even when values are exactly the iso value.
+/// The grid is surrounded by a layer of points outside the surface, so a surface which reaches the boundary of the grid is closed half a cell outside the grid.
+///
+pub(super) fn marching_tetrahedra(values: &[f64], size: [usize; 3], origin: Vec3, spacing: Vec3, iso_value: f64) -> (Vec<f64>, Vec<u32>)
+{
+    // The grid including the surrounding layer, where point (x, y, z) is point (x - 1, y - 1, z - 1) of the input
+    let padded = [size[0] + 2, size[1] + 2, size[2] + 2];
+    let index = |x: usize, y: usize, z: usize| x + padded[0] * (y + padded[1] * z);
+    let point = |index: usize| [index % padded[0], (index / padded[0]) % padded[1], index / (padded[0] * padded[1])];
+    let position = |index: usize| {
+        let [x, y, z] = point(index);
+        origin + Vec3::new(spacing.x * (x as f64 - 1.0), spacing.y * (y as f64 - 1.0), spacing.z * (z as f64 - 1.0))
+    };
+    let value = |index: usize| {
+        let [x, y, z] = point(index);
+        if (1..=size[0]).contains(&x) && (1..=size[1]).contains(&y) && (1..=size[2]).contains(&z) {
+            Some(values[x - 1 + size[0] * (y - 1 + size[1] * (z - 1))])
+        } else {
+            None
+        }
+    };
+    let is_inside = |index: usize| value(index).map(|value| value < iso_value).unwrap_or(false);
+
+    let mut positions = Vec::new();
+    let mut indices = Vec::new();
+    let mut edge_vertices = HashMap::new();
+    let mut vertex = |inside: usize, outside: usize| *edge_vertices.entry((inside.min(outside), inside.max(outside))).or_insert_with(|| {
+        let t = match (value(inside), value(outside)) {
+            (Some(inside_value), Some(outside_value)) => ((iso_value - inside_value) / (outside_value - inside_value)).max(MINIMUM_FRACTION).min(1.0 - MINIMUM_FRACTION),
+            _ => 0.5
+        };
+        let p = position(inside) + (position(outside) - position(inside)) * t;
+        positions.extend_from_slice(&[p.x, p.y, p.z]);
+        (positions.len() / 3 - 1) as u32
+    });
+
+    for z in 0..padded[2] - 1 {
+        for y in 0..padded[1] - 1 {
+            for x in 0..padded[0] - 1 {
+                let mut corners = [0; 8];
+                let mut inside = [false; 8];
+                for corner in 0..8 {
+                    corners[corner] = index(x + (corner & 1), y + ((corner >> 1) & 1), z + ((corner >> 2) & 1));
+                    inside[corner] = is_inside(corners[corner]);
+                }
+                if inside.iter().all(|inside| !inside) || inside.iter().all(|inside| *inside) {
+                    continue;
+                }
+
+                for tetrahedron in TETRAHEDRA.iter() {
+                    let (inner, outer): (Vec<usize>, Vec<usize>) = tetrahedron.iter().map(|corner| corners[*corner])
+                        .partition(|index| is_inside(*index));
+                    // The crossed edges in cyclic order around the surface
+                    let edges = match (inner.len(), outer.len()) {
+                        (1, 3) => vec![(inner[0], outer[0]), (inner[0], outer[1]), (inner[0], outer[2])],
+                        (3, 1) => vec![(inner[0], outer[0]), (inner[1], outer[0]), (inner[2], outer[0])],
+                        (2, 2) => vec![(inner[0], outer[0]), (inner[0], outer[1]), (inner[1], outer[1]), (inner[1], outer[0])],
+                        _ => continue
+                    };
+
+                    // The orientation is decided from the midpoints of the edges, which only depends on the grid and therefore never degenerates
+                    let midpoint = |(a, b): (usize, usize)| (position(a) + position(b)) * 0.5;
+                    let normal = (midpoint(edges[1]) - midpoint(edges[0])).cross(midpoint(edges[2]) - midpoint(edges[0]));
+                    let centroid = |corners: &[usize]| corners.iter().fold(Vec3::new(0.0, 0.0, 0.0), |sum, index| sum + position(*index)) / corners.len() as f64;
+                    let mut polygon = edges.iter().map(|(a, b)| vertex(*a, *b)).collect::<Vec<_>>();
+                    if normal.dot(centroid(&outer) - centroid(&inner)) < 0.0 {
+                        polygon.reverse();
+                    }
+                    for i in 1..polygon.len() - 1 {
+                        indices.extend_from_slice(&[polygon[0], polygon[i], polygon[i + 1]]);
+                    }
+                }
+            }
+        }
+    }
+    (positions, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marching_tetrahedra_single_point() {
+        // A single point inside gives an octahedron-like surface around it with a vertex on each of the 14 grid edges from the point
+        let (positions, indices) = marching_tetrahedra(&[-1.0], [1, 1, 1], Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 1.0), 0.0);
+        assert_eq!(positions.len(), 3 * 14);
+        assert_eq!(indices.len(), 3 * 24);
+        assert!(positions.iter().all(|coordinate| coordinate.abs() <= 0.5));
+    }
+}
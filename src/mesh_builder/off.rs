@@ -0,0 +1,71 @@
+//!
+//! Parser for the .off file format, see [MeshBuilder::with_off](crate::mesh_builder::MeshBuilder::with_off).
+//!
+
+use crate::mesh_builder::Error;
+
+fn invalid(message: String) -> Error {
+    Error::InvalidFile { message }
+}
+
+///
+/// Parses the .off file and returns the positions and the indices, where faces with more than three corners are triangulated as a fan.
+/// Additional data, like vertex normals and colors or face colors, is ignored.
+///
+pub(super) fn parse(source: &str) -> Result<(Vec<f64>, Vec<u32>), Error> {
+    let mut lines = source.lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter(|line| !line.is_empty());
+
+    let header = lines.next().ok_or_else(|| invalid("The off file is empty".to_string()))?;
+    let mut words = header.split_whitespace();
+    let keyword = words.next().unwrap();
+    if !keyword.ends_with("OFF") {
+        return Err(invalid(format!("An off file must start with 'OFF', not '{}'", keyword)));
+    }
+    if keyword.contains('4') || keyword.starts_with('n') {
+        return Err(invalid(format!("The off variant '{}' is not supported", keyword)));
+    }
+
+    // The counts are allowed on the same line as the keyword
+    let mut counts: Vec<&str> = words.collect();
+    if counts.is_empty() {
+        counts = lines.next().ok_or_else(|| invalid("The off file does not specify the number of vertices and faces".to_string()))?
+            .split_whitespace().collect();
+    }
+    let count = |i: usize| counts.get(i).and_then(|count| count.parse::<usize>().ok())
+        .ok_or_else(|| invalid("Invalid number of vertices or faces in off file".to_string()));
+    let (num_vertices, num_faces) = (count(0)?, count(1)?);
+
+    // The counts are not used to preallocate, since they are not validated until the data is read
+    let mut positions = Vec::new();
+    for _ in 0..num_vertices {
+        let line = lines.next().ok_or_else(|| invalid("Unexpected end of off file".to_string()))?;
+        let values: Vec<f64> = line.split_whitespace().take(3).filter_map(|word| word.parse().ok()).collect();
+        if values.len() != 3 {
+            return Err(invalid(format!("Invalid vertex in off file: '{}'", line)));
+        }
+        positions.extend(values);
+    }
+
+    let mut indices = Vec::new();
+    for _ in 0..num_faces {
+        let line = lines.next().ok_or_else(|| invalid("Unexpected end of off file".to_string()))?;
+        let mut words = line.split_whitespace().map(|word| word.parse::<u32>());
+        let corners = match words.next() {
+            Some(Ok(count)) => words.take(count as usize).collect::<Result<Vec<u32>, _>>().ok().filter(|corners| corners.len() == count as usize),
+            _ => None
+        }.ok_or_else(|| invalid(format!("Invalid face in off file: '{}'", line)))?;
+
+        if corners.len() < 3 {
+            return Err(invalid(format!("A face has {} corners, but at least three is required", corners.len())));
+        }
+        if let Some(index) = corners.iter().find(|index| **index as usize >= num_vertices) {
+            return Err(invalid(format!("The vertex index {} is out of range", index)));
+        }
+        for i in 1..corners.len() - 1 {
+            indices.extend_from_slice(&[corners[0], corners[i], corners[i + 1]]);
+        }
+    }
+    Ok((positions, indices))
+}
@@ -0,0 +1,290 @@
+//!
+//! Parser for the .ply file format, see [MeshBuilder::with_ply](crate::mesh_builder::MeshBuilder::with_ply).
+//!
+
+use crate::mesh_builder::Error;
+use crate::mesh::math::{Vec3, Vec4, vec3, vec4};
+
+/// The data extracted from a .ply file.
+pub(super) struct Ply {
+    pub positions: Vec<f64>,
+    pub indices: Vec<u32>,
+    pub normals: Option<Vec<Vec3>>,
+    pub colors: Option<Vec<Vec4>>,
+    /// The value of the requested face property for each triangle.
+    pub face_values: Option<Vec<f64>>
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Type {
+    I8, U8, I16, U16, I32, U32, F32, F64
+}
+
+impl Type {
+    fn parse(name: &str) -> Result<Type, Error> {
+        Ok(match name {
+            "char" | "int8" => Type::I8,
+            "uchar" | "uint8" => Type::U8,
+            "short" | "int16" => Type::I16,
+            "ushort" | "uint16" => Type::U16,
+            "int" | "int32" => Type::I32,
+            "uint" | "uint32" => Type::U32,
+            "float" | "float32" => Type::F32,
+            "double" | "float64" => Type::F64,
+            _ => return Err(invalid(format!("Unknown property type '{}'", name)))
+        })
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Type::I8 | Type::U8 => 1,
+            Type::I16 | Type::U16 => 2,
+            Type::I32 | Type::U32 | Type::F32 => 4,
+            Type::F64 => 8
+        }
+    }
+
+    /// The value which corresponds to full intensity of a color channel of this type.
+    fn color_scale(&self) -> f64 {
+        match self {
+            Type::U8 => 255.0,
+            Type::U16 => 65535.0,
+            _ => 1.0
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Property {
+    Scalar { name: String, data_type: Type },
+    List { name: String, count_type: Type, data_type: Type }
+}
+
+impl Property {
+    fn name(&self) -> &str {
+        match self {
+            Property::Scalar { name, .. } | Property::List { name, .. } => name
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>
+}
+
+/// Reads the values of the body of a .ply file one at a time, independent of the format.
+struct Reader<'a> {
+    format: Format,
+    bytes: &'a [u8],
+    position: usize,
+    tokens: std::str::SplitWhitespace<'a>
+}
+
+impl<'a> Reader<'a> {
+    fn new(format: Format, bytes: &'a [u8]) -> Result<Self, Error> {
+        let text = if format == Format::Ascii {
+            std::str::from_utf8(bytes).map_err(|e| invalid(format!("Ascii ply file is not valid utf8: {}", e)))?
+        } else { "" };
+        Ok(Reader { format, bytes, position: 0, tokens: text.split_whitespace() })
+    }
+
+    fn read(&mut self, data_type: Type) -> Result<f64, Error> {
+        if self.format == Format::Ascii {
+            return self.tokens.next().and_then(|token| token.parse().ok())
+                .ok_or_else(|| invalid("Missing or invalid value in ascii ply file".to_string()));
+        }
+
+        let size = data_type.size();
+        if self.position + size > self.bytes.len() {
+            return Err(invalid("Unexpected end of binary ply file".to_string()));
+        }
+        let mut buffer = [0u8; 8];
+        buffer[..size].copy_from_slice(&self.bytes[self.position..self.position + size]);
+        if self.format == Format::BinaryBigEndian {
+            buffer[..size].reverse();
+        }
+        self.position += size;
+
+        Ok(match data_type {
+            Type::I8 => buffer[0] as i8 as f64,
+            Type::U8 => buffer[0] as f64,
+            Type::I16 => i16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+            Type::U16 => u16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+            Type::I32 => i32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as f64,
+            Type::U32 => u32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as f64,
+            Type::F32 => f32::from_le_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as f64,
+            Type::F64 => f64::from_le_bytes(buffer)
+        })
+    }
+
+    /// Reads all values of the given property, which is a single value for a scalar property.
+    fn read_property(&mut self, property: &Property) -> Result<Vec<f64>, Error> {
+        match property {
+            Property::Scalar { data_type, .. } => Ok(vec![self.read(*data_type)?]),
+            Property::List { count_type, data_type, .. } => {
+                let count = self.read(*count_type)? as usize;
+                (0..count).map(|_| self.read(*data_type)).collect()
+            }
+        }
+    }
+}
+
+fn invalid(message: String) -> Error {
+    Error::InvalidFile { message }
+}
+
+/// Returns the format, the elements and the remaining bytes after the header.
+fn parse_header(bytes: &[u8]) -> Result<(Format, Vec<Element>, &[u8]), Error> {
+    const END: &[u8] = b"end_header";
+    let end = bytes.windows(END.len()).position(|window| window == END)
+        .ok_or_else(|| invalid("The ply header is not terminated by 'end_header'".to_string()))?;
+    let body_start = bytes[end..].iter().position(|byte| *byte == b'\n').map(|i| end + i + 1).unwrap_or(bytes.len());
+    let header = std::str::from_utf8(&bytes[..end]).map_err(|e| invalid(format!("The ply header is not valid utf8: {}", e)))?;
+
+    let mut lines = header.lines().map(|line| line.trim());
+    if lines.next() != Some("ply") {
+        return Err(invalid("A ply file must start with 'ply'".to_string()));
+    }
+
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    for line in lines {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["format", name, _version] => {
+                format = Some(match *name {
+                    "ascii" => Format::Ascii,
+                    "binary_little_endian" => Format::BinaryLittleEndian,
+                    "binary_big_endian" => Format::BinaryBigEndian,
+                    _ => return Err(invalid(format!("Unknown ply format '{}'", name)))
+                });
+            },
+            ["element", name, count] => {
+                let count = count.parse().map_err(|_| invalid(format!("Invalid number of elements '{}'", count)))?;
+                elements.push(Element { name: name.to_string(), count, properties: Vec::new() });
+            },
+            ["property", "list", count_type, data_type, name] => {
+                let element = elements.last_mut().ok_or_else(|| invalid("Property defined before any element".to_string()))?;
+                element.properties.push(Property::List { name: name.to_string(), count_type: Type::parse(count_type)?, data_type: Type::parse(data_type)? });
+            },
+            ["property", data_type, name] => {
+                let element = elements.last_mut().ok_or_else(|| invalid("Property defined before any element".to_string()))?;
+                element.properties.push(Property::Scalar { name: name.to_string(), data_type: Type::parse(data_type)? });
+            },
+            ["comment", ..] | ["obj_info", ..] | [] => {},
+            _ => return Err(invalid(format!("Invalid line in ply header: '{}'", line)))
+        }
+    }
+    let format = format.ok_or_else(|| invalid("The ply header does not specify the format".to_string()))?;
+    Ok((format, elements, &bytes[body_start..]))
+}
+
+///
+/// Parses the .ply file and returns positions, indices and, if present, normals and colors of the vertices.
+/// If a face property is given, the value of that property is returned for each triangle.
+///
+pub(super) fn parse(bytes: &[u8], face_property: Option<&str>) -> Result<Ply, Error> {
+    let (format, elements, body) = parse_header(bytes)?;
+    let mut reader = Reader::new(format, body)?;
+
+    let mut ply = Ply { positions: Vec::new(), indices: Vec::new(), normals: None, colors: None, face_values: None };
+    let mut num_vertices = 0;
+    for element in elements.iter() {
+        let index_of = |name: &str| element.properties.iter().position(|property| property.name() == name);
+        match element.name.as_str() {
+            "vertex" => {
+                num_vertices = element.count;
+                let position = [index_of("x"), index_of("y"), index_of("z")];
+                let normal = [index_of("nx"), index_of("ny"), index_of("nz")];
+                let color = [index_of("red"), index_of("green"), index_of("blue"), index_of("alpha")];
+                if position.iter().any(|index| index.is_none()) {
+                    return Err(invalid("The vertices must have the properties 'x', 'y' and 'z'".to_string()));
+                }
+                let has_normals = normal.iter().all(|index| index.is_some());
+                let has_colors = color[..3].iter().all(|index| index.is_some());
+
+                let color_scale = |index: Option<usize>| index.map(|i| match element.properties[i] {
+                    Property::Scalar { data_type, .. } => data_type.color_scale(),
+                    _ => 1.0
+                }).unwrap_or(1.0);
+                let scales = [color_scale(color[0]), color_scale(color[1]), color_scale(color[2]), color_scale(color[3])];
+
+                let mut normals = Vec::new();
+                let mut colors = Vec::new();
+                for _ in 0..element.count {
+                    let mut values = Vec::with_capacity(element.properties.len());
+                    for property in element.properties.iter() {
+                        values.push(reader.read_property(property)?.first().cloned().unwrap_or(0.0));
+                    }
+                    let value = |index: Option<usize>| values[index.unwrap()];
+                    ply.positions.extend_from_slice(&[value(position[0]), value(position[1]), value(position[2])]);
+                    if has_normals {
+                        normals.push(vec3(value(normal[0]), value(normal[1]), value(normal[2])));
+                    }
+                    if has_colors {
+                        let alpha = color[3].map(|i| values[i] / scales[3]).unwrap_or(1.0);
+                        colors.push(vec4(value(color[0]) / scales[0], value(color[1]) / scales[1], value(color[2]) / scales[2], alpha));
+                    }
+                }
+                if has_normals { ply.normals = Some(normals); }
+                if has_colors { ply.colors = Some(colors); }
+            },
+            "face" => {
+                let indices = index_of("vertex_indices").or_else(|| index_of("vertex_index"))
+                    .ok_or_else(|| invalid("The faces must have the property 'vertex_indices'".to_string()))?;
+                let value_index = match face_property {
+                    Some(name) => Some(index_of(name).ok_or_else(|| invalid(format!("The faces do not have the property '{}'", name)))?),
+                    None => None
+                };
+
+                let mut face_values = Vec::new();
+                for _ in 0..element.count {
+                    let mut corners = Vec::new();
+                    let mut value = 0.0;
+                    for (i, property) in element.properties.iter().enumerate() {
+                        let values = reader.read_property(property)?;
+                        if i == indices {
+                            corners = values;
+                        }
+                        else if Some(i) == value_index {
+                            value = values.first().cloned().unwrap_or(0.0);
+                        }
+                    }
+                    if corners.len() < 3 {
+                        return Err(invalid(format!("A face has {} corners, but at least three is required", corners.len())));
+                    }
+                    for i in 1..corners.len() - 1 {
+                        for corner in [corners[0], corners[i], corners[i + 1]].iter() {
+                            if *corner < 0.0 || *corner as usize >= num_vertices {
+                                return Err(invalid(format!("The vertex index {} is out of range", corner)));
+                            }
+                            ply.indices.push(*corner as u32);
+                        }
+                        face_values.push(value);
+                    }
+                }
+                if value_index.is_some() {
+                    ply.face_values = Some(face_values);
+                }
+            },
+            _ => {
+                for _ in 0..element.count {
+                    for property in element.properties.iter() {
+                        reader.read_property(property)?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(ply)
+}
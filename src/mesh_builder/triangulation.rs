@@ -0,0 +1,326 @@
+//!
+//! Triangulation of polygons with holes by ear clipping, see [MeshBuilder::with_polygons](crate::mesh_builder::MeshBuilder::with_polygons).
+//!
+
+use crate::mesh::math::{vec2, Vec2, Vec3, InnerSpace, Zero};
+
+///
+/// Returns the signed area of the polygon, which is positive if the polygon is counterclockwise.
+///
+pub(super) fn signed_area(points: &[Vec2]) -> f64
+{
+    (0..points.len()).map(|i| {
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        a.x * b.y - b.x * a.y
+    }).sum::<f64>() * 0.5
+}
+
+///
+/// Triangulates the polygon with the given outline and holes. The triangles are returned as indices into the points of the outline
+/// followed by the points of each of the holes and are oriented counterclockwise, independent of the orientation of the input.
+///
+/// The holes are connected to the outline by bridges, which turns the polygon into a single (weakly) simple polygon,
+/// and then ears, i.e. convex corners with no other vertices inside, are clipped until only a triangle is left.
+///
+pub(super) fn triangulate(outline: &[Vec2], holes: &[Vec<Vec2>]) -> Vec<[u32; 3]>
+{
+    let mut points = outline.to_vec();
+    let mut polygon: Vec<u32> = (0..outline.len() as u32).collect();
+    if signed_area(outline) < 0.0 {
+        polygon.reverse();
+    }
+
+    let mut hole_loops = Vec::new();
+    for hole in holes {
+        let offset = points.len() as u32;
+        points.extend_from_slice(hole);
+        let mut hole_loop: Vec<u32> = (offset..offset + hole.len() as u32).collect();
+        // The holes must have the opposite orientation of the outline
+        if signed_area(hole) > 0.0 {
+            hole_loop.reverse();
+        }
+        if hole_loop.len() >= 3 {
+            hole_loops.push(hole_loop);
+        }
+    }
+
+    // The holes are bridged from right to left, so a bridge never crosses a hole which is not yet bridged
+    let max_x = |hole_loop: &Vec<u32>| hole_loop.iter().map(|i| points[*i as usize].x).fold(f64::MIN, f64::max);
+    hole_loops.sort_by(|a, b| max_x(b).partial_cmp(&max_x(a)).unwrap_or(std::cmp::Ordering::Equal));
+    for hole_loop in hole_loops {
+        bridge_hole(&points, &mut polygon, &hole_loop);
+    }
+
+    clip_ears(&points, polygon)
+}
+
+///
+/// Triangulates the planar or almost planar polygon in 3D with the given outline and holes by projecting it onto the plane
+/// which fits the outline best (found by Newell's method), see [triangulate]. The triangles are oriented like the outline.
+/// If the outline is degenerate, i.e. all of its points are on a line, no triangles are returned.
+///
+pub(super) fn triangulate_3d(outline: &[Vec3], holes: &[Vec<Vec3>]) -> Vec<[u32; 3]>
+{
+    let mut normal = Vec3::zero();
+    for i in 0..outline.len() {
+        let (a, b) = (outline[i], outline[(i + 1) % outline.len()]);
+        normal += Vec3::new((a.y - b.y) * (a.z + b.z), (a.z - b.z) * (a.x + b.x), (a.x - b.x) * (a.y + b.y));
+    }
+    if normal.magnitude2() == 0.0 {
+        return Vec::new();
+    }
+    let normal = normal.normalize();
+
+    // An orthonormal basis of the plane, where the cross product of the axes is the normal
+    let axis = if normal.x.abs() <= normal.y.abs() && normal.x.abs() <= normal.z.abs() { Vec3::unit_x() }
+        else if normal.y.abs() <= normal.z.abs() { Vec3::unit_y() }
+        else { Vec3::unit_z() };
+    let u = (axis - normal * normal.dot(axis)).normalize();
+    let v = normal.cross(u);
+    let project = |points: &[Vec3]| points.iter().map(|p| vec2(p.dot(u), p.dot(v))).collect::<Vec<_>>();
+    triangulate(&project(outline), &holes.iter().map(|hole| project(hole)).collect::<Vec<_>>())
+}
+
+///
+/// Replaces each face in the .obj source with more than three corners by the triangles of its triangulation,
+/// which keeps the texture coordinate and normal indices of the corners. The .obj parser splits polygons into a fan of triangles,
+/// which is only correct for convex polygons. Faces which refer to undefined positions are left unchanged.
+///
+#[cfg(feature = "obj-io")]
+pub(super) fn triangulate_obj_faces(source: String) -> String
+{
+    let mut positions = Vec::new();
+    let mut result: Option<String> = None;
+    for (line_number, line) in source.lines().enumerate() {
+        let content = line.split('#').next().unwrap();
+        let mut tokens = content.split_whitespace();
+        let mut replacement = None;
+        match tokens.next() {
+            Some("v") => {
+                let coordinates = tokens.take(3).map(|token| token.parse::<f64>().unwrap_or(f64::NAN)).collect::<Vec<_>>();
+                positions.push(Vec3::new(coordinates.first().copied().unwrap_or(f64::NAN), coordinates.get(1).copied().unwrap_or(f64::NAN),
+                                         coordinates.get(2).copied().unwrap_or(f64::NAN)));
+            },
+            Some("f") => {
+                let corners = tokens.collect::<Vec<_>>();
+                if corners.len() > 3 {
+                    // Indices start at 1 and negative indices are relative to the last position defined so far
+                    let outline = corners.iter().map(|corner| match corner.split('/').next().unwrap().parse::<i64>() {
+                        Ok(index) if index > 0 && index as usize <= positions.len() => Some(positions[index as usize - 1]),
+                        Ok(index) if index < 0 && (-index) as usize <= positions.len() => Some(positions[(positions.len() as i64 + index) as usize]),
+                        _ => None
+                    }).collect::<Option<Vec<_>>>();
+                    let triangles = outline.map(|outline| triangulate_3d(&outline, &[])).unwrap_or_default();
+                    if !triangles.is_empty() {
+                        replacement = Some(triangles.iter()
+                            .map(|t| format!("f {} {} {}", corners[t[0] as usize], corners[t[1] as usize], corners[t[2] as usize]))
+                            .collect::<Vec<_>>().join("\n"));
+                    }
+                }
+            },
+            _ => {}
+        }
+
+        if let Some(ref mut result) = result {
+            result.push_str(replacement.as_deref().unwrap_or(line));
+            result.push('\n');
+        } else if let Some(replacement) = replacement {
+            // The source is only copied when the first face is replaced
+            let mut copy = source.lines().take(line_number).collect::<Vec<_>>().join("\n");
+            if line_number > 0 {
+                copy.push('\n');
+            }
+            copy.push_str(&replacement);
+            copy.push('\n');
+            result = Some(copy);
+        }
+    }
+    result.unwrap_or(source)
+}
+
+/// The cross product of `b - a` and `c - a`, which is positive if `a`, `b` and `c` are in counterclockwise order.
+pub(super) fn cross(a: Vec2, b: Vec2, c: Vec2) -> f64
+{
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Returns whether the point is inside or on the boundary of the counterclockwise triangle.
+fn is_in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool
+{
+    cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+}
+
+///
+/// Returns whether the point is inside the angle of the polygon at the given position of the counterclockwise polygon,
+/// so a diagonal from that corner to the point starts inside the polygon.
+///
+fn is_locally_inside(points: &[Vec2], polygon: &[u32], position: usize, p: Vec2) -> bool
+{
+    let n = polygon.len();
+    let a = points[polygon[(position + n - 1) % n] as usize];
+    let v = points[polygon[position] as usize];
+    let b = points[polygon[(position + 1) % n] as usize];
+    if cross(a, v, b) >= 0.0 {
+        cross(a, v, p) >= 0.0 && cross(v, b, p) >= 0.0
+    } else {
+        cross(a, v, p) >= 0.0 || cross(v, b, p) >= 0.0
+    }
+}
+
+///
+/// Connects the hole to the polygon with a bridge from the rightmost vertex of the hole to a visible vertex of the polygon
+/// and inserts the hole into the polygon, where the two vertices of the bridge are duplicated.
+///
+fn bridge_hole(points: &[Vec2], polygon: &mut Vec<u32>, hole_loop: &[u32])
+{
+    let start = (0..hole_loop.len()).max_by(|a, b| points[hole_loop[*a] as usize].x.partial_cmp(&points[hole_loop[*b] as usize].x)
+        .unwrap_or(std::cmp::Ordering::Equal)).unwrap();
+    let m = points[hole_loop[start] as usize];
+    let n = polygon.len();
+
+    // Find the closest edge to the right of the vertex and use its rightmost end point as candidate
+    let mut closest_x = f64::MAX;
+    let mut candidate = None;
+    for i in 0..n {
+        let (p, q) = (points[polygon[i] as usize], points[polygon[(i + 1) % n] as usize]);
+        if p.y != q.y && p.y.min(q.y) <= m.y && m.y <= p.y.max(q.y) {
+            let x = p.x + (m.y - p.y) * (q.x - p.x) / (q.y - p.y);
+            if x >= m.x && x < closest_x {
+                closest_x = x;
+                candidate = Some(if p.x > q.x { i } else { (i + 1) % n });
+            }
+        }
+    }
+    let mut candidate = match candidate {
+        Some(candidate) => candidate,
+        // The hole is not inside the polygon, so it is bridged to the closest vertex
+        None => (0..n).min_by(|a, b| (points[polygon[*a] as usize] - m).magnitude2()
+            .partial_cmp(&(points[polygon[*b] as usize] - m).magnitude2()).unwrap_or(std::cmp::Ordering::Equal)).unwrap()
+    };
+
+    // A vertex inside the triangle between the vertex, the intersection point and the candidate can block the view to the candidate,
+    // in which case the vertex in the triangle with the smallest angle to the ray is used instead
+    let p = points[polygon[candidate] as usize];
+    let intersection = Vec2::new(closest_x, m.y);
+    if closest_x < f64::MAX && p != intersection {
+        let (a, b, c) = if p.y < m.y { (m, p, intersection) } else { (m, intersection, p) };
+        let mut best_tan = f64::MAX;
+        for i in 0..n {
+            let v = points[polygon[i] as usize];
+            if i != candidate && v.x > m.x && v.x <= p.x && is_in_triangle(v, a, b, c) && is_locally_inside(points, polygon, i, m) {
+                let tan = (m.y - v.y).abs() / (v.x - m.x);
+                if tan < best_tan || (tan == best_tan && v.x > points[polygon[candidate] as usize].x) {
+                    best_tan = tan;
+                    candidate = i;
+                }
+            }
+        }
+    }
+    // If the candidate is a duplicated vertex of an earlier bridge, the duplicate which the bridge starts inside is used
+    let position = points[polygon[candidate] as usize];
+    if !is_locally_inside(points, polygon, candidate, m) {
+        if let Some(i) = (0..n).find(|i| points[polygon[*i] as usize] == position && is_locally_inside(points, polygon, *i, m)) {
+            candidate = i;
+        }
+    }
+
+    let mut inserted = Vec::with_capacity(hole_loop.len() + 2);
+    inserted.extend((0..=hole_loop.len()).map(|i| hole_loop[(start + i) % hole_loop.len()]));
+    inserted.push(polygon[candidate]);
+    polygon.splice(candidate + 1..candidate + 1, inserted);
+}
+
+///
+/// Triangulates the counterclockwise polygon by clipping ears. If no ear is found, which can happen because of rounding errors
+/// or if the polygon is self-intersecting, the most convex corner is clipped instead, so the triangulation always finishes.
+///
+fn clip_ears(points: &[Vec2], mut polygon: Vec<u32>) -> Vec<[u32; 3]>
+{
+    let mut triangles = Vec::with_capacity(polygon.len().saturating_sub(2));
+    let mut start = 0;
+    while polygon.len() > 3 {
+        let n = polygon.len();
+        let corner = |i: usize| (polygon[(i + n - 1) % n], polygon[i % n], polygon[(i + 1) % n]);
+        let position = |index: u32| points[index as usize];
+
+        let is_ear = |i: usize| {
+            let (a, v, b) = corner(i);
+            let (pa, pv, pb) = (position(a), position(v), position(b));
+            if cross(pa, pv, pb) <= 0.0 {
+                return false;
+            }
+            polygon.iter().all(|other| {
+                let p = position(*other);
+                p == pa || p == pv || p == pb || !is_in_triangle(p, pa, pv, pb)
+            })
+        };
+        // Continue from the last clipped ear, which gives better shaped triangles than always starting from the beginning
+        let ear = (start..start + n).map(|i| i % n).find(|i| is_ear(*i)).unwrap_or_else(|| {
+            (0..n).max_by(|i, j| {
+                let (a, v, b) = corner(*i);
+                let (c, w, d) = corner(*j);
+                cross(position(a), position(v), position(b)).partial_cmp(&cross(position(c), position(w), position(d)))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            }).unwrap()
+        });
+
+        let (a, v, b) = corner(ear);
+        if a != v && v != b && b != a {
+            triangles.push([a, v, b]);
+        }
+        polygon.remove(ear);
+        start = ear;
+    }
+    if polygon.len() == 3 && polygon[0] != polygon[1] && polygon[1] != polygon[2] && polygon[2] != polygon[0] {
+        triangles.push([polygon[0], polygon[1], polygon[2]]);
+    }
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::math::vec2;
+
+    fn area(points: &[Vec2], triangles: &[[u32; 3]]) -> f64 {
+        triangles.iter().map(|t| {
+            let area = 0.5 * cross(points[t[0] as usize], points[t[1] as usize], points[t[2] as usize]);
+            assert!(area >= 0.0);
+            area
+        }).sum()
+    }
+
+    #[test]
+    fn test_triangulate_concave() {
+        // A clockwise L-shape with a collinear vertex
+        let outline = vec![vec2(0.0, 0.0), vec2(0.0, 2.0), vec2(1.0, 2.0), vec2(1.0, 1.0), vec2(2.0, 1.0), vec2(2.0, 0.0), vec2(1.0, 0.0)];
+        let triangles = triangulate(&outline, &[]);
+        assert_eq!(triangles.len(), outline.len() - 2);
+        assert!((area(&outline, &triangles) - 3.0).abs() < 0.000001);
+    }
+
+    #[test]
+    fn test_triangulate_with_holes() {
+        let outline = vec![vec2(0.0, 0.0), vec2(4.0, 0.0), vec2(4.0, 4.0), vec2(0.0, 4.0)];
+        let holes = vec![
+            vec![vec2(1.0, 1.0), vec2(2.0, 1.0), vec2(2.0, 2.0), vec2(1.0, 2.0)],
+            vec![vec2(2.5, 2.5), vec2(3.5, 2.5), vec2(3.5, 3.5), vec2(2.5, 3.5)],
+            vec![vec2(2.5, 0.5), vec2(3.5, 1.0), vec2(2.5, 1.5)]
+        ];
+        let triangles = triangulate(&outline, &holes);
+        let points = outline.iter().chain(holes.iter().flatten()).cloned().collect::<Vec<_>>();
+        // Each hole adds two triangles
+        assert_eq!(triangles.len(), points.len() - 2 + 2 * holes.len());
+        assert!((area(&points, &triangles) - (16.0 - 1.0 - 1.0 - 0.5)).abs() < 0.000001);
+    }
+
+    #[cfg(feature = "obj-io")]
+    #[test]
+    fn test_triangulate_obj_faces() {
+        let source = "v 0 0 0\nv 1 0 0\nv 1 1 0\nf 1 2 3\n".to_string();
+        assert_eq!(triangulate_obj_faces(source.clone()), source);
+
+        let source = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvt 0 0\nf 1/1 2/1 3/1 4/1 # quad\ns 1";
+        assert_eq!(triangulate_obj_faces(source.to_string()), "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvt 0 0\nf 4/1 1/1 2/1\nf 2/1 3/1 4/1\ns 1\n");
+    }
+}
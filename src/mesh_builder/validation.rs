@@ -0,0 +1,263 @@
+//!
+//! Validation and repair of the input to [MeshBuilder::build](crate::mesh_builder::MeshBuilder::build)
+//! and [MeshBuilder::build_with_repair](crate::mesh_builder::MeshBuilder::build_with_repair).
+//!
+
+use crate::mesh_builder::{Error, RepairReport};
+use crate::mesh_builder::weld;
+use std::collections::{HashMap, HashSet};
+
+/// The input to the mesh construction after it has been validated or repaired.
+pub(super) struct Validated<T> {
+    pub positions: Vec<f64>,
+    pub indices: Vec<u32>,
+    pub tags: Vec<T>,
+    /// The index of the vertex built from each vertex of the input, if it is kept.
+    pub vertices: Vec<Option<u32>>,
+    /// Whether each face of the input is kept.
+    pub kept_faces: Vec<bool>
+}
+
+///
+/// Validates the positions, indices and tags. If `repair` is false, an error is returned for the first problem,
+/// otherwise the problems are fixed by removing the offending data and the changes are listed in the returned report.
+/// If a weld tolerance is given, the vertices within the tolerance of each other are welded before the faces are validated,
+/// and the faces which collapse because of the welding are handled like the other invalid faces.
+///
+pub(super) fn validate<T: Clone>(mut positions: Vec<f64>, indices: Option<Vec<u32>>, tags: Option<Vec<T>>, default_tag: T,
+                                 weld_tolerance: Option<f64>, repair: bool) -> Result<(Validated<T>, RepairReport), Error>
+{
+    let mut report = RepairReport::default();
+
+    let superfluous_coordinates = positions.len() % 3;
+    if superfluous_coordinates != 0 {
+        if !repair {
+            return Err(Error::InvalidPositions {
+                message: format!("The number of positions ({}) is not a multiple of three", positions.len())
+            });
+        }
+        report.removed_coordinates = superfluous_coordinates;
+        positions.truncate(positions.len() - report.removed_coordinates);
+    }
+    let num_vertices = positions.len() / 3;
+
+    let mut indices = indices.unwrap_or_else(|| (0..num_vertices as u32).collect());
+    let superfluous_indices = indices.len() % 3;
+    if superfluous_indices != 0 {
+        if !repair {
+            return Err(Error::InvalidIndices {
+                message: format!("The number of indices ({}) is not a multiple of three", indices.len())
+            });
+        }
+        report.removed_indices = superfluous_indices;
+        indices.truncate(indices.len() - report.removed_indices);
+    }
+    let num_faces = indices.len() / 3;
+
+    let mut tags = match tags {
+        Some(tags) => tags,
+        None => vec![default_tag.clone(); num_faces]
+    };
+    if tags.len() != num_faces {
+        if !repair {
+            return Err(Error::InvalidTags {
+                message: format!("The number of tags ({}) is not equal to the number of faces ({})", tags.len(), num_faces)
+            });
+        }
+        if tags.len() < num_faces {
+            report.added_tags = num_faces - tags.len();
+            tags.resize(num_faces, default_tag);
+        }
+        else {
+            report.removed_tags = tags.len() - num_faces;
+            tags.truncate(num_faces);
+        }
+    }
+
+    let welded = weld_tolerance.map(|tolerance| weld::weld(&positions, tolerance));
+
+    // The number of faces adjacent to each edge, where the key is the edge with the lowest vertex index first
+    let mut edges = HashMap::<(u32, u32), usize>::new();
+    // The edges of the faces in the direction of the face orientation
+    let mut directed_edges = HashSet::<(u32, u32)>::new();
+    let mut kept_faces = vec![true; num_faces];
+    for face in 0..num_faces {
+        let corners = [indices[3 * face], indices[3 * face + 1], indices[3 * face + 2]];
+
+        if let Some(index) = corners.iter().find(|index| **index as usize >= num_vertices) {
+            if !repair {
+                return Err(Error::InvalidIndices {
+                    message: format!("Face {} refers to vertex {}, but there are only {} vertices", face, index, num_vertices)
+                });
+            }
+            report.out_of_range_faces.push(face);
+            kept_faces[face] = false;
+            continue;
+        }
+
+        let is_degenerate = |corners: &[u32; 3]| corners[0] == corners[1] || corners[1] == corners[2] || corners[2] == corners[0];
+        if is_degenerate(&corners) {
+            if !repair {
+                return Err(Error::DegenerateFace {
+                    message: format!("Face {} with the vertices {:?} refers to the same vertex more than once", face, corners)
+                });
+            }
+            report.degenerate_faces.push(face);
+            kept_faces[face] = false;
+            continue;
+        }
+
+        let corners = match welded {
+            Some(ref welded) => [welded[corners[0] as usize] as u32, welded[corners[1] as usize] as u32, welded[corners[2] as usize] as u32],
+            None => corners
+        };
+        if is_degenerate(&corners) {
+            if !repair {
+                return Err(Error::DegenerateFace {
+                    message: format!("Face {} with the vertices {:?} collapses because two of its vertices are welded", face, &indices[3 * face..3 * face + 3])
+                });
+            }
+            report.welded_faces.push(face);
+            kept_faces[face] = false;
+            continue;
+        }
+        indices[3 * face..3 * face + 3].copy_from_slice(&corners);
+
+        let edge = |a: u32, b: u32| (a.min(b), a.max(b));
+        let face_edges = [edge(corners[0], corners[1]), edge(corners[1], corners[2]), edge(corners[2], corners[0])];
+        if let Some((a, b)) = face_edges.iter().find(|edge| edges.get(edge).copied().unwrap_or(0) >= 2) {
+            if !repair {
+                return Err(Error::NonManifoldEdge {
+                    message: format!("The edge between vertex {} and {} of face {} is already shared by two other faces", a, b, face)
+                });
+            }
+            report.non_manifold_faces.push(face);
+            kept_faces[face] = false;
+            continue;
+        }
+        let face_directed_edges = [(corners[0], corners[1]), (corners[1], corners[2]), (corners[2], corners[0])];
+        if let Some((a, b)) = face_directed_edges.iter().find(|edge| directed_edges.contains(edge)) {
+            if !repair {
+                return Err(Error::NonManifoldEdge {
+                    message: format!("The edge from vertex {} to {} of face {} is already used in the same direction by another face", a, b, face)
+                });
+            }
+            report.non_manifold_faces.push(face);
+            kept_faces[face] = false;
+            continue;
+        }
+        for edge in face_edges.iter() {
+            *edges.entry(*edge).or_insert(0) += 1;
+        }
+        directed_edges.extend(face_directed_edges.iter());
+    }
+
+    // Removing faces can split the faces around another vertex, so the vertices are checked until they are all manifold
+    while let Some((vertex, faces)) = find_non_manifold_vertex(&indices, &kept_faces) {
+        if !repair {
+            return Err(Error::NonManifoldVertex {
+                message: format!("The faces {:?} are only connected to the other faces around vertex {} through the vertex", faces, vertex)
+            });
+        }
+        for face in faces {
+            report.non_manifold_vertex_faces.push(face);
+            kept_faces[face] = false;
+        }
+    }
+    report.non_manifold_vertex_faces.sort_unstable();
+
+    if kept_faces.iter().any(|kept| !kept) {
+        let mut kept = kept_faces.iter();
+        tags.retain(|_| *kept.next().unwrap());
+        indices = indices.chunks(3).zip(kept_faces.iter()).filter(|(_, kept)| **kept).flat_map(|(face, _)| face.to_vec()).collect();
+    }
+
+    // Vertices which are not part of any face are not allowed in a mesh, so they are removed when repairing and when they are welded into another vertex
+    let mut kept_vertices = vec![!repair; num_vertices];
+    if let Some(ref welded) = welded {
+        for vertex in 0..num_vertices {
+            kept_vertices[vertex] &= welded[vertex] == vertex;
+        }
+    }
+    for index in indices.iter() {
+        kept_vertices[*index as usize] = true;
+    }
+    if repair {
+        report.unreferenced_vertices = (0..num_vertices)
+            .filter(|vertex| !kept_vertices[*vertex] && welded.as_ref().map(|welded| welded[*vertex] == *vertex).unwrap_or(true))
+            .collect();
+    }
+
+    let mut vertices = vec![None; num_vertices];
+    let mut count = 0;
+    for vertex in 0..num_vertices {
+        if kept_vertices[vertex] {
+            vertices[vertex] = Some(count);
+            positions.copy_within(3 * vertex..3 * vertex + 3, 3 * count as usize);
+            count += 1;
+        }
+    }
+    positions.truncate(3 * count as usize);
+    for index in indices.iter_mut() {
+        *index = vertices[*index as usize].unwrap();
+    }
+    if let Some(ref welded) = welded {
+        for vertex in 0..num_vertices {
+            vertices[vertex] = vertices[welded[vertex]];
+        }
+    }
+    Ok((Validated { positions, indices, tags, vertices, kept_faces }, report))
+}
+
+///
+/// Finds the first vertex where the faces around the vertex do not form one fan, i.e. where some of the faces are only connected to each other
+/// through the vertex, and returns the vertex and the faces which are not in the fan containing the first face around the vertex.
+/// The faces must be manifold along the edges and have consistent orientations.
+///
+fn find_non_manifold_vertex(indices: &[u32], kept_faces: &[bool]) -> Option<(u32, Vec<usize>)>
+{
+    let faces = (0..kept_faces.len()).filter(|face| kept_faces[*face]).collect::<Vec<_>>();
+    // The corner of a face at a vertex is 3 * face + i, where indices[3 * face + i] is the vertex
+    let mut corner_of_edge = HashMap::new();
+    for face in faces.iter() {
+        for i in 0..3 {
+            corner_of_edge.insert((indices[3 * face + i], indices[3 * face + (i + 1) % 3]), 3 * face + i);
+        }
+    }
+
+    // The corners around a vertex are in the same fan if their faces share an edge
+    let mut parent = (0..indices.len()).collect::<Vec<_>>();
+    fn root(parent: &mut [usize], corner: usize) -> usize {
+        let mut corner = corner;
+        while parent[corner] != corner {
+            parent[corner] = parent[parent[corner]];
+            corner = parent[corner];
+        }
+        corner
+    }
+    for face in faces.iter() {
+        for i in 0..3 {
+            let corner = 3 * face + i;
+            let previous = indices[3 * face + (i + 2) % 3];
+            // The other face of the edge from the previous vertex to this vertex has a corner at this vertex followed by the previous vertex
+            if let Some(other) = corner_of_edge.get(&(indices[corner], previous)) {
+                let (a, b) = (root(&mut parent, corner), root(&mut parent, *other));
+                parent[a] = b;
+            }
+        }
+    }
+
+    let mut fans = HashMap::new();
+    for face in faces.iter() {
+        for i in 0..3 {
+            let corner = 3 * face + i;
+            let fan = root(&mut parent, corner);
+            fans.entry(indices[corner]).or_insert_with(Vec::new).push((face, fan));
+        }
+    }
+    fans.into_iter().filter_map(|(vertex, corners)| {
+        let first_fan = corners[0].1;
+        let other_faces = corners.iter().filter(|(_, fan)| *fan != first_fan).map(|(face, _)| **face).collect::<Vec<_>>();
+        if other_faces.is_empty() { None } else { Some((vertex, other_faces)) }
+    }).min_by_key(|(vertex, _)| *vertex)
+}
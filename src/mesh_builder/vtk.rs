@@ -0,0 +1,227 @@
+//!
+//! Parser for the legacy .vtk file format, see [MeshBuilder::with_vtk](crate::mesh_builder::MeshBuilder::with_vtk).
+//!
+
+use crate::mesh_builder::Error;
+
+/// The data extracted from a legacy .vtk file.
+pub(super) struct Vtk {
+    pub positions: Vec<f64>,
+    pub indices: Vec<u32>,
+    /// The scalar fields of the points with a single component.
+    pub point_scalars: Vec<(String, Vec<f64>)>
+}
+
+fn invalid(message: String) -> Error {
+    Error::InvalidFile { message }
+}
+
+/// The number of values of `count` elements with `size` values each, where the numbers are read from the file and can therefore overflow.
+fn num_values(count: usize, size: usize) -> Result<usize, Error> {
+    count.checked_mul(size).ok_or_else(|| invalid(format!("Invalid number of elements ({}) in vtk file", count)))
+}
+
+/// Reads the whitespace separated tokens of the file.
+struct Tokens<'a> {
+    tokens: std::str::SplitWhitespace<'a>
+}
+
+impl<'a> Tokens<'a> {
+    fn keyword(&mut self) -> Option<String> {
+        self.tokens.next().map(|token| token.to_uppercase())
+    }
+
+    fn string(&mut self) -> Result<&'a str, Error> {
+        self.tokens.next().ok_or_else(|| invalid("Unexpected end of vtk file".to_string()))
+    }
+
+    fn number<N: std::str::FromStr>(&mut self) -> Result<N, Error> {
+        let token = self.string()?;
+        token.parse().map_err(|_| invalid(format!("Expected a number in vtk file, found '{}'", token)))
+    }
+
+    fn numbers<N: std::str::FromStr>(&mut self, count: usize) -> Result<Vec<N>, Error> {
+        (0..count).map(|_| self.number()).collect()
+    }
+
+    fn skip(&mut self, count: usize) -> Result<(), Error> {
+        for _ in 0..count {
+            self.string()?;
+        }
+        Ok(())
+    }
+}
+
+///
+/// Parses the cells of a `POLYGONS` or `TRIANGLE_STRIPS` section, both in the classic format with the size of each cell
+/// in front of the indices and in the format with `OFFSETS` and `CONNECTIVITY` arrays from version 5 of the file format.
+///
+fn parse_cells(tokens: &mut Tokens<'_>) -> Result<Vec<Vec<u32>>, Error> {
+    let count: usize = tokens.number()?;
+    let size: usize = tokens.number()?;
+    // The count is not used to preallocate, since it is not validated until the cells are read
+    let mut cells = Vec::new();
+
+    let mut lookahead = tokens.tokens.clone();
+    if lookahead.next().map(|token| token.to_uppercase()) == Some("OFFSETS".to_string()) {
+        tokens.skip(2)?;
+        let offsets: Vec<usize> = tokens.numbers(count)?;
+        if tokens.keyword().as_deref() != Some("CONNECTIVITY") {
+            return Err(invalid("Expected CONNECTIVITY after OFFSETS in vtk file".to_string()));
+        }
+        tokens.skip(1)?;
+        let connectivity: Vec<u32> = tokens.numbers(size)?;
+        for window in offsets.windows(2) {
+            cells.push(connectivity.get(window[0]..window[1]).ok_or_else(|| invalid("Invalid cell offsets in vtk file".to_string()))?.to_vec());
+        }
+    }
+    else {
+        for _ in 0..count {
+            let corners = tokens.number()?;
+            cells.push(tokens.numbers(corners)?);
+        }
+    }
+    Ok(cells)
+}
+
+///
+/// Skips the values of an attribute section of `POINT_DATA` or `CELL_DATA` with the given number of elements.
+/// Returns the name and values of the attribute if it is a scalar field with a single component.
+///
+fn parse_attribute(keyword: &str, tokens: &mut Tokens<'_>, count: usize) -> Result<Option<(String, Vec<f64>)>, Error> {
+    match keyword {
+        "SCALARS" => {
+            let name = tokens.string()?.to_string();
+            tokens.skip(1)?;
+            let mut lookahead = tokens.tokens.clone();
+            let components = match lookahead.next() {
+                Some(token) if token.parse::<usize>().is_ok() => tokens.number()?,
+                _ => 1
+            };
+            let mut lookahead = tokens.tokens.clone();
+            if lookahead.next().map(|token| token.to_uppercase()) == Some("LOOKUP_TABLE".to_string()) {
+                tokens.skip(2)?;
+            }
+            let values = tokens.numbers(num_values(count, components)?)?;
+            Ok(if components == 1 { Some((name, values)) } else { None })
+        },
+        "VECTORS" | "NORMALS" => {
+            tokens.skip(2)?;
+            tokens.skip(num_values(count, 3)?)?;
+            Ok(None)
+        },
+        "TEXTURE_COORDINATES" => {
+            tokens.skip(1)?;
+            let dimension: usize = tokens.number()?;
+            tokens.skip(1)?;
+            tokens.skip(num_values(count, dimension)?)?;
+            Ok(None)
+        },
+        "COLOR_SCALARS" => {
+            tokens.skip(1)?;
+            let components: usize = tokens.number()?;
+            tokens.skip(num_values(count, components)?)?;
+            Ok(None)
+        },
+        "LOOKUP_TABLE" => {
+            tokens.skip(1)?;
+            let size: usize = tokens.number()?;
+            tokens.skip(num_values(size, 4)?)?;
+            Ok(None)
+        },
+        "FIELD" => {
+            tokens.skip(1)?;
+            let arrays: usize = tokens.number()?;
+            for _ in 0..arrays {
+                tokens.skip(1)?;
+                let components: usize = tokens.number()?;
+                let tuples: usize = tokens.number()?;
+                tokens.skip(1)?;
+                tokens.skip(num_values(tuples, components)?)?;
+            }
+            Ok(None)
+        },
+        _ => Err(invalid(format!("Unsupported section '{}' in vtk file", keyword)))
+    }
+}
+
+///
+/// Parses the ASCII legacy .vtk file with a polydata dataset and returns the positions, the indices of the polygons and triangle strips,
+/// where polygons are triangulated as a fan, and the scalar fields of the points.
+///
+pub(super) fn parse(source: &str) -> Result<Vtk, Error> {
+    let mut lines = source.lines();
+    let version = lines.next().unwrap_or("");
+    if !version.trim().to_lowercase().starts_with("# vtk datafile") {
+        return Err(invalid("A vtk file must start with '# vtk DataFile Version'".to_string()));
+    }
+    let _title = lines.next();
+    match lines.next().map(|line| line.trim().to_uppercase()) {
+        Some(ref format) if format == "ASCII" => {},
+        Some(ref format) if format == "BINARY" => return Err(invalid("Only ASCII vtk files are supported".to_string())),
+        _ => return Err(invalid("A vtk file must specify the format as ASCII or BINARY".to_string()))
+    }
+
+    let rest = source.splitn(4, '\n').nth(3).unwrap_or("");
+    let mut tokens = Tokens { tokens: rest.split_whitespace() };
+    if tokens.keyword().as_deref() != Some("DATASET") || tokens.keyword().as_deref() != Some("POLYDATA") {
+        return Err(invalid("Only vtk files with a POLYDATA dataset are supported".to_string()));
+    }
+
+    let mut vtk = Vtk { positions: Vec::new(), indices: Vec::new(), point_scalars: Vec::new() };
+    let mut num_vertices = 0;
+    // The number of elements of the POINT_DATA or CELL_DATA section which is currently read and whether it is POINT_DATA
+    let mut data = (0, false);
+    while let Some(keyword) = tokens.keyword() {
+        match keyword.as_str() {
+            "POINTS" => {
+                num_vertices = tokens.number()?;
+                tokens.skip(1)?;
+                vtk.positions = tokens.numbers(num_values(num_vertices, 3)?)?;
+            },
+            "POLYGONS" => {
+                for corners in parse_cells(&mut tokens)? {
+                    if corners.len() < 3 {
+                        return Err(invalid(format!("A polygon has {} corners, but at least three is required", corners.len())));
+                    }
+                    for i in 1..corners.len() - 1 {
+                        vtk.indices.extend_from_slice(&[corners[0], corners[i], corners[i + 1]]);
+                    }
+                }
+            },
+            "TRIANGLE_STRIPS" => {
+                for corners in parse_cells(&mut tokens)? {
+                    for i in 0..corners.len().saturating_sub(2) {
+                        // Every second triangle in a strip is flipped to keep a consistent orientation
+                        if i % 2 == 0 {
+                            vtk.indices.extend_from_slice(&[corners[i], corners[i + 1], corners[i + 2]]);
+                        } else {
+                            vtk.indices.extend_from_slice(&[corners[i + 1], corners[i], corners[i + 2]]);
+                        }
+                    }
+                }
+            },
+            "VERTICES" | "LINES" => {
+                parse_cells(&mut tokens)?;
+            },
+            "POINT_DATA" => data = (tokens.number()?, true),
+            "CELL_DATA" => data = (tokens.number()?, false),
+            "METADATA" => {
+                // Metadata is terminated by an empty line, which is not visible in the tokens, so the rest of the file is ignored
+                break;
+            },
+            _ => {
+                if let Some(scalars) = parse_attribute(&keyword, &mut tokens, data.0)? {
+                    if data.1 {
+                        vtk.point_scalars.push(scalars);
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(index) = vtk.indices.iter().find(|index| **index as usize >= num_vertices) {
+        return Err(invalid(format!("The vertex index {} is out of range", index)));
+    }
+    Ok(vtk)
+}
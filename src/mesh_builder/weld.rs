@@ -0,0 +1,73 @@
+//!
+//! Welding of vertices within a distance tolerance, see [MeshBuilder::with_welding](crate::mesh_builder::MeshBuilder::with_welding).
+//!
+
+use std::collections::HashMap;
+
+///
+/// Returns the vertex which each vertex is welded into, which is the first vertex within the tolerance of it, or the vertex itself.
+/// The vertices are sorted into a grid of cells with the tolerance as size, so only the vertices in the neighbouring cells are compared.
+///
+pub(super) fn weld(positions: &[f64], tolerance: f64) -> Vec<usize>
+{
+    let num_vertices = positions.len() / 3;
+    let position = |vertex: usize| [positions[3 * vertex], positions[3 * vertex + 1], positions[3 * vertex + 2]];
+    let mut welded = Vec::with_capacity(num_vertices);
+
+    if tolerance <= 0.0 {
+        // Only weld vertices with exactly the same position, where -0.0 and 0.0 are considered equal
+        let key = |coordinate: f64| if coordinate == 0.0 { 0 } else { coordinate.to_bits() };
+        let mut vertices = HashMap::new();
+        for vertex in 0..num_vertices {
+            let p = position(vertex);
+            let key = [key(p[0]), key(p[1]), key(p[2])];
+            welded.push(*vertices.entry(key).or_insert(vertex));
+        }
+        return welded;
+    }
+
+    // The cell coordinates saturate for coordinates which are very large compared to the tolerance
+    let cell = |coordinate: f64| (coordinate / tolerance).floor() as i64;
+    let mut grid = HashMap::<[i64; 3], Vec<usize>>::new();
+    for vertex in 0..num_vertices {
+        let p = position(vertex);
+        let cell = [cell(p[0]), cell(p[1]), cell(p[2])];
+        let mut target = None;
+        'search: for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    for other in grid.get(&[cell[0].saturating_add(dx), cell[1].saturating_add(dy), cell[2].saturating_add(dz)]).into_iter().flatten() {
+                        let q = position(*other);
+                        let distance_squared = (p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2) + (p[2] - q[2]).powi(2);
+                        if distance_squared <= tolerance * tolerance {
+                            target = Some(*other);
+                            break 'search;
+                        }
+                    }
+                }
+            }
+        }
+        welded.push(target.unwrap_or_else(|| {
+            grid.entry(cell).or_default().push(vertex);
+            vertex
+        }));
+    }
+    welded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weld() {
+        let positions = vec![0.0, 0.0, 0.0,  1.0, 0.0, 0.0,  -0.0, 0.0, 0.0,  1.05, 0.0, 0.0,  0.99, 0.02, 0.0,  2.0, 0.0, 0.0];
+        assert_eq!(weld(&positions, 0.0), vec![0, 1, 0, 3, 4, 5]);
+        assert_eq!(weld(&positions, 0.1), vec![0, 1, 0, 1, 1, 5]);
+        assert_eq!(weld(&positions, 1.5), vec![0, 0, 0, 0, 0, 5]);
+
+        // Coordinates which are very large compared to the tolerance
+        let positions = vec![1e6, 0.0, 0.0,  0.0, 1e6, 0.0,  0.0, 0.0, 1e6,  -1e300, 0.0, 0.0,  1e6, 0.0, 0.0];
+        assert_eq!(weld(&positions, 1e-14), vec![0, 1, 2, 3, 0]);
+    }
+}
@@ -8,6 +8,10 @@ pub use crate::mesh::ids::*;
 pub use crate::mesh::traversal::Walker;
 pub use crate::mesh::iterators::*;
 pub use crate::mesh::intersection::*;
+#[cfg(feature = "obj-io")]
+pub use crate::mesh::material::Material;
+#[cfg(feature = "3d-io")]
+pub use crate::mesh::format_3d::{Options3d, Precision, Compression};
 
 pub use crate::mesh::Mesh;
 
@@ -0,0 +1,11 @@
+//! Helpers shared by the tests.
+
+/// Returns a generator of pseudo-random numbers in the range [0, 1) which is deterministic for a given seed.
+pub(crate) fn random(seed: u64) -> impl FnMut() -> f64
+{
+    let mut seed = seed;
+    move || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 11) as f64 / (1u64 << 53) as f64
+    }
+}